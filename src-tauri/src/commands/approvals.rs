@@ -0,0 +1,279 @@
+use chrono::{DateTime, Duration, Utc};
+use crate::commands::{devices, inventory, settings};
+use crate::models::{PendingData, PendingDevice, TrustDecisionKind, UsbDevice};
+use crate::services::{backend, storage};
+
+const PENDING_FILE: &str = "pending.json";
+
+fn read_pending() -> Result<PendingData, String> {
+    storage::read_json(PENDING_FILE).map_err(|e| e.to_string())
+}
+
+fn write_pending(data: &PendingData) -> Result<(), String> {
+    storage::write_json(PENDING_FILE, data).map_err(|e| e.to_string())
+}
+
+/// Name of the logged-in user making a decision
+pub fn current_user() -> String {
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Disables a device and queues it for an operator decision.
+/// A device that is already queued keeps its original deadline.
+pub fn enqueue(device: &UsbDevice) -> Result<PendingDevice, String> {
+    let mut data = read_pending()?;
+    if let Some(existing) = data.devices.iter().find(|p| p.device.instance_id == device.instance_id) {
+        return Ok(existing.clone());
+    }
+
+    devices::disable_device(device.instance_id.clone())?;
+
+    let timeout = settings::read_settings()?.approval_timeout_secs;
+    let now = Utc::now();
+    let pending = PendingDevice {
+        device: device.clone(),
+        requested_at: now.to_rfc3339(),
+        expires_at: (now + Duration::seconds(timeout as i64)).to_rfc3339(),
+    };
+    data.devices.push(pending.clone());
+    write_pending(&data)?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "WARN".to_string(),
+        format!("Device awaiting approval: {}", device.friendly_name),
+        Some(device.instance_id.clone()),
+    );
+
+    Ok(pending)
+}
+
+/// Removes a device from the queue, failing if it isn't queued
+fn take(instance_id: &str) -> Result<PendingDevice, String> {
+    let mut data = read_pending()?;
+    let index = data.devices
+        .iter()
+        .position(|p| p.device.instance_id == instance_id)
+        .ok_or_else(|| format!("Device is not awaiting approval: {}", instance_id))?;
+
+    let pending = data.devices.remove(index);
+    write_pending(&data)?;
+    Ok(pending)
+}
+
+/// Denies every request whose deadline has passed; the devices are already disabled
+pub fn expire_pending() -> Result<Vec<PendingDevice>, String> {
+    let mut data = read_pending()?;
+    let now = Utc::now();
+    let (expired, kept): (Vec<PendingDevice>, Vec<PendingDevice>) = data.devices
+        .into_iter()
+        .partition(|p| {
+            DateTime::parse_from_rfc3339(&p.expires_at)
+                .map(|t| t <= now)
+                .unwrap_or(true)
+        });
+
+    if expired.is_empty() {
+        return Ok(expired);
+    }
+
+    data.devices = kept;
+    write_pending(&data)?;
+
+    for pending in &expired {
+        let _ = crate::commands::logs::add_event_log(
+            "BLOCK".to_string(),
+            format!("Approval timed out, device denied: {}", pending.device.friendly_name),
+            Some(pending.device.instance_id.clone()),
+        );
+        let _ = inventory::record_decision(&pending.device.instance_id, TrustDecisionKind::TimedOut);
+    }
+
+    Ok(expired)
+}
+
+/// Drops the request for a device that was unplugged before anyone decided
+pub fn withdraw(instance_id: &str) -> Result<(), String> {
+    let Ok(pending) = take(instance_id) else {
+        return Ok(());
+    };
+
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Approval request withdrawn, device removed: {}", pending.device.friendly_name),
+        Some(pending.device.instance_id),
+    );
+
+    Ok(())
+}
+
+/// Gets the devices waiting for an approval decision
+#[tauri::command]
+pub fn get_pending_devices() -> Result<Vec<PendingDevice>, String> {
+    Ok(read_pending()?.devices)
+}
+
+/// Approves a pending device and re-enables it, optionally trusting it (until `expires_at`)
+#[tauri::command]
+pub fn approve_device(
+    instance_id: String,
+    add_to_whitelist: bool,
+    expires_at: Option<String>,
+) -> Result<(), String> {
+    if expires_at.is_some() && !add_to_whitelist {
+        return Err("An expiry only applies when the device is added to the whitelist".to_string());
+    }
+
+    let device = read_pending()?
+        .devices
+        .into_iter()
+        .find(|p| p.device.instance_id == instance_id)
+        .map(|p| p.device)
+        .ok_or_else(|| format!("Device is not awaiting approval: {}", instance_id))?;
+
+    crate::commands::whitelist::validate_schedule(expires_at.as_deref(), &[])?;
+
+    // Enable before trusting so a failed enable leaves the device untrusted and still pending
+    devices::enable_device(instance_id.clone())?;
+    if add_to_whitelist {
+        if let Err(e) = crate::commands::whitelist::add_to_whitelist(device.clone(), expires_at, None) {
+            let _ = backend::current().disable_device(&instance_id);
+            return Err(e);
+        }
+    }
+    take(&instance_id)?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Device approved by {}: {}", current_user(), device.friendly_name),
+        Some(instance_id.clone()),
+    );
+    let _ = inventory::record_decision(&instance_id, TrustDecisionKind::Approved);
+
+    Ok(())
+}
+
+/// Rejects a pending device; it stays disabled
+#[tauri::command]
+pub fn reject_device(instance_id: String) -> Result<(), String> {
+    let pending = take(&instance_id)?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "BLOCK".to_string(),
+        format!("Device rejected by {}: {}", current_user(), pending.device.friendly_name),
+        Some(instance_id.clone()),
+    );
+    let _ = inventory::record_decision(&instance_id, TrustDecisionKind::Rejected);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::logs::get_event_logs;
+    use crate::commands::whitelist::get_whitelist;
+    use crate::models::Settings;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    const CRUZER: &str = "USB\\VID_0781&PID_5567\\4C530001";
+
+    fn device(instance_id: &str) -> UsbDevice {
+        UsbDevice { instance_id: instance_id.to_string(), friendly_name: "SanDisk Cruzer".to_string(), ..Default::default() }
+    }
+
+    fn pending_ids() -> Vec<String> {
+        get_pending_devices().unwrap().into_iter().map(|p| p.device.instance_id).collect()
+    }
+
+    fn has_log(level: &str, message: &str) -> bool {
+        get_event_logs().unwrap().iter().any(|l| l.level == level && l.message == message)
+    }
+
+    #[test]
+    fn queued_device_is_disabled_and_keeps_its_deadline() {
+        let env = TestEnv::new(FixtureBackend::new());
+        let first = enqueue(&device(CRUZER)).unwrap();
+        let again = enqueue(&device(CRUZER)).unwrap();
+
+        assert_eq!(first.expires_at, again.expires_at);
+        assert_eq!(pending_ids(), vec![CRUZER]);
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", CRUZER)]);
+        assert!(has_log("WARN", "Device awaiting approval: SanDisk Cruzer"));
+    }
+
+    #[test]
+    fn approval_enables_and_trusts_the_device() {
+        let env = TestEnv::new(FixtureBackend::new());
+        enqueue(&device(CRUZER)).unwrap();
+        approve_device(CRUZER.to_string(), true, Some("2099-01-01T00:00:00Z".to_string())).unwrap();
+
+        assert!(pending_ids().is_empty());
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", CRUZER), format!("enable_device:{}", CRUZER)]);
+        let whitelist = get_whitelist().unwrap();
+        assert_eq!(whitelist.len(), 1);
+        assert_eq!(whitelist[0].expires_at.as_deref(), Some("2099-01-01T00:00:00Z"));
+        assert!(has_log("INFO", &format!("Device approved by {}: SanDisk Cruzer", current_user())));
+    }
+
+    #[test]
+    fn approval_without_trust_only_enables() {
+        let env = TestEnv::new(FixtureBackend::new());
+        enqueue(&device(CRUZER)).unwrap();
+        approve_device(CRUZER.to_string(), false, None).unwrap();
+
+        assert!(pending_ids().is_empty());
+        assert!(get_whitelist().unwrap().is_empty());
+        assert_eq!(env.backend.actions().last(), Some(&format!("enable_device:{}", CRUZER)));
+    }
+
+    #[test]
+    fn invalid_approval_changes_nothing() {
+        let env = TestEnv::new(FixtureBackend::new());
+        enqueue(&device(CRUZER)).unwrap();
+
+        assert_eq!(approve_device(CRUZER.to_string(), false, Some("2099-01-01T00:00:00Z".to_string())), Err("An expiry only applies when the device is added to the whitelist".to_string()));
+        assert!(approve_device(CRUZER.to_string(), true, Some("tomorrow".to_string())).is_err());
+        assert!(approve_device("USB\\VID_1234&PID_5678\\1".to_string(), true, None).is_err());
+
+        // Still disabled, untrusted and waiting for a decision
+        assert_eq!(pending_ids(), vec![CRUZER]);
+        assert!(get_whitelist().unwrap().is_empty());
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", CRUZER)]);
+    }
+
+    #[test]
+    fn rejected_device_stays_disabled() {
+        let env = TestEnv::new(FixtureBackend::new());
+        enqueue(&device(CRUZER)).unwrap();
+        reject_device(CRUZER.to_string()).unwrap();
+
+        assert!(pending_ids().is_empty());
+        assert!(get_whitelist().unwrap().is_empty());
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", CRUZER)]);
+        assert!(has_log("BLOCK", &format!("Device rejected by {}: SanDisk Cruzer", current_user())));
+        assert!(reject_device(CRUZER.to_string()).is_err());
+    }
+
+    #[test]
+    fn timed_out_request_is_denied() {
+        let env = TestEnv::new(FixtureBackend::new());
+        enqueue(&device(CRUZER)).unwrap();
+        assert!(expire_pending().unwrap().is_empty());
+
+        // A zero timeout puts the deadline at the moment the device was queued
+        let other = "USB\\VID_1234&PID_5678\\1";
+        storage::write_json("settings.json", &Settings { approval_timeout_secs: 0, ..Default::default() }).unwrap();
+        enqueue(&device(other)).unwrap();
+
+        let expired = expire_pending().unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].device.instance_id, other);
+        assert_eq!(pending_ids(), vec![CRUZER]);
+        assert!(!env.backend.actions().iter().any(|a| a.starts_with("enable_device")));
+        assert!(has_log("BLOCK", "Approval timed out, device denied: SanDisk Cruzer"));
+        assert!(approve_device(other.to_string(), false, None).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::models::{BluetoothDevice, TrustDecisionKind, UsbDevice};
+use crate::services::backend::{self, SystemBackend};
+use crate::services::bluetooth::{self, BluetoothRecord};
+use crate::commands::whitelist::is_device_trusted;
+use crate::commands::{inventory, logs, policy};
+
+/// Gets paired and connected Bluetooth devices with their trust status
+#[tauri::command]
+pub fn get_bluetooth_devices() -> Result<Vec<BluetoothDevice>, String> {
+    list_devices(backend::current().as_ref())
+}
+
+/// Lists the Bluetooth devices reported by a backend, marking whitelisted ones as trusted
+/// and recording the policy outcome for each
+pub fn list_devices(backend: &dyn SystemBackend) -> Result<Vec<BluetoothDevice>, String> {
+    let json_output = backend.bluetooth_devices_json()
+        .map_err(|e| e.to_string())?;
+    let records: Vec<BluetoothRecord> = backend::parse_list(&json_output)?;
+
+    let device_policy = policy::read_policy().unwrap_or_default();
+    let mut devices: Vec<BluetoothDevice> = records.into_iter().filter_map(to_device).collect();
+    for bt in &mut devices {
+        bt.device.is_trusted = is_device_trusted(&bt.device).unwrap_or(false);
+        bt.device.policy_action = Some(policy::evaluate(&device_policy, &bt.device));
+    }
+    Ok(devices)
+}
+
+fn to_device(record: BluetoothRecord) -> Option<BluetoothDevice> {
+    let address = bluetooth::normalize_address(&record.address)?;
+    let (vendor_id, product_id) = record.modalias.as_deref().map(bluetooth::parse_modalias).unwrap_or_default();
+    let status = if record.blocked {
+        "Disabled"
+    } else if record.connected {
+        "OK"
+    } else {
+        "Disconnected"
+    };
+
+    let device = UsbDevice {
+        instance_id: bluetooth::instance_id(&address),
+        friendly_name: record.alias.or(record.name).filter(|n| !n.is_empty()).unwrap_or_else(|| address.clone()),
+        device_class: record.class_of_device.map_or("Bluetooth", bluetooth::device_class_name).to_string(),
+        status: status.to_string(),
+        vendor_id,
+        product_id,
+        serial: Some(address.clone()),
+        bus_type: Some("Bluetooth".to_string()),
+        ..Default::default()
+    };
+    Some(BluetoothDevice {
+        device,
+        address,
+        class_of_device: record.class_of_device,
+        paired: record.paired,
+        connected: record.connected,
+        blocked: record.blocked,
+    })
+}
+
+fn parse_address(address: &str) -> Result<String, String> {
+    bluetooth::normalize_address(address).ok_or_else(|| format!("Invalid Bluetooth address: {}", address))
+}
+
+/// Removes the pairing with a Bluetooth device
+#[tauri::command]
+pub fn unpair_bluetooth_device(address: String) -> Result<(), String> {
+    let address = parse_address(&address)?;
+    backend::current().unpair_bluetooth_device(&address)
+        .map_err(|e| e.to_string())?;
+
+    let instance_id = bluetooth::instance_id(&address);
+    let _ = logs::add_event_log(
+        "BLOCK".to_string(),
+        format!("Bluetooth device unpaired: {}", address),
+        Some(instance_id.clone()),
+    );
+    let _ = inventory::record_decision(&instance_id, TrustDecisionKind::Blocked);
+    Ok(())
+}
+
+/// Blocks a Bluetooth device from connecting
+#[tauri::command]
+pub fn block_bluetooth_device(address: String) -> Result<(), String> {
+    let address = parse_address(&address)?;
+    backend::current().set_bluetooth_blocked(&address, true)
+        .map_err(|e| e.to_string())?;
+
+    let instance_id = bluetooth::instance_id(&address);
+    let _ = logs::add_event_log(
+        "BLOCK".to_string(),
+        format!("Bluetooth device blocked: {}", address),
+        Some(instance_id.clone()),
+    );
+    let _ = inventory::record_decision(&instance_id, TrustDecisionKind::Blocked);
+    Ok(())
+}
+
+/// Lets a blocked Bluetooth device connect again
+#[tauri::command]
+pub fn unblock_bluetooth_device(address: String) -> Result<(), String> {
+    let address = parse_address(&address)?;
+    backend::current().set_bluetooth_blocked(&address, false)
+        .map_err(|e| e.to_string())?;
+
+    let instance_id = bluetooth::instance_id(&address);
+    let _ = logs::add_event_log(
+        "INFO".to_string(),
+        format!("Bluetooth device unblocked: {}", address),
+        Some(instance_id.clone()),
+    );
+    let _ = inventory::record_decision(&instance_id, TrustDecisionKind::Enabled);
+    Ok(())
+}
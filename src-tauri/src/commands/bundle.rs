@@ -0,0 +1,178 @@
+use chrono::Utc;
+use crate::commands::{policy, settings, whitelist};
+use crate::services::bundle::{self, BundlePayload, BUNDLE_VERSION};
+use crate::services::storage;
+
+/// Signs the current whitelist and device policy and writes the bundle to the app
+/// data directory, returning the file path
+#[tauri::command]
+pub fn export_policy_bundle(signing_key: String) -> Result<String, String> {
+    let key = bundle::parse_signing_key(&signing_key).map_err(|e| e.to_string())?;
+    let payload = BundlePayload {
+        version: BUNDLE_VERSION,
+        created_at: Utc::now().to_rfc3339(),
+        whitelist: whitelist::get_whitelist()?,
+        policy: policy::read_policy()?,
+    };
+    let content = bundle::sign(&payload, &key).map_err(|e| e.to_string())?;
+
+    let export_dir = storage::get_app_data_dir()
+        .map_err(|e| e.to_string())?;
+    let filename = format!("sentinelguard_bundle_{}.json", Utc::now().format("%Y%m%d_%H%M%S"));
+    let export_path = export_dir.join(&filename);
+    std::fs::write(&export_path, content)
+        .map_err(|e| e.to_string())?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Signed policy bundle exported: {} whitelist entries", payload.whitelist.len()),
+        None,
+    );
+
+    Ok(export_path.to_string_lossy().to_string())
+}
+
+/// Verifies a bundle against the configured public key, then replaces the whitelist and
+/// device policy with its contents. Rejected bundles are logged as ERROR and change nothing.
+#[tauri::command]
+pub fn import_policy_bundle(bundle: String) -> Result<(), String> {
+    let payload = verified_payload(&bundle)
+        .and_then(|payload| apply(&payload).map(|_| payload))
+        .inspect_err(|e| {
+            let _ = crate::commands::logs::add_event_log(
+                "ERROR".to_string(),
+                format!("Policy bundle rejected: {}", e),
+                None,
+            );
+        })?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "WARN".to_string(),
+        format!(
+            "Policy bundle from {} imported: {} whitelist entries, {} class rules",
+            payload.created_at,
+            payload.whitelist.len(),
+            payload.policy.class_rules.len()
+        ),
+        None,
+    );
+
+    Ok(())
+}
+
+fn verified_payload(text: &str) -> Result<BundlePayload, String> {
+    let public_key = settings::read_settings()?
+        .bundle_public_key
+        .ok_or("No bundle public key is configured")?;
+    let key = bundle::parse_verifying_key(&public_key).map_err(|e| e.to_string())?;
+    let payload = bundle::verify(text, &key).map_err(|e| e.to_string())?;
+    policy::validate_policy(&payload.policy)?;
+    for entry in &payload.whitelist {
+        whitelist::validate_schedule(entry.expires_at.as_deref(), &entry.allowed_windows)?;
+    }
+    Ok(payload)
+}
+
+/// Writes a verified payload, restoring the previous whitelist if the policy can't be written
+fn apply(payload: &BundlePayload) -> Result<(), String> {
+    let previous = whitelist::get_whitelist()?;
+    whitelist::replace_entries(payload.whitelist.clone())?;
+    if let Err(e) = policy::write_policy(&payload.policy) {
+        let _ = whitelist::replace_entries(previous);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use ed25519_dalek::SigningKey;
+    use crate::commands::logs::get_event_logs;
+    use crate::models::{DevicePolicy, PolicyAction, WhitelistEntry};
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn entry(instance_id: &str, expires_at: Option<&str>) -> WhitelistEntry {
+        serde_json::from_value(serde_json::json!({
+            "instance_id": instance_id,
+            "friendly_name": instance_id,
+            "added_at": "2026-01-05T09:00:00+00:00",
+            "expires_at": expires_at,
+        })).unwrap()
+    }
+
+    fn bundle_text(whitelist: Vec<WhitelistEntry>, signed_by: u8) -> String {
+        let payload = BundlePayload {
+            version: BUNDLE_VERSION,
+            created_at: "2026-01-05T09:00:00+00:00".to_string(),
+            whitelist,
+            policy: DevicePolicy { class_rules: Vec::new(), default_action: PolicyAction::Prompt },
+        };
+        bundle::sign(&payload, &key(signed_by)).unwrap()
+    }
+
+    /// Trusts key 1 and starts from a whitelist holding only `USB\OLD`
+    fn setup() {
+        settings::set_bundle_public_key(Some(STANDARD.encode(key(1).verifying_key().to_bytes()))).unwrap();
+        whitelist::replace_entries(vec![entry("USB\\OLD", None)]).unwrap();
+    }
+
+    fn whitelist_ids() -> Vec<String> {
+        whitelist::get_whitelist().unwrap().into_iter().map(|e| e.instance_id).collect()
+    }
+
+    fn errors() -> Vec<String> {
+        get_event_logs().unwrap().into_iter().filter(|l| l.level == "ERROR").map(|l| l.message).collect()
+    }
+
+    #[test]
+    fn verified_bundle_replaces_whitelist_and_policy() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        setup();
+        import_policy_bundle(bundle_text(vec![entry("USB\\NEW", None)], 1)).unwrap();
+
+        assert_eq!(whitelist_ids(), vec!["USB\\NEW"]);
+        assert_eq!(policy::read_policy().unwrap().default_action, PolicyAction::Prompt);
+        assert!(errors().is_empty());
+    }
+
+    #[test]
+    fn rejected_bundle_is_logged_and_changes_nothing() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        setup();
+
+        assert!(import_policy_bundle(bundle_text(vec![entry("USB\\NEW", None)], 2)).is_err());
+        assert!(import_policy_bundle(bundle_text(vec![entry("USB\\NEW", Some("next week"))], 1)).is_err());
+        assert!(import_policy_bundle("not a bundle".to_string()).is_err());
+
+        assert_eq!(whitelist_ids(), vec!["USB\\OLD"]);
+        assert_eq!(policy::read_policy().unwrap().default_action, PolicyAction::Block);
+        let errors = errors();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("Policy bundle rejected: "));
+    }
+
+    #[test]
+    fn failed_policy_write_restores_the_whitelist() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        setup();
+        // A directory in place of policies.json makes the policy write fail after the whitelist write
+        std::fs::create_dir(storage::get_app_data_dir().unwrap().join("policies.json")).unwrap();
+
+        assert!(import_policy_bundle(bundle_text(vec![entry("USB\\NEW", None)], 1)).is_err());
+        assert_eq!(whitelist_ids(), vec!["USB\\OLD"]);
+        assert_eq!(errors().len(), 1);
+    }
+
+    #[test]
+    fn import_needs_a_configured_key() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        assert_eq!(import_policy_bundle(bundle_text(Vec::new(), 1)), Err("No bundle public key is configured".to_string()));
+    }
+}
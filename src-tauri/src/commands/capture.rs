@@ -0,0 +1,239 @@
+use chrono::{DateTime, Local, Utc};
+use crate::models::{CaptureData, DisabledCaptureDevice, PrivacyMode, TrustDecisionKind, UsbDevice};
+use crate::services::{backend, capture, storage};
+use crate::commands::{devices, inventory, logs, settings, whitelist};
+
+const CAPTURE_FILE: &str = "capture.json";
+
+fn read_capture_data() -> Result<CaptureData, String> {
+    storage::read_json(CAPTURE_FILE).map_err(|e| e.to_string())
+}
+
+fn write_capture_data(data: &CaptureData) -> Result<(), String> {
+    storage::write_json(CAPTURE_FILE, data).map_err(|e| e.to_string())
+}
+
+/// Connected cameras and microphones (composite devices are listed once, by their parent)
+pub fn capture_devices() -> Result<Vec<UsbDevice>, String> {
+    let devices = devices::list_devices(backend::current().as_ref()).map(devices::group_devices)?;
+    Ok(devices.into_iter().filter(|d| !capture::kinds(d).is_empty()).collect())
+}
+
+/// Gets connected cameras and microphones
+#[tauri::command]
+pub fn get_capture_devices() -> Result<Vec<UsbDevice>, String> {
+    capture_devices()
+}
+
+/// Whether privacy mode currently keeps capture devices disabled
+pub fn privacy_mode_active(mode: &PrivacyMode) -> bool {
+    privacy_mode_active_at(mode, Local::now())
+}
+
+/// Whether privacy mode keeps capture devices disabled at `now`
+fn privacy_mode_active_at(mode: &PrivacyMode, now: DateTime<Local>) -> bool {
+    mode.enabled && !mode.allowed_windows.iter().any(|w| whitelist::window_contains(w, now))
+}
+
+/// Disables every connected capture device that is still enabled. Returns the instance IDs disabled.
+fn disable_all(by_privacy_mode: bool) -> Result<Vec<String>, String> {
+    let reason = if by_privacy_mode { "privacy mode" } else { "capture devices disabled" };
+    let mut data = read_capture_data()?;
+    let mut changed = Vec::new();
+
+    for device in capture_devices()?.into_iter().filter(|d| d.status != "Disabled") {
+        let kinds = capture::kinds(&device);
+        if let Err(e) = backend::current().disable_device(&device.instance_id) {
+            let _ = logs::add_event_log(
+                "ERROR".to_string(),
+                format!("Failed to disable {} {} ({}): {}", capture::describe(&kinds).to_lowercase(), device.friendly_name, reason, e),
+                Some(device.instance_id.clone()),
+            );
+            continue;
+        }
+
+        let _ = logs::add_event_log(
+            "BLOCK".to_string(),
+            format!("{} disabled ({}): {}", capture::describe(&kinds), reason, device.friendly_name),
+            Some(device.instance_id.clone()),
+        );
+        let _ = inventory::record_decision(&device.instance_id, TrustDecisionKind::Blocked);
+        data.disabled.retain(|d| d.instance_id != device.instance_id);
+        data.disabled.push(DisabledCaptureDevice {
+            instance_id: device.instance_id.clone(),
+            friendly_name: device.friendly_name.clone(),
+            capture: kinds,
+            disabled_at: Utc::now().to_rfc3339(),
+            by_privacy_mode,
+        });
+        changed.push(device.instance_id);
+    }
+
+    write_capture_data(&data)?;
+    Ok(changed)
+}
+
+/// Re-enables capture devices the capture controls disabled; with `only_privacy_mode` just
+/// the ones privacy mode turned off. Devices that can't be enabled (e.g. unplugged) are
+/// logged and forgotten. Returns the instance IDs enabled.
+fn enable_all(only_privacy_mode: bool) -> Result<Vec<String>, String> {
+    let reason = if only_privacy_mode { "privacy mode allowed hours" } else { "capture devices enabled" };
+    let mut data = read_capture_data()?;
+    let (selected, kept): (Vec<_>, Vec<_>) = data.disabled
+        .into_iter()
+        .partition(|d| d.by_privacy_mode || !only_privacy_mode);
+    data.disabled = kept;
+    if selected.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut changed = Vec::new();
+    for device in selected {
+        if let Err(e) = backend::current().enable_device(&device.instance_id) {
+            let _ = logs::add_event_log(
+                "WARN".to_string(),
+                format!("Could not re-enable {} {} ({}): {}", capture::describe(&device.capture).to_lowercase(), device.friendly_name, reason, e),
+                Some(device.instance_id.clone()),
+            );
+            continue;
+        }
+
+        let _ = logs::add_event_log(
+            "INFO".to_string(),
+            format!("{} enabled ({}): {}", capture::describe(&device.capture), reason, device.friendly_name),
+            Some(device.instance_id.clone()),
+        );
+        let _ = inventory::record_decision(&device.instance_id, TrustDecisionKind::Enabled);
+        changed.push(device.instance_id);
+    }
+
+    write_capture_data(&data)?;
+    Ok(changed)
+}
+
+/// Disables every connected camera and microphone
+#[tauri::command]
+pub fn disable_capture_devices() -> Result<Vec<String>, String> {
+    disable_all(false)
+}
+
+/// Re-enables the cameras and microphones disabled by the capture controls.
+/// Refused while privacy mode is keeping them off.
+#[tauri::command]
+pub fn enable_capture_devices() -> Result<Vec<String>, String> {
+    if privacy_mode_active(&settings::read_settings()?.privacy_mode) {
+        return Err("Privacy mode is active: capture devices stay disabled outside the allowed hours".to_string());
+    }
+    enable_all(false)
+}
+
+/// Applies privacy mode: outside the allowed hours capture devices are disabled (including
+/// newly connected ones), inside them the devices privacy mode disabled come back on.
+/// Returns whether privacy mode is active.
+pub fn enforce_privacy_mode() -> Result<bool, String> {
+    let active = privacy_mode_active(&settings::read_settings()?.privacy_mode);
+    if active {
+        disable_all(true)?;
+    } else {
+        enable_all(true)?;
+    }
+    Ok(active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::{CaptureKind, TimeWindow};
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    const WEBCAM: &str = "USB\\VID_046D&PID_0825\\5&3B1C2D4E&0&3";
+    const MOUSE: &str = "USB\\VID_046D&PID_C077\\5&2A1B3C4D&0&2";
+
+    fn env() -> TestEnv {
+        let json = r#"[
+            {"instance_id":"USB\\VID_046D&PID_0825\\5&3B1C2D4E&0&3","friendly_name":"HD Webcam","device_class":"Camera","status":"OK","interface_classes":[14,1]},
+            {"instance_id":"USB\\VID_046D&PID_C077\\5&2A1B3C4D&0&2","friendly_name":"USB Mouse","device_class":"Mouse","status":"OK","interface_classes":[3]}
+        ]"#;
+        TestEnv::new(FixtureBackend::new().with_output("usb_devices", json))
+    }
+
+    /// Local time on a day in January 2026 (the 5th is a Monday)
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 1, day, hour, minute, 0).unwrap()
+    }
+
+    fn window(days: &[&str], start: &str, end: &str) -> TimeWindow {
+        TimeWindow { days: days.iter().map(|d| d.to_string()).collect(), start: start.to_string(), end: end.to_string() }
+    }
+
+    /// A window from an hour ago to an hour from now, wrapping midnight if it has to
+    fn window_around_now() -> TimeWindow {
+        let now = Local::now();
+        window(&[], &(now - Duration::hours(1)).format("%H:%M").to_string(), &(now + Duration::hours(1)).format("%H:%M").to_string())
+    }
+
+    fn privacy(enabled: bool, allowed_windows: Vec<TimeWindow>) -> PrivacyMode {
+        PrivacyMode { enabled, allowed_windows }
+    }
+
+    #[test]
+    fn privacy_mode_applies_outside_the_allowed_hours() {
+        let office = privacy(true, vec![window(&["weekdays"], "09:00", "17:00")]);
+        assert!(!privacy_mode_active_at(&office, at(5, 9, 0)));
+        assert!(!privacy_mode_active_at(&office, at(9, 16, 59)));
+        assert!(privacy_mode_active_at(&office, at(9, 17, 0)));
+        assert!(privacy_mode_active_at(&office, at(5, 8, 59)));
+        assert!(privacy_mode_active_at(&office, at(10, 12, 0)));
+
+        assert!(privacy_mode_active_at(&privacy(true, Vec::new()), at(5, 12, 0)));
+        assert!(!privacy_mode_active_at(&privacy(false, Vec::new()), at(5, 12, 0)));
+    }
+
+    #[test]
+    fn allowed_hours_can_run_past_midnight() {
+        // Friday night into Saturday morning only
+        let late = privacy(true, vec![window(&["fri"], "22:00", "06:00")]);
+        assert!(!privacy_mode_active_at(&late, at(9, 23, 30)));
+        assert!(!privacy_mode_active_at(&late, at(10, 5, 59)));
+        assert!(privacy_mode_active_at(&late, at(10, 6, 0)));
+        assert!(privacy_mode_active_at(&late, at(9, 21, 59)));
+        assert!(privacy_mode_active_at(&late, at(8, 23, 30)));
+        assert!(privacy_mode_active_at(&late, at(9, 5, 0)));
+    }
+
+    #[test]
+    fn privacy_mode_disables_and_restores_capture_devices() {
+        let env = env();
+        settings::set_privacy_mode(true, None).unwrap();
+
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", WEBCAM)]);
+        let disabled = read_capture_data().unwrap().disabled;
+        assert_eq!(disabled.len(), 1);
+        assert_eq!(disabled[0].capture, vec![CaptureKind::Camera, CaptureKind::Microphone]);
+        assert!(disabled[0].by_privacy_mode);
+        assert!(logs::get_event_logs().unwrap().iter().any(|l| l.level == "BLOCK" && l.message == "Camera and microphone disabled (privacy mode): HD Webcam"));
+        assert!(enable_capture_devices().is_err());
+
+        // Entering the allowed hours brings the webcam back, once
+        settings::set_privacy_mode(true, Some(vec![window_around_now()])).unwrap();
+        assert_eq!(enforce_privacy_mode(), Ok(false));
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", WEBCAM), format!("enable_device:{}", WEBCAM)]);
+        assert!(read_capture_data().unwrap().disabled.is_empty());
+        assert!(!env.backend.actions().iter().any(|a| a.ends_with(MOUSE)));
+    }
+
+    #[test]
+    fn privacy_mode_leaves_manually_disabled_devices_off() {
+        let env = env();
+        assert_eq!(disable_capture_devices(), Ok(vec![WEBCAM.to_string()]));
+        assert!(!read_capture_data().unwrap().disabled[0].by_privacy_mode);
+
+        assert_eq!(enforce_privacy_mode(), Ok(false));
+        assert_eq!(env.backend.actions(), vec![format!("disable_device:{}", WEBCAM)]);
+
+        assert_eq!(enable_capture_devices(), Ok(vec![WEBCAM.to_string()]));
+        assert_eq!(env.backend.actions().last(), Some(&format!("enable_device:{}", WEBCAM)));
+    }
+}
@@ -1,13 +1,131 @@
-use crate::models::{UsbDevice, DashboardStats};
-use crate::services::powershell;
+use std::collections::{HashMap, HashSet};
+use crate::models::{BlockDevice, UsbDevice, DashboardStats, DevicePolicy, EventLog, PolicyAction, TrustDecisionKind};
+use crate::services::backend::{self, SystemBackend};
+use crate::services::{badusb, capture, device_id};
 use crate::commands::whitelist::is_device_trusted;
+use crate::commands::policy;
 
-/// Gets all connected USB devices with their trust status
+// Buses that only carry built-in hardware (a laptop's touchpad, audio codec, ...)
+const INTERNAL_BUSES: [&str; 3] = ["ACPI", "PCI", "HDAudio"];
+
+/// Gets connected devices with their trust status, with the functions of composite
+/// devices grouped under them. `classes` limits the list to those device classes.
 #[tauri::command]
-pub fn get_connected_devices() -> Result<Vec<UsbDevice>, String> {
-    let json_output = powershell::get_usb_devices_json()
+pub fn get_connected_devices(classes: Option<Vec<String>>) -> Result<Vec<UsbDevice>, String> {
+    let mut devices = list_devices(backend::current().as_ref())?;
+    if let Some(classes) = classes.filter(|c| !c.is_empty()) {
+        devices.retain(|d| classes.iter().any(|c| c.eq_ignore_ascii_case(&d.device_class)));
+    }
+    Ok(group_devices(devices))
+}
+
+/// Disks behind a USB device (empty until the OS has created them)
+pub fn block_devices(instance_id: &str) -> Result<Vec<BlockDevice>, String> {
+    let json = backend::current().block_devices_json(instance_id)
+        .map_err(|e| e.to_string())?;
+    backend::parse_list(&json)
+}
+
+/// Lists the devices reported by a backend, marking whitelisted ones as trusted
+/// and recording the policy outcome for each
+pub fn list_devices(backend: &dyn SystemBackend) -> Result<Vec<UsbDevice>, String> {
+    let json_output = backend.usb_devices_json()
         .map_err(|e| e.to_string())?;
     
+    let device_policy = policy::read_policy().unwrap_or_default();
+    let mut devices = parse_devices(&json_output)?;
+    resolve_bus_types(&mut devices);
+    devices.retain(|d| !d.bus_type.as_deref().is_some_and(|b| INTERNAL_BUSES.contains(&b)));
+    for device in &mut devices {
+        device.is_trusted = is_device_trusted(device).unwrap_or(false);
+        device.risk_flags = badusb::assess(device);
+        device.capture = capture::classify(device);
+        device.policy_action = Some(policy::evaluate(&device_policy, device));
+    }
+    
+    Ok(devices)
+}
+
+/// Fills in missing bus types from the instance ID's enumerator, or from the nearest
+/// ancestor with one (an `HID\...` keyboard is on whatever bus its parent is)
+fn resolve_bus_types(devices: &mut [UsbDevice]) {
+    let parents: HashMap<String, Option<String>> = devices
+        .iter()
+        .map(|d| (d.instance_id.to_uppercase(), d.parent_id.clone()))
+        .collect();
+
+    for device in devices.iter_mut().filter(|d| d.bus_type.is_none()) {
+        let mut current = Some(device.instance_id.clone());
+        // Bounded walk in case of a parent cycle in bad backend output
+        for _ in 0..parents.len() + 1 {
+            let Some(id) = current else {
+                break;
+            };
+            if let Some(bus) = device_id::enumerator_bus(&id) {
+                device.bus_type = Some(bus.to_string());
+                break;
+            }
+            current = parents.get(&id.to_uppercase()).cloned().flatten();
+        }
+        if device.bus_type.is_none() {
+            device.bus_type = device.instance_id.split('\\').next().map(str::to_uppercase);
+        }
+    }
+}
+
+/// Nests each device under its parent when the parent is also listed, so a composite
+/// device carries its interfaces and their HID collections or disks. Devices behind a hub
+/// stay at the top level: a hub is a separate device, not a function of what's plugged into it.
+/// The same goes for Thunderbolt daisy chains, where every device can do DMA on its own.
+/// Children inherit their parent's trust, and are re-evaluated against the policy when they do.
+pub fn group_devices(devices: Vec<UsbDevice>) -> Vec<UsbDevice> {
+    const CLASS_HUB: u8 = 0x09;
+    let device_policy = policy::read_policy().unwrap_or_default();
+    let groupable: HashSet<String> = devices
+        .iter()
+        .filter(|d| !d.interface_classes.contains(&CLASS_HUB) && d.bus_type.as_deref() != Some("Thunderbolt"))
+        .map(|d| d.instance_id.to_uppercase())
+        .collect();
+    let parent_of = |device: &UsbDevice| {
+        device.parent_id
+            .as_ref()
+            .map(|p| p.to_uppercase())
+            .filter(|p| *p != device.instance_id.to_uppercase() && groupable.contains(p))
+    };
+
+    let mut children: HashMap<String, Vec<UsbDevice>> = HashMap::new();
+    let mut roots = Vec::new();
+    for device in devices {
+        match parent_of(&device) {
+            Some(parent) => children.entry(parent).or_default().push(device),
+            None => roots.push(device),
+        }
+    }
+
+    fn attach(device: &mut UsbDevice, children: &mut HashMap<String, Vec<UsbDevice>>, device_policy: &DevicePolicy) {
+        let Some(mut own) = children.remove(&device.instance_id.to_uppercase()) else {
+            return;
+        };
+        for child in &mut own {
+            if device.is_trusted && !child.is_trusted {
+                child.is_trusted = true;
+                child.policy_action = Some(policy::evaluate(device_policy, child));
+            }
+            attach(child, children, device_policy);
+        }
+        device.children = own;
+    }
+    for root in &mut roots {
+        attach(root, &mut children, &device_policy);
+    }
+
+    // Whatever is left sits in a parent cycle; list it rather than drop it
+    roots.extend(children.into_values().flatten());
+    roots
+}
+
+/// Parses the device listing JSON (trust status is left unset)
+pub fn parse_devices(json_output: &str) -> Result<Vec<UsbDevice>, String> {
     // Handle empty or null output
     if json_output.is_empty() || json_output == "null" {
         return Ok(vec![]);
@@ -16,7 +134,7 @@ pub fn get_connected_devices() -> Result<Vec<UsbDevice>, String> {
     // Parse the JSON - PowerShell returns array or single object
     let devices: Vec<UsbDevice> = if json_output.starts_with('[') {
         // Array of devices
-        let raw: Vec<serde_json::Value> = serde_json::from_str(&json_output)
+        let raw: Vec<serde_json::Value> = serde_json::from_str(json_output)
             .map_err(|e| format!("JSON parse error: {}", e))?;
         
         raw.into_iter().filter_map(|v| {
@@ -24,35 +142,33 @@ pub fn get_connected_devices() -> Result<Vec<UsbDevice>, String> {
             let friendly_name = v.get("friendly_name")?.as_str()?.to_string();
             let device_class = v.get("device_class")?.as_str()?.to_string();
             let status = v.get("status")?.as_str()?.to_string();
-            let is_trusted = is_device_trusted(&instance_id).unwrap_or(false);
             
-            Some(UsbDevice {
+            Some(with_identity(UsbDevice {
                 instance_id,
                 friendly_name,
                 device_class,
                 status,
-                is_trusted,
-            })
+                ..Default::default()
+            }, &v))
         }).collect()
     } else {
         // Single device object
-        let v: serde_json::Value = serde_json::from_str(&json_output)
+        let v: serde_json::Value = serde_json::from_str(json_output)
             .map_err(|e| format!("JSON parse error: {}", e))?;
         
         let instance_id = v.get("instance_id").and_then(|v| v.as_str()).unwrap_or("").to_string();
         let friendly_name = v.get("friendly_name").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string();
         let device_class = v.get("device_class").and_then(|v| v.as_str()).unwrap_or("USB").to_string();
         let status = v.get("status").and_then(|v| v.as_str()).unwrap_or("OK").to_string();
-        let is_trusted = is_device_trusted(&instance_id).unwrap_or(false);
         
         if !instance_id.is_empty() {
-            vec![UsbDevice {
+            vec![with_identity(UsbDevice {
                 instance_id,
                 friendly_name,
                 device_class,
                 status,
-                is_trusted,
-            }]
+                ..Default::default()
+            }, &v)]
         } else {
             vec![]
         }
@@ -61,18 +177,60 @@ pub fn get_connected_devices() -> Result<Vec<UsbDevice>, String> {
     Ok(devices)
 }
 
+/// Fills VID/PID/serial and interface classes, preferring fields the backend reported
+/// over what can be parsed from the instance ID
+fn with_identity(mut device: UsbDevice, v: &serde_json::Value) -> UsbDevice {
+    let parsed = device_id::parse_instance_id(&device.instance_id);
+    
+    device.vendor_id = match v.get("vendor_id") {
+        Some(value) => value.as_str().and_then(device_id::normalize_hex_id),
+        None => parsed.vendor_id,
+    };
+    device.product_id = match v.get("product_id") {
+        Some(value) => value.as_str().and_then(device_id::normalize_hex_id),
+        None => parsed.product_id,
+    };
+    device.serial = match v.get("serial") {
+        Some(value) => value.as_str().filter(|s| !s.is_empty()).map(String::from),
+        None => parsed.serial,
+    };
+    
+    // ConvertTo-Json collapses single-element arrays into a bare number
+    device.interface_classes = match v.get("interface_classes") {
+        Some(serde_json::Value::Array(values)) => values.iter()
+            .filter_map(|c| c.as_u64())
+            .filter_map(|c| u8::try_from(c).ok())
+            .collect(),
+        Some(value) => value.as_u64().and_then(|c| u8::try_from(c).ok()).into_iter().collect(),
+        None => vec![],
+    };
+    device.interfaces = match v.get("interfaces") {
+        Some(serde_json::Value::Array(values)) => values.iter()
+            .filter_map(|i| i.as_str())
+            .map(str::to_lowercase)
+            .collect(),
+        Some(value) => value.as_str().map(str::to_lowercase).into_iter().collect(),
+        None => vec![],
+    };
+    device.bus_type = v.get("bus_type").and_then(|b| b.as_str()).map(String::from);
+    device.parent_id = v.get("parent_id").and_then(|p| p.as_str()).filter(|p| !p.is_empty()).map(String::from);
+    
+    device
+}
+
 /// Enables a previously disabled device
 #[tauri::command]
 pub fn enable_device(instance_id: String) -> Result<(), String> {
-    powershell::enable_device(&instance_id)
+    backend::current().enable_device(&instance_id)
         .map_err(|e| e.to_string())?;
     
     // Log the event
     let _ = crate::commands::logs::add_event_log(
         "INFO".to_string(),
         format!("Device enabled: {}", instance_id),
-        Some(instance_id),
+        Some(instance_id.clone()),
     );
+    let _ = crate::commands::inventory::record_decision(&instance_id, TrustDecisionKind::Enabled);
     
     Ok(())
 }
@@ -80,15 +238,32 @@ pub fn enable_device(instance_id: String) -> Result<(), String> {
 /// Disables a device (blocks it)
 #[tauri::command]
 pub fn disable_device(instance_id: String) -> Result<(), String> {
-    powershell::disable_device(&instance_id)
+    backend::current().disable_device(&instance_id)
         .map_err(|e| e.to_string())?;
     
     // Log the event
     let _ = crate::commands::logs::add_event_log(
         "BLOCK".to_string(),
         format!("Device blocked: {}", instance_id),
-        Some(instance_id),
+        Some(instance_id.clone()),
     );
+    let _ = crate::commands::inventory::record_decision(&instance_id, TrustDecisionKind::Blocked);
+    
+    Ok(())
+}
+
+/// Turns default-deny for newly attached USB devices on or off
+#[tauri::command]
+pub fn set_default_deny(enabled: bool) -> Result<(), String> {
+    backend::current().set_default_deny(enabled)
+        .map_err(|e| e.to_string())?;
+    
+    let (level, message) = if enabled {
+        ("WARN", "USB default-deny enabled: new devices stay blocked until enabled")
+    } else {
+        ("INFO", "USB default-deny disabled: new devices are allowed")
+    };
+    let _ = crate::commands::logs::add_event_log(level.to_string(), message.to_string(), None);
     
     Ok(())
 }
@@ -96,22 +271,217 @@ pub fn disable_device(instance_id: String) -> Result<(), String> {
 /// Gets dashboard statistics
 #[tauri::command]
 pub fn get_dashboard_stats() -> Result<DashboardStats, String> {
-    let devices = get_connected_devices()?;
+    let devices = get_connected_devices(None)?;
     let logs = crate::commands::logs::get_event_logs()?;
     
+    Ok(compute_dashboard_stats(&devices, &logs))
+}
+
+/// Builds dashboard statistics from the current devices and event log.
+/// Devices are counted by policy outcome.
+pub fn compute_dashboard_stats(devices: &[UsbDevice], logs: &[EventLog]) -> DashboardStats {
     let total_devices = devices.len();
-    let trusted_devices = devices.iter().filter(|d| d.is_trusted).count();
-    let blocked_devices = devices.iter().filter(|d| !d.is_trusted).count();
+    let trusted_devices = devices.iter().filter(|d| policy::outcome(d) == PolicyAction::Allow).count();
+    let blocked_devices = devices.iter().filter(|d| policy::outcome(d) == PolicyAction::Block).count();
+    let pending_devices = devices.iter().filter(|d| policy::outcome(d) == PolicyAction::Prompt).count();
     let blocked_threats = logs.iter().filter(|l| l.level == "BLOCK").count();
     let total_scans = logs.len();
-    let is_secure = blocked_devices == 0;
+    let is_secure = blocked_devices == 0 && pending_devices == 0;
     
-    Ok(DashboardStats {
+    DashboardStats {
         total_devices,
         trusted_devices,
         blocked_devices,
+        pending_devices,
         total_scans,
         blocked_threats,
         is_secure,
-    })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::whitelist;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    const STICK: &str = r#"{"instance_id":"USB\\VID_0781&PID_5567\\4C530001","friendly_name":"SanDisk Cruzer","device_class":"DiskDrive","status":"OK","interface_classes":8}"#;
+    const MOUSE: &str = r#"{"instance_id":"USB\\VID_046D&PID_C077\\5&2A1B3C4D&0&2","friendly_name":"USB Mouse","device_class":"Mouse","status":"OK","interface_classes":[3]}"#;
+
+    fn env_with_devices(json: &str) -> TestEnv {
+        TestEnv::new(FixtureBackend::new().with_output("usb_devices", json))
+    }
+
+    #[test]
+    fn parse_devices_reads_an_array() {
+        let devices = parse_devices(&format!("[{},{}]", STICK, MOUSE)).unwrap();
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].friendly_name, "SanDisk Cruzer");
+        assert_eq!(devices[0].vendor_id.as_deref(), Some("0781"));
+        assert_eq!(devices[0].serial.as_deref(), Some("4C530001"));
+        assert_eq!(devices[1].interface_classes, vec![3]);
+    }
+
+    #[test]
+    fn parse_devices_reads_a_single_object() {
+        let devices = parse_devices(STICK).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].instance_id, "USB\\VID_0781&PID_5567\\4C530001");
+        // ConvertTo-Json turned the one-element array into a bare number
+        assert_eq!(devices[0].interface_classes, vec![8]);
+    }
+
+    #[test]
+    fn parse_devices_handles_empty_output() {
+        assert!(parse_devices("").unwrap().is_empty());
+        assert!(parse_devices("null").unwrap().is_empty());
+        assert!(parse_devices(r#"{"friendly_name":"No ID"}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn connected_devices_are_marked_trusted_from_the_whitelist() {
+        let _env = env_with_devices(&format!("[{},{}]", STICK, MOUSE));
+        let stick = parse_devices(STICK).unwrap().remove(0);
+        whitelist::add_to_whitelist(stick, None, None).unwrap();
+
+        let devices = get_connected_devices(None).unwrap();
+        assert_eq!(devices.len(), 2);
+        let trusted: Vec<_> = devices.iter().filter(|d| d.is_trusted).map(|d| d.friendly_name.as_str()).collect();
+        assert_eq!(trusted, vec!["SanDisk Cruzer"]);
+        assert_eq!(devices[0].bus_type.as_deref(), Some("USB"));
+    }
+
+    #[test]
+    fn connected_devices_can_be_filtered_by_class() {
+        let _env = env_with_devices(&format!("[{},{}]", STICK, MOUSE));
+        let devices = get_connected_devices(Some(vec!["mouse".to_string()])).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].friendly_name, "USB Mouse");
+    }
+
+    #[test]
+    fn connected_devices_report_backend_errors() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        assert!(get_connected_devices(None).unwrap_err().contains("usb_devices"));
+    }
+
+    #[test]
+    fn dashboard_stats_count_devices_by_policy_outcome() {
+        let _env = env_with_devices(&format!("[{},{}]", STICK, MOUSE));
+        let stick = parse_devices(STICK).unwrap().remove(0);
+        whitelist::add_to_whitelist(stick, None, None).unwrap();
+        let mouse = parse_devices(MOUSE).unwrap().remove(0);
+        disable_device(mouse.instance_id).unwrap();
+
+        let stats = get_dashboard_stats().unwrap();
+        assert_eq!(stats.total_devices, 2);
+        // Untrusted devices fall to the default Block action
+        assert_eq!((stats.trusted_devices, stats.blocked_devices, stats.pending_devices), (1, 1, 0));
+        assert!(!stats.is_secure);
+        // One INFO entry for the whitelist, one BLOCK entry for the disabled mouse
+        assert_eq!(stats.total_scans, 2);
+        assert_eq!(stats.blocked_threats, 1);
+    }
+
+    const HUB: &str = r#"{"instance_id":"USB\\VID_05E3&PID_0610\\5&1F2E3D4C&0&1","friendly_name":"Generic USB Hub","device_class":"USB","status":"OK","interface_classes":[9]}"#;
+    const COMBO: &str = r#"{"instance_id":"USB\\VID_1234&PID_5678\\ABC123","friendly_name":"Keyboard Stick","device_class":"USB","status":"OK","interface_classes":[3,8],"parent_id":"USB\\VID_05E3&PID_0610\\5&1F2E3D4C&0&1"}"#;
+    const COMBO_HID: &str = r#"{"instance_id":"USB\\VID_1234&PID_5678&MI_00\\6&1A2B3C&0&0000","friendly_name":"USB Input Device","device_class":"HIDClass","status":"OK","interface_classes":[3],"parent_id":"USB\\VID_1234&PID_5678\\ABC123"}"#;
+    const COMBO_KEYBOARD: &str = r#"{"instance_id":"HID\\VID_1234&PID_5678&MI_00\\7&2B3C4D&0&0000","friendly_name":"HID Keyboard Device","device_class":"Keyboard","status":"OK","parent_id":"USB\\VID_1234&PID_5678&MI_00\\6&1A2B3C&0&0000"}"#;
+    const DOCK: &str = r#"{"instance_id":"THUNDERBOLT\\0000002A\\DOCK01","friendly_name":"Thunderbolt Dock","device_class":"System","status":"OK"}"#;
+    const DOCK_DISK: &str = r#"{"instance_id":"THUNDERBOLT\\0000002B\\DISK01","friendly_name":"Thunderbolt SSD","device_class":"System","status":"OK","parent_id":"THUNDERBOLT\\0000002A\\DOCK01"}"#;
+    const TOUCHPAD: &str = r#"{"instance_id":"HID\\ELAN0001&COL01\\5&3A2B1C&0&0000","friendly_name":"I2C HID Touchpad","device_class":"Mouse","status":"OK","parent_id":"ACPI\\PNP0C50\\1"}"#;
+
+    fn devices(json: &[&str]) -> Vec<UsbDevice> {
+        parse_devices(&format!("[{}]", json.join(","))).unwrap()
+    }
+
+    fn names(devices: &[UsbDevice]) -> Vec<&str> {
+        devices.iter().map(|d| d.friendly_name.as_str()).collect()
+    }
+
+    #[test]
+    fn bus_is_resolved_through_the_parent_chain() {
+        let mut listed = devices(&[COMBO, COMBO_HID, COMBO_KEYBOARD, DOCK, TOUCHPAD]);
+        let acpi_touchpad = UsbDevice { instance_id: "ACPI\\PNP0C50\\1".to_string(), ..Default::default() };
+        listed.push(acpi_touchpad);
+        resolve_bus_types(&mut listed);
+
+        let buses: Vec<Option<&str>> = listed.iter().map(|d| d.bus_type.as_deref()).collect();
+        assert_eq!(buses, vec![Some("USB"), Some("USB"), Some("USB"), Some("Thunderbolt"), Some("ACPI"), Some("ACPI")]);
+
+        // Without a known bus anywhere up the chain, the device's own enumerator is used
+        let mut virtual_hid = devices(&[r#"{"instance_id":"HID\\VID_1234&PID_0001\\1","friendly_name":"Virtual HID","device_class":"HIDClass","status":"OK","parent_id":"ROOT\\VHF\\0000"}"#]);
+        resolve_bus_types(&mut virtual_hid);
+        assert_eq!(virtual_hid[0].bus_type.as_deref(), Some("HID"));
+    }
+
+    #[test]
+    fn internal_devices_are_not_listed() {
+        let _env = env_with_devices(&format!("[{},{}]", STICK, TOUCHPAD));
+        assert_eq!(names(&get_connected_devices(None).unwrap()), vec!["SanDisk Cruzer"]);
+    }
+
+    #[test]
+    fn composite_functions_are_nested_under_their_device() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        let mut listed = devices(&[HUB, COMBO_KEYBOARD, COMBO, COMBO_HID, DOCK, DOCK_DISK]);
+        resolve_bus_types(&mut listed);
+        let grouped = group_devices(listed);
+
+        // The hub and every Thunderbolt device stay top-level
+        let mut top = names(&grouped);
+        top.sort();
+        assert_eq!(top, vec!["Generic USB Hub", "Keyboard Stick", "Thunderbolt Dock", "Thunderbolt SSD"]);
+
+        let combo = grouped.iter().find(|d| d.friendly_name == "Keyboard Stick").unwrap();
+        assert_eq!(names(&combo.children), vec!["USB Input Device"]);
+        assert_eq!(names(&combo.children[0].children), vec!["HID Keyboard Device"]);
+        assert!(grouped.iter().filter(|d| d.friendly_name != "Keyboard Stick").all(|d| d.children.is_empty()));
+    }
+
+    #[test]
+    fn children_of_a_trusted_device_are_allowed() {
+        let _env = env_with_devices(&format!("[{},{},{}]", COMBO, COMBO_HID, COMBO_KEYBOARD));
+        policy::set_device_policy(DevicePolicy { class_rules: Vec::new(), default_action: PolicyAction::Prompt }).unwrap();
+        let combo = parse_devices(COMBO).unwrap().remove(0);
+        whitelist::add_to_whitelist(combo, None, None).unwrap();
+
+        let grouped = get_connected_devices(None).unwrap();
+        assert_eq!(grouped.len(), 1);
+        let hid = &grouped[0].children[0];
+        let keyboard = &hid.children[0];
+        for device in [&grouped[0], hid, keyboard] {
+            assert!(device.is_trusted, "{}", device.friendly_name);
+            assert_eq!(device.policy_action, Some(PolicyAction::Allow), "{}", device.friendly_name);
+        }
+
+        // Untrusted, the same functions keep the outcome they were evaluated with
+        whitelist::clear_whitelist().unwrap();
+        let grouped = get_connected_devices(None).unwrap();
+        assert_eq!(grouped[0].children[0].policy_action, Some(PolicyAction::Prompt));
+    }
+
+    #[test]
+    fn class_filter_applies_before_grouping() {
+        let _env = env_with_devices(&format!("[{},{},{},{}]", STICK, COMBO, COMBO_HID, COMBO_KEYBOARD));
+        let keyboards = get_connected_devices(Some(vec!["KEYBOARD".to_string()])).unwrap();
+        assert_eq!(names(&keyboards), vec!["HID Keyboard Device"]);
+        assert!(keyboards[0].children.is_empty());
+        assert_eq!(keyboards[0].bus_type.as_deref(), Some("USB"));
+
+        assert_eq!(get_connected_devices(Some(Vec::new())).unwrap().len(), 2);
+        assert!(get_connected_devices(Some(vec!["Printer".to_string()])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn disable_device_goes_through_the_backend() {
+        let env = TestEnv::new(FixtureBackend::new());
+        disable_device("USB\\VID_046D&PID_C077\\1".to_string()).unwrap();
+        enable_device("USB\\VID_046D&PID_C077\\1".to_string()).unwrap();
+        assert_eq!(
+            env.backend.actions(),
+            vec!["disable_device:USB\\VID_046D&PID_C077\\1", "enable_device:USB\\VID_046D&PID_C077\\1"]
+        );
+    }
 }
@@ -0,0 +1,35 @@
+use std::path::Path;
+use crate::models::{BlockDevice, DiskVolume};
+use crate::services::encryption::{self, EncryptionReport};
+
+/// Inspects a block device or disk image for LUKS, BitLocker or VeraCrypt volumes at the
+/// given partition offsets (the whole device when there are none)
+#[tauri::command]
+pub fn check_encryption(path: String, volumes: Option<Vec<DiskVolume>>) -> Result<EncryptionReport, String> {
+    check_disk(&BlockDevice { path, volumes: volumes.unwrap_or_default() })
+}
+
+/// Inspects the volumes of a disk reported by the backend
+pub fn check_disk(disk: &BlockDevice) -> Result<EncryptionReport, String> {
+    encryption::inspect_path(Path::new(&disk.path), &disk.volumes)
+        .map_err(|e| format!("Cannot inspect {}: {}", disk.path, e))
+}
+
+/// Describes the volumes that break the encryption requirement, e.g. `/dev/sdb partition 1 (FAT32)`
+pub fn unencrypted_volumes(report: &EncryptionReport) -> Vec<String> {
+    if report.volumes.is_empty() {
+        return vec![format!("{} (no volumes)", report.path)];
+    }
+    report.volumes
+        .iter()
+        .filter(|v| !v.encryption.is_encrypted())
+        .map(|v| {
+            let mut text = report.path.clone();
+            if let Some(partition) = v.partition {
+                text.push_str(&format!(" partition {}", partition));
+            }
+            text.push_str(&format!(" ({})", v.filesystem.as_deref().unwrap_or("no filesystem")));
+            text
+        })
+        .collect()
+}
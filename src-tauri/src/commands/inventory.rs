@@ -0,0 +1,322 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use crate::commands::approvals::current_user;
+use crate::models::{
+    ConnectionRecord, DiskLayout, InventoryData, InventoryQuery, InventoryRecord, TrustDecision, TrustDecisionKind, UsbDevice,
+};
+use crate::services::storage;
+
+const INVENTORY_FILE: &str = "inventory.json";
+// Connections and decisions kept per device; counts keep going past this
+const MAX_HISTORY: usize = 100;
+
+fn read_inventory() -> Result<InventoryData, String> {
+    storage::read_json(INVENTORY_FILE).map_err(|e| e.to_string())
+}
+
+fn write_inventory(data: &InventoryData) -> Result<(), String> {
+    storage::write_json(INVENTORY_FILE, data).map_err(|e| e.to_string())
+}
+
+fn trim_history<T>(items: &mut Vec<T>) {
+    if items.len() > MAX_HISTORY {
+        items.drain(..items.len() - MAX_HISTORY);
+    }
+}
+
+/// Adds a connection to the device's record, creating the record on first sight
+fn connect(data: &mut InventoryData, device: &UsbDevice, now: &str, user: &str) {
+    let index = match data.devices.iter().position(|r| r.instance_id == device.instance_id) {
+        Some(index) => index,
+        None => {
+            data.devices.push(InventoryRecord {
+                instance_id: device.instance_id.clone(),
+                friendly_name: device.friendly_name.clone(),
+                device_class: device.device_class.clone(),
+                vendor_id: None,
+                product_id: None,
+                serial: None,
+                first_seen: now.to_string(),
+                last_seen: now.to_string(),
+                connection_count: 0,
+                connections: Vec::new(),
+                decisions: Vec::new(),
+                disk_layouts: Vec::new(),
+            });
+            data.devices.len() - 1
+        }
+    };
+
+    let record = &mut data.devices[index];
+    // Names and IDs can improve once drivers are installed, so keep the latest
+    record.friendly_name = device.friendly_name.clone();
+    record.device_class = device.device_class.clone();
+    record.vendor_id = device.vendor_id.clone().or(record.vendor_id.take());
+    record.product_id = device.product_id.clone().or(record.product_id.take());
+    record.serial = device.serial.clone().or(record.serial.take());
+    record.last_seen = now.to_string();
+    record.connection_count += 1;
+    record.connections.push(ConnectionRecord {
+        connected_at: now.to_string(),
+        disconnected_at: None,
+        user: user.to_string(),
+    });
+    trim_history(&mut record.connections);
+}
+
+/// Records a new connection of a device
+pub fn record_connection(device: &UsbDevice) -> Result<(), String> {
+    let mut data = read_inventory()?;
+    connect(&mut data, device, &Utc::now().to_rfc3339(), &current_user());
+    write_inventory(&data)
+}
+
+/// Records that a device was unplugged
+pub fn record_disconnection(device: &UsbDevice) -> Result<(), String> {
+    let mut data = read_inventory()?;
+    let Some(record) = data.devices.iter_mut().find(|r| r.instance_id == device.instance_id) else {
+        return Ok(());
+    };
+
+    let now = Utc::now().to_rfc3339();
+    record.last_seen = now.clone();
+    if let Some(open) = record.connections.last_mut().filter(|c| c.disconnected_at.is_none()) {
+        open.disconnected_at = Some(now);
+    }
+    write_inventory(&data)
+}
+
+/// Reconciles the inventory with the devices attached when monitoring starts.
+/// Devices plugged in while the app wasn't running count as new connections, and
+/// connections still open for devices that are gone are closed at their last sighting.
+pub fn record_present(devices: &[UsbDevice]) -> Result<(), String> {
+    let mut data = read_inventory()?;
+    let now = Utc::now().to_rfc3339();
+    let user = current_user();
+
+    for record in &mut data.devices {
+        if devices.iter().any(|d| d.instance_id == record.instance_id) {
+            continue;
+        }
+        if let Some(open) = record.connections.last_mut().filter(|c| c.disconnected_at.is_none()) {
+            open.disconnected_at = Some(record.last_seen.clone());
+        }
+    }
+
+    for device in devices {
+        let still_connected = data.devices
+            .iter_mut()
+            .find(|r| r.instance_id == device.instance_id)
+            .filter(|r| r.connections.last().is_some_and(|c| c.disconnected_at.is_none()));
+        match still_connected {
+            Some(record) => record.last_seen = now.clone(),
+            None => connect(&mut data, device, &now, &user),
+        }
+    }
+
+    write_inventory(&data)
+}
+
+/// Appends a trust decision to a device's record (devices never seen are ignored)
+pub fn record_decision(instance_id: &str, decision: TrustDecisionKind) -> Result<(), String> {
+    let mut data = read_inventory()?;
+    let Some(record) = data.devices.iter_mut().find(|r| r.instance_id == instance_id) else {
+        return Ok(());
+    };
+
+    record.decisions.push(TrustDecision {
+        timestamp: Utc::now().to_rfc3339(),
+        decision,
+        user: current_user(),
+    });
+    trim_history(&mut record.decisions);
+    write_inventory(&data)
+}
+
+/// Replaces the disk layouts stored for a device with the latest inspection
+pub fn record_disk_layouts(instance_id: &str, layouts: Vec<DiskLayout>) -> Result<(), String> {
+    let mut data = read_inventory()?;
+    let Some(record) = data.devices.iter_mut().find(|r| r.instance_id == instance_id) else {
+        return Ok(());
+    };
+
+    record.disk_layouts = layouts;
+    write_inventory(&data)
+}
+
+fn parse_bound(value: Option<&str>) -> Result<Option<DateTime<FixedOffset>>, String> {
+    value
+        .map(|v| DateTime::parse_from_rfc3339(v).map_err(|e| format!("Invalid time '{}': {}", v, e)))
+        .transpose()
+}
+
+/// Whether the device was connected at some point inside `[since, until]`
+fn seen_between(
+    record: &InventoryRecord,
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+) -> bool {
+    let overlaps = |start: &str, end: &str| {
+        let (Ok(start), Ok(end)) = (DateTime::parse_from_rfc3339(start), DateTime::parse_from_rfc3339(end)) else {
+            return false;
+        };
+        since.is_none_or(|s| end >= s) && until.is_none_or(|u| start <= u)
+    };
+
+    if record.connections.is_empty() {
+        return overlaps(&record.first_seen, &record.last_seen);
+    }
+    record.connections.iter().any(|c| {
+        let end = c.disconnected_at.as_deref().unwrap_or(&record.last_seen);
+        overlaps(&c.connected_at, end)
+    })
+}
+
+fn matches_search(record: &InventoryRecord, search: &str) -> bool {
+    let search = search.to_lowercase();
+    [
+        Some(&record.friendly_name),
+        Some(&record.instance_id),
+        record.vendor_id.as_ref(),
+        record.product_id.as_ref(),
+        record.serial.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|field| field.to_lowercase().contains(&search))
+}
+
+/// Gets every device ever seen, most recently seen first, optionally filtered
+#[tauri::command]
+pub fn get_device_inventory(query: Option<InventoryQuery>) -> Result<Vec<InventoryRecord>, String> {
+    let query = query.unwrap_or_default();
+    let since = parse_bound(query.seen_since.as_deref())?;
+    let until = parse_bound(query.seen_until.as_deref())?;
+
+    let mut records: Vec<InventoryRecord> = read_inventory()?
+        .devices
+        .into_iter()
+        .filter(|r| query.search.as_deref().is_none_or(|s| matches_search(r, s)))
+        .filter(|r| query.device_class.as_deref().is_none_or(|c| r.device_class.eq_ignore_ascii_case(c)))
+        .filter(|r| (since.is_none() && until.is_none()) || seen_between(r, since, until))
+        .collect();
+
+    records.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    const CRUZER: &str = "USB\\VID_0781&PID_5567\\4C530001";
+    const KEYBOARD: &str = "USB\\VID_046D&PID_C31C\\5&1A2B3C4D&0&1";
+
+    fn device(instance_id: &str, name: &str, device_class: &str) -> UsbDevice {
+        UsbDevice { instance_id: instance_id.to_string(), friendly_name: name.to_string(), device_class: device_class.to_string(), ..Default::default() }
+    }
+
+    fn record(instance_id: &str) -> InventoryRecord {
+        read_inventory().unwrap().devices.into_iter().find(|r| r.instance_id == instance_id).unwrap()
+    }
+
+    fn query(search: Option<&str>, device_class: Option<&str>, since: Option<&str>, until: Option<&str>) -> Vec<String> {
+        let query = InventoryQuery {
+            search: search.map(str::to_string),
+            device_class: device_class.map(str::to_string),
+            seen_since: since.map(str::to_string),
+            seen_until: until.map(str::to_string),
+        };
+        get_device_inventory(Some(query)).unwrap().into_iter().map(|r| r.instance_id).collect()
+    }
+
+    /// A Cruzer plugged in on the 5th and 7th, and a keyboard plugged in on the 6th and still attached on the 8th
+    fn seed() {
+        let cruzer = UsbDevice { vendor_id: Some("0781".to_string()), product_id: Some("5567".to_string()), serial: Some("4C530001".to_string()), ..device(CRUZER, "SanDisk Cruzer", "DiskDrive") };
+        let mut data = InventoryData::default();
+        connect(&mut data, &cruzer, "2026-01-05T09:00:00+00:00", "alice");
+        data.devices[0].connections[0].disconnected_at = Some("2026-01-05T10:00:00+00:00".to_string());
+        connect(&mut data, &device(KEYBOARD, "USB Keyboard", "Keyboard"), "2026-01-06T09:00:00+00:00", "bob");
+        connect(&mut data, &cruzer, "2026-01-07T09:00:00+00:00", "alice");
+        data.devices[0].connections[1].disconnected_at = Some("2026-01-07T10:00:00+00:00".to_string());
+        data.devices[0].last_seen = "2026-01-07T10:00:00+00:00".to_string();
+        data.devices[1].last_seen = "2026-01-08T12:00:00+00:00".to_string();
+        write_inventory(&data).unwrap();
+    }
+
+    #[test]
+    fn connections_update_first_and_last_seen() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        record_connection(&device(CRUZER, "USB Mass Storage Device", "DiskDrive")).unwrap();
+        let first = record(CRUZER);
+        assert_eq!(first.connection_count, 1);
+        assert_eq!(first.first_seen, first.last_seen);
+        assert!(first.connections[0].disconnected_at.is_none());
+
+        record_disconnection(&device(CRUZER, "USB Mass Storage Device", "DiskDrive")).unwrap();
+        record_connection(&device(CRUZER, "SanDisk Cruzer", "DiskDrive")).unwrap();
+        let second = record(CRUZER);
+        assert_eq!(second.connection_count, 2);
+        assert_eq!(second.first_seen, first.first_seen);
+        assert!(second.last_seen >= first.last_seen);
+        assert_eq!(second.friendly_name, "SanDisk Cruzer");
+        assert!(second.connections[0].disconnected_at.is_some());
+        assert!(second.connections[1].disconnected_at.is_none());
+        assert_eq!(second.connections[1].user, current_user());
+    }
+
+    #[test]
+    fn history_is_trimmed_but_counts_keep_going() {
+        let mut data = InventoryData::default();
+        for _ in 0..MAX_HISTORY + 5 {
+            connect(&mut data, &device(CRUZER, "SanDisk Cruzer", "DiskDrive"), "2026-01-05T09:00:00+00:00", "alice");
+        }
+        assert_eq!(data.devices.len(), 1);
+        assert_eq!(data.devices[0].connection_count as usize, MAX_HISTORY + 5);
+        assert_eq!(data.devices[0].connections.len(), MAX_HISTORY);
+    }
+
+    #[test]
+    fn decisions_are_recorded_in_order() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        record_decision(CRUZER, TrustDecisionKind::Blocked).unwrap();
+        assert!(read_inventory().unwrap().devices.is_empty());
+
+        record_connection(&device(CRUZER, "SanDisk Cruzer", "DiskDrive")).unwrap();
+        record_decision(CRUZER, TrustDecisionKind::Blocked).unwrap();
+        record_decision(CRUZER, TrustDecisionKind::Approved).unwrap();
+        record_decision(CRUZER, TrustDecisionKind::Trusted).unwrap();
+
+        let decisions = record(CRUZER).decisions;
+        let kinds: Vec<TrustDecisionKind> = decisions.iter().map(|d| d.decision).collect();
+        assert_eq!(kinds, vec![TrustDecisionKind::Blocked, TrustDecisionKind::Approved, TrustDecisionKind::Trusted]);
+        assert!(decisions.iter().all(|d| d.user == current_user()));
+    }
+
+    #[test]
+    fn query_filters_by_search_and_class() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        seed();
+
+        assert_eq!(query(None, None, None, None), vec![KEYBOARD, CRUZER]);
+        assert_eq!(query(Some("cruzer"), None, None, None), vec![CRUZER]);
+        assert_eq!(query(Some("4c530001"), None, None, None), vec![CRUZER]);
+        assert_eq!(query(Some("046d"), None, None, None), vec![KEYBOARD]);
+        assert_eq!(query(None, Some("keyboard"), None, None), vec![KEYBOARD]);
+        assert!(query(Some("cruzer"), Some("Keyboard"), None, None).is_empty());
+    }
+
+    #[test]
+    fn query_filters_by_connection_time() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        seed();
+
+        // Between the Cruzer's two connections only the keyboard was attached
+        assert_eq!(query(None, None, Some("2026-01-06T00:00:00Z"), Some("2026-01-06T23:59:59Z")), vec![KEYBOARD]);
+        assert_eq!(query(None, None, Some("2026-01-05T09:30:00Z"), Some("2026-01-05T09:45:00Z")), vec![CRUZER]);
+        assert_eq!(query(None, None, Some("2026-01-08T00:00:00Z"), None), vec![KEYBOARD]);
+        assert_eq!(query(None, None, None, Some("2026-01-05T23:59:59Z")), vec![CRUZER]);
+        assert!(get_device_inventory(Some(InventoryQuery { seen_since: Some("yesterday".to_string()), ..Default::default() })).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use chrono::Utc;
+use crate::models::{KernelModule, ModuleAction, ModuleData, ModuleDenylistEntry};
+use crate::services::backend::{self, SystemBackend};
+use crate::services::kernel_modules::normalize_name;
+use crate::services::storage;
+use crate::commands::logs;
+
+const MODULES_FILE: &str = "modules.json";
+
+/// Reads the module baseline and denylist (empty when the file doesn't exist yet)
+pub fn read_module_data() -> Result<ModuleData, String> {
+    storage::read_json(MODULES_FILE).map_err(|e| e.to_string())
+}
+
+fn write_module_data(data: &ModuleData) -> Result<(), String> {
+    storage::write_json(MODULES_FILE, data).map_err(|e| e.to_string())
+}
+
+/// Lists the modules reported by a backend, marking which were in the baseline and
+/// which are on the denylist
+pub fn list_modules(backend: &dyn SystemBackend, data: &ModuleData) -> Result<Vec<KernelModule>, String> {
+    let json_output = backend.kernel_modules_json()
+        .map_err(|e| e.to_string())?;
+    let mut modules: Vec<KernelModule> = backend::parse_list(&json_output)?;
+
+    let baseline: HashSet<String> = data.baseline.iter().map(|n| normalize_name(n)).collect();
+    for module in &mut modules {
+        let key = normalize_name(&module.name);
+        module.in_baseline = baseline.contains(&key);
+        module.denied = data.denylist.iter().find(|e| normalize_name(&e.name) == key).map(|e| e.action);
+    }
+    modules.sort_by_key(|m| m.name.to_lowercase());
+    Ok(modules)
+}
+
+/// Modules loaded outside the baseline or on the denylist that haven't been reported yet.
+/// They are recorded in modules.json; a module is forgotten when it unloads so a reload is reported again.
+pub fn take_unreported(backend: &dyn SystemBackend) -> Result<Vec<KernelModule>, String> {
+    let mut data = read_module_data()?;
+    let modules = list_modules(backend, &data)?;
+
+    let loaded: HashSet<String> = modules.iter().map(|m| normalize_name(&m.name)).collect();
+    let before = data.reported.len();
+    data.reported.retain(|name| loaded.contains(name));
+    let mut reported: HashSet<String> = data.reported.iter().cloned().collect();
+    let unreported: Vec<KernelModule> = modules
+        .into_iter()
+        .filter(|m| !m.in_baseline || m.denied.is_some())
+        .filter(|m| reported.insert(normalize_name(&m.name)))
+        .collect();
+
+    if data.reported.len() != before || !unreported.is_empty() {
+        data.reported.extend(unreported.iter().map(|m| normalize_name(&m.name)));
+        write_module_data(&data)?;
+    }
+    Ok(unreported)
+}
+
+/// Gets the loaded kernel modules (Linux) or running kernel drivers (Windows)
+#[tauri::command]
+pub fn get_kernel_modules() -> Result<Vec<KernelModule>, String> {
+    list_modules(backend::current().as_ref(), &read_module_data()?)
+}
+
+/// Stores the currently loaded modules as the baseline. Returns how many there are.
+pub fn save_baseline() -> Result<usize, String> {
+    let mut data = read_module_data()?;
+    let modules = list_modules(backend::current().as_ref(), &data)?;
+    data.baseline = modules.into_iter().map(|m| m.name).collect();
+    data.baseline_taken_at = Some(Utc::now().to_rfc3339());
+    write_module_data(&data)?;
+    Ok(data.baseline.len())
+}
+
+/// Makes the currently loaded modules the baseline new loads are compared against
+#[tauri::command]
+pub fn reset_module_baseline() -> Result<usize, String> {
+    let count = save_baseline()?;
+    let _ = logs::add_event_log(
+        "WARN".to_string(),
+        format!("Kernel module baseline reset: {} modules", count),
+        None,
+    );
+    Ok(count)
+}
+
+/// Gets the module denylist
+#[tauri::command]
+pub fn get_module_denylist() -> Result<Vec<ModuleDenylistEntry>, String> {
+    Ok(read_module_data()?.denylist)
+}
+
+/// Adds a module to the denylist, or changes the action of an existing entry
+#[tauri::command]
+pub fn add_module_denylist_entry(name: String, action: ModuleAction) -> Result<ModuleDenylistEntry, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(format!("Invalid module name '{}'", name));
+    }
+
+    let mut data = read_module_data()?;
+    let key = normalize_name(&name);
+    data.denylist.retain(|e| normalize_name(&e.name) != key);
+    let entry = ModuleDenylistEntry {
+        name: name.clone(),
+        action,
+        added_at: Utc::now().to_rfc3339(),
+    };
+    data.denylist.push(entry.clone());
+    write_module_data(&data)?;
+
+    let _ = logs::add_event_log(
+        "INFO".to_string(),
+        format!("Kernel module '{}' added to the denylist ({:?})", name, action),
+        None,
+    );
+    Ok(entry)
+}
+
+/// Removes a module from the denylist
+#[tauri::command]
+pub fn remove_module_denylist_entry(name: String) -> Result<(), String> {
+    let mut data = read_module_data()?;
+    let key = normalize_name(&name);
+    let before = data.denylist.len();
+    data.denylist.retain(|e| normalize_name(&e.name) != key);
+    if data.denylist.len() == before {
+        return Ok(());
+    }
+    write_module_data(&data)?;
+
+    let _ = logs::add_event_log(
+        "WARN".to_string(),
+        format!("Kernel module '{}' removed from the denylist", name.trim()),
+        None,
+    );
+    Ok(())
+}
+
+/// Unloads a kernel module (Linux) or stops a kernel driver (Windows)
+#[tauri::command]
+pub fn unload_kernel_module(name: String) -> Result<(), String> {
+    backend::current().unload_kernel_module(&name)
+        .map_err(|e| e.to_string())?;
+
+    let _ = logs::add_event_log(
+        "BLOCK".to_string(),
+        format!("Kernel module unloaded: {}", name),
+        None,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    /// A backend reporting these loaded modules
+    fn loaded(names: &[&str]) -> FixtureBackend {
+        let modules: Vec<String> = names.iter().map(|n| format!(r#"{{"name":"{}"}}"#, n)).collect();
+        FixtureBackend::new().with_output("kernel_modules", &format!("[{}]", modules.join(",")))
+    }
+
+    fn names(modules: &[KernelModule]) -> Vec<&str> {
+        modules.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    fn env_with_baseline(baseline: &[&str]) -> TestEnv {
+        let env = TestEnv::new(loaded(baseline));
+        save_baseline().unwrap();
+        env
+    }
+
+    #[test]
+    fn list_modules_marks_baseline_and_denylist() {
+        let _env = env_with_baseline(&["ext4", "snd-hda-intel"]);
+        add_module_denylist_entry("usb-storage".to_string(), ModuleAction::Unload).unwrap();
+
+        let modules = list_modules(&loaded(&["snd_hda_intel", "usb_storage", "Ext4"]), &read_module_data().unwrap()).unwrap();
+        assert_eq!(names(&modules), vec!["Ext4", "snd_hda_intel", "usb_storage"]);
+        assert!(modules[0].in_baseline && modules[1].in_baseline);
+        assert!(!modules[2].in_baseline);
+        assert_eq!(modules[2].denied, Some(ModuleAction::Unload));
+    }
+
+    #[test]
+    fn new_modules_are_reported_once_across_restarts() {
+        let _env = env_with_baseline(&["ext4", "snd_hda_intel"]);
+        let now = loaded(&["ext4", "snd_hda_intel", "usb_storage", "uas"]);
+
+        assert_eq!(names(&take_unreported(&now).unwrap()), vec!["uas", "usb_storage"]);
+        assert!(take_unreported(&now).unwrap().is_empty());
+
+        // What was reported is read back from modules.json, as after a restart
+        let mut reported = read_module_data().unwrap().reported;
+        reported.sort();
+        assert_eq!(reported, vec!["uas", "usb_storage"]);
+        assert!(take_unreported(&loaded(&["ext4", "usb-storage", "uas"])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unloaded_modules_are_forgotten_so_a_reload_is_reported() {
+        let _env = env_with_baseline(&["ext4"]);
+
+        assert_eq!(names(&take_unreported(&loaded(&["ext4", "usb_storage"])).unwrap()), vec!["usb_storage"]);
+        assert!(take_unreported(&loaded(&["ext4"])).unwrap().is_empty());
+        assert!(read_module_data().unwrap().reported.is_empty());
+        assert_eq!(names(&take_unreported(&loaded(&["ext4", "usb_storage"])).unwrap()), vec!["usb_storage"]);
+    }
+
+    #[test]
+    fn denylisted_baseline_modules_are_reported() {
+        let _env = env_with_baseline(&["ext4", "bluetooth"]);
+        add_module_denylist_entry("bluetooth".to_string(), ModuleAction::Flag).unwrap();
+
+        let unreported = take_unreported(&loaded(&["ext4", "bluetooth"])).unwrap();
+        assert_eq!(names(&unreported), vec!["bluetooth"]);
+        assert!(unreported[0].in_baseline);
+        assert_eq!(unreported[0].denied, Some(ModuleAction::Flag));
+    }
+
+    #[test]
+    fn module_data_without_reported_still_loads() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        storage::write_json(MODULES_FILE, &serde_json::json!({ "baseline": ["ext4"], "baseline_taken_at": null, "denylist": [] })).unwrap();
+        let data = read_module_data().unwrap();
+        assert_eq!(data.baseline, vec!["ext4"]);
+        assert!(data.reported.is_empty());
+    }
+}
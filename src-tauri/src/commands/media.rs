@@ -0,0 +1,18 @@
+use std::path::Path;
+use crate::services::backend;
+use crate::services::media_scan::{self, MediaScanReport};
+
+/// Scans a folder or mounted drive for autorun files, executable shortcuts,
+/// disguised executables and scripts at its root
+#[tauri::command]
+pub fn scan_media(path: String) -> Result<MediaScanReport, String> {
+    media_scan::scan(Path::new(&path))
+        .map_err(|e| format!("Cannot scan {}: {}", path, e))
+}
+
+/// Folders where a USB device's volumes are mounted (empty until the OS has mounted them)
+pub fn mount_points(instance_id: &str) -> Result<Vec<String>, String> {
+    let json = backend::current().mount_points_json(instance_id)
+        .map_err(|e| e.to_string())?;
+    backend::parse_list(&json)
+}
@@ -8,5 +8,17 @@ pub mod network;
 pub mod cleanup;
 pub mod firewall;
 pub mod processes;
+pub mod policy;
+pub mod settings;
+pub mod approvals;
+pub mod inventory;
+pub mod bundle;
+pub mod encryption;
+pub mod partitions;
+pub mod media;
+pub mod yara;
+pub mod bluetooth;
+pub mod capture;
+pub mod kernel_modules;
 
 
@@ -0,0 +1,10 @@
+use std::path::Path;
+use crate::models::DiskLayout;
+use crate::services::partition;
+
+/// Lists the partitions of a block device or disk image and flags suspicious layouts
+#[tauri::command]
+pub fn inspect_partitions(path: String) -> Result<DiskLayout, String> {
+    partition::inspect_layout_path(Path::new(&path))
+        .map_err(|e| format!("Cannot inspect {}: {}", path, e))
+}
@@ -0,0 +1,190 @@
+use crate::models::{ClassRule, DevicePolicy, PolicyAction, UsbDevice};
+use crate::services::storage;
+
+const POLICY_FILE: &str = "policies.json";
+
+/// Reads the persisted device policy (defaults when the file doesn't exist yet)
+pub fn read_policy() -> Result<DevicePolicy, String> {
+    storage::read_json(POLICY_FILE).map_err(|e| e.to_string())
+}
+
+/// Gets the device policy
+#[tauri::command]
+pub fn get_device_policy() -> Result<DevicePolicy, String> {
+    read_policy()
+}
+
+/// Checks that every class rule can match something
+pub fn validate_policy(policy: &DevicePolicy) -> Result<(), String> {
+    if let Some(rule) = policy.class_rules.iter().find(|r| r.class_code.is_none() && r.device_class.is_none()) {
+        return Err(format!("Class rule '{}' needs a class code or device class", rule.label));
+    }
+    Ok(())
+}
+
+/// Validates and persists a device policy without logging
+pub fn write_policy(policy: &DevicePolicy) -> Result<(), String> {
+    validate_policy(policy)?;
+    storage::write_json(POLICY_FILE, policy).map_err(|e| e.to_string())
+}
+
+/// Replaces the device policy
+#[tauri::command]
+pub fn set_device_policy(policy: DevicePolicy) -> Result<(), String> {
+    write_policy(&policy)?;
+    
+    let _ = crate::commands::logs::add_event_log(
+        "WARN".to_string(),
+        format!(
+            "Device policy updated: {} class rules, default {:?}",
+            policy.class_rules.len(),
+            policy.default_action
+        ),
+        None,
+    );
+    
+    Ok(())
+}
+
+/// Whether a class rule applies to a device
+pub fn rule_matches(rule: &ClassRule, device: &UsbDevice) -> bool {
+    let code_matches = rule.class_code
+        .is_some_and(|code| device.interface_classes.contains(&code));
+    let class_matches = rule.device_class.as_deref()
+        .is_some_and(|class| class.eq_ignore_ascii_case(&device.device_class));
+    code_matches || class_matches
+}
+
+/// Decides what happens to a device.
+/// Whitelisted devices are allowed and untrusted devices with BadUSB risk flags are
+/// blocked; otherwise the most restrictive matching class rule wins (a storage stick
+/// that also exposes HID hits both rules), then the default.
+pub fn evaluate(policy: &DevicePolicy, device: &UsbDevice) -> PolicyAction {
+    if device.is_trusted {
+        return PolicyAction::Allow;
+    }
+    if !device.risk_flags.is_empty() {
+        return PolicyAction::Block;
+    }
+    
+    let matched: Vec<PolicyAction> = policy.class_rules.iter()
+        .filter(|rule| rule_matches(rule, device))
+        .map(|rule| rule.action)
+        .collect();
+    
+    if matched.contains(&PolicyAction::Block) {
+        PolicyAction::Block
+    } else if matched.contains(&PolicyAction::Prompt) {
+        PolicyAction::Prompt
+    } else if matched.contains(&PolicyAction::Allow) {
+        PolicyAction::Allow
+    } else {
+        policy.default_action
+    }
+}
+
+/// The evaluated outcome for a device, falling back to its trust flag when it wasn't evaluated
+pub fn outcome(device: &UsbDevice) -> PolicyAction {
+    device.policy_action.unwrap_or(if device.is_trusted {
+        PolicyAction::Allow
+    } else {
+        PolicyAction::Block
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RiskFlag;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    fn class_rule(class_code: Option<u8>, device_class: Option<&str>, action: PolicyAction) -> ClassRule {
+        ClassRule { class_code, device_class: device_class.map(String::from), action, label: "rule".to_string() }
+    }
+
+    fn policy(rules: Vec<ClassRule>, default_action: PolicyAction) -> DevicePolicy {
+        DevicePolicy { class_rules: rules, default_action }
+    }
+
+    fn device(device_class: &str, interface_classes: &[u8]) -> UsbDevice {
+        UsbDevice {
+            instance_id: "USB\\VID_0781&PID_5567\\4C530001".to_string(),
+            device_class: device_class.to_string(),
+            interface_classes: interface_classes.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn trusted_devices_are_allowed_whatever_the_rules() {
+        let lockdown = policy(vec![class_rule(Some(0x08), None, PolicyAction::Block)], PolicyAction::Block);
+        let mut stick = device("DiskDrive", &[0x08]);
+        stick.is_trusted = true;
+        // Trust is checked before the BadUSB heuristics too
+        stick.risk_flags = vec![RiskFlag::StorageWithHid];
+        assert_eq!(evaluate(&lockdown, &stick), PolicyAction::Allow);
+    }
+
+    #[test]
+    fn risk_flags_block_untrusted_devices() {
+        let open = policy(vec![class_rule(Some(0x03), None, PolicyAction::Allow)], PolicyAction::Allow);
+        let mut keyboard = device("Keyboard", &[0x03]);
+        assert_eq!(evaluate(&open, &keyboard), PolicyAction::Allow);
+        keyboard.risk_flags = vec![RiskFlag::ProgrammableBoardKeyboard];
+        assert_eq!(evaluate(&open, &keyboard), PolicyAction::Block);
+    }
+
+    #[test]
+    fn most_restrictive_matching_rule_wins() {
+        let rules = policy(
+            vec![
+                class_rule(Some(0x03), None, PolicyAction::Allow),
+                class_rule(Some(0x08), None, PolicyAction::Prompt),
+                class_rule(None, Some("image"), PolicyAction::Block),
+            ],
+            PolicyAction::Allow,
+        );
+        assert_eq!(evaluate(&rules, &device("Keyboard", &[0x03])), PolicyAction::Allow);
+        assert_eq!(evaluate(&rules, &device("DiskDrive", &[0x08, 0x03])), PolicyAction::Prompt);
+        assert_eq!(evaluate(&rules, &device("Image", &[0x0E, 0x03, 0x08])), PolicyAction::Block);
+    }
+
+    #[test]
+    fn rule_order_does_not_matter() {
+        let allow_first = vec![class_rule(Some(0x08), None, PolicyAction::Allow), class_rule(None, Some("DiskDrive"), PolicyAction::Block)];
+        let block_first: Vec<ClassRule> = allow_first.iter().rev().cloned().collect();
+        let stick = device("DiskDrive", &[0x08]);
+        assert_eq!(evaluate(&policy(allow_first, PolicyAction::Allow), &stick), PolicyAction::Block);
+        assert_eq!(evaluate(&policy(block_first, PolicyAction::Allow), &stick), PolicyAction::Block);
+    }
+
+    #[test]
+    fn default_applies_when_no_rule_matches() {
+        let rules = vec![class_rule(Some(0x08), Some("DiskDrive"), PolicyAction::Block)];
+        let printer = device("Printer", &[0x07]);
+        assert_eq!(evaluate(&policy(rules.clone(), PolicyAction::Prompt), &printer), PolicyAction::Prompt);
+        assert_eq!(evaluate(&policy(rules, PolicyAction::Allow), &printer), PolicyAction::Allow);
+        assert_eq!(evaluate(&DevicePolicy::default(), &printer), PolicyAction::Block);
+    }
+
+    #[test]
+    fn outcome_falls_back_to_trust() {
+        let mut stick = device("DiskDrive", &[0x08]);
+        assert_eq!(outcome(&stick), PolicyAction::Block);
+        stick.is_trusted = true;
+        assert_eq!(outcome(&stick), PolicyAction::Allow);
+        stick.policy_action = Some(PolicyAction::Prompt);
+        assert_eq!(outcome(&stick), PolicyAction::Prompt);
+    }
+
+    #[test]
+    fn rules_without_a_match_condition_are_rejected() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        let mut empty = class_rule(None, None, PolicyAction::Block);
+        empty.label = "Everything".to_string();
+        let invalid = policy(vec![empty], PolicyAction::Allow);
+        assert_eq!(set_device_policy(invalid), Err("Class rule 'Everything' needs a class code or device class".to_string()));
+        assert_eq!(read_policy().unwrap().default_action, PolicyAction::Block);
+    }
+}
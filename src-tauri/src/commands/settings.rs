@@ -0,0 +1,173 @@
+use crate::models::{EncryptionRequirement, EnforcementMode, PrivacyMode, Settings, TimeWindow};
+use crate::services::{bundle, storage};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Reads the persisted settings (defaults when the file doesn't exist yet)
+pub fn read_settings() -> Result<Settings, String> {
+    storage::read_json(SETTINGS_FILE).map_err(|e| e.to_string())
+}
+
+/// Gets the application settings
+#[tauri::command]
+pub fn get_settings() -> Result<Settings, String> {
+    read_settings()
+}
+
+/// Sets how untrusted devices are handled when they are connected
+#[tauri::command]
+pub fn set_enforcement_mode(mode: EnforcementMode) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    if settings.enforcement_mode == mode {
+        return Ok(());
+    }
+
+    settings.enforcement_mode = mode;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let level = if mode == EnforcementMode::Monitor { "WARN" } else { "INFO" };
+    let _ = crate::commands::logs::add_event_log(
+        level.to_string(),
+        format!("Enforcement mode set to {:?}", mode),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Turns automatic blocking of suspected keystroke injectors on or off
+#[tauri::command]
+pub fn set_keystroke_injection_blocking(enabled: bool) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    if settings.block_keystroke_injection == enabled {
+        return Ok(());
+    }
+
+    settings.block_keystroke_injection = enabled;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let (level, state) = if enabled { ("INFO", "enabled") } else { ("WARN", "disabled") };
+    let _ = crate::commands::logs::add_event_log(
+        level.to_string(),
+        format!("Keystroke injection blocking {}", state),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Turns automatic blocking of storage devices with high-severity scan findings on or off
+#[tauri::command]
+pub fn set_suspicious_media_blocking(enabled: bool) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    if settings.block_suspicious_media == enabled {
+        return Ok(());
+    }
+
+    settings.block_suspicious_media = enabled;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let (level, state) = if enabled { ("INFO", "enabled") } else { ("WARN", "disabled") };
+    let _ = crate::commands::logs::add_event_log(
+        level.to_string(),
+        format!("Suspicious removable media blocking {}", state),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Sets how long a device may wait for approval before it is denied
+#[tauri::command]
+pub fn set_approval_timeout(seconds: u64) -> Result<(), String> {
+    if seconds == 0 {
+        return Err("Approval timeout must be at least one second".to_string());
+    }
+
+    let mut settings = read_settings()?;
+    settings.approval_timeout_secs = seconds;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Approval timeout set to {} seconds", seconds),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Sets (or clears) the public key that imported policy bundles must be signed with
+#[tauri::command]
+pub fn set_bundle_public_key(public_key: Option<String>) -> Result<(), String> {
+    let public_key = public_key
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+    if let Some(key) = &public_key {
+        bundle::parse_verifying_key(key).map_err(|e| e.to_string())?;
+    }
+
+    let mut settings = read_settings()?;
+    settings.bundle_public_key = public_key;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let message = if settings.bundle_public_key.is_some() {
+        "Policy bundle public key updated"
+    } else {
+        "Policy bundle public key cleared: bundle imports are disabled"
+    };
+    let _ = crate::commands::logs::add_event_log("WARN".to_string(), message.to_string(), None);
+
+    Ok(())
+}
+
+/// Sets whether unencrypted removable storage is allowed, warned about or blocked
+#[tauri::command]
+pub fn set_encryption_requirement(requirement: EncryptionRequirement) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    if settings.encryption_requirement == requirement {
+        return Ok(());
+    }
+
+    settings.encryption_requirement = requirement;
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let level = if requirement == EncryptionRequirement::Off { "WARN" } else { "INFO" };
+    let _ = crate::commands::logs::add_event_log(
+        level.to_string(),
+        format!("Removable storage encryption requirement set to {:?}", requirement),
+        None,
+    );
+
+    Ok(())
+}
+
+/// Turns privacy mode on or off. While it is on, cameras and microphones are kept disabled
+/// outside `allowed_windows`; the change is applied right away.
+#[tauri::command]
+pub fn set_privacy_mode(enabled: bool, allowed_windows: Option<Vec<TimeWindow>>) -> Result<(), String> {
+    let allowed_windows = allowed_windows.unwrap_or_default();
+    crate::commands::whitelist::validate_schedule(None, &allowed_windows)?;
+
+    let mut settings = read_settings()?;
+    settings.privacy_mode = PrivacyMode { enabled, allowed_windows };
+    storage::write_json(SETTINGS_FILE, &settings)
+        .map_err(|e| e.to_string())?;
+
+    let message = match (enabled, settings.privacy_mode.allowed_windows.len()) {
+        (false, _) => "Privacy mode disabled".to_string(),
+        (true, 0) => "Privacy mode enabled: cameras and microphones are always disabled".to_string(),
+        (true, n) => format!("Privacy mode enabled: cameras and microphones are disabled outside {} allowed windows", n),
+    };
+    let level = if enabled { "INFO" } else { "WARN" };
+    let _ = crate::commands::logs::add_event_log(level.to_string(), message, None);
+
+    crate::commands::capture::enforce_privacy_mode()?;
+    Ok(())
+}
@@ -1,6 +1,10 @@
-use crate::models::{WhitelistEntry, WhitelistData, UsbDevice};
-use crate::services::storage;
-use chrono::Utc;
+use crate::commands::approvals::current_user;
+use crate::models::{
+    WhitelistEntry, WhitelistData, UsbDevice, TimeWindow, TrustDecisionKind, UsbGuardImport, SkippedRule,
+    WhitelistMetadata, WhitelistQuery,
+};
+use crate::services::{device_id, storage, usbguard};
+use chrono::{DateTime, Datelike, Local, NaiveTime, Utc, Weekday};
 
 const WHITELIST_FILE: &str = "whitelist.json";
 
@@ -12,16 +16,153 @@ pub fn get_whitelist() -> Result<Vec<WhitelistEntry>, String> {
     Ok(data.entries)
 }
 
-/// Checks if a device is in the whitelist
-pub fn is_device_trusted(instance_id: &str) -> Result<bool, String> {
+/// Checks if a device matches any whitelist entry that currently applies
+pub fn is_device_trusted(device: &UsbDevice) -> Result<bool, String> {
     let data: WhitelistData = storage::read_json(WHITELIST_FILE)
         .map_err(|e| e.to_string())?;
-    Ok(data.entries.iter().any(|e| e.instance_id == instance_id))
+    let now = Local::now();
+    Ok(data.entries.iter().any(|e| entry_matches(e, device) && entry_is_active(e, now)))
 }
 
-/// Adds a device to the whitelist
+/// Whether an entry applies at `now`: not expired, and inside one of its windows if it has any
+pub fn entry_is_active(entry: &WhitelistEntry, now: DateTime<Local>) -> bool {
+    if is_expired(entry, now) {
+        return false;
+    }
+    entry.allowed_windows.is_empty()
+        || entry.allowed_windows.iter().any(|w| window_contains(w, now))
+}
+
+fn is_expired(entry: &WhitelistEntry, now: DateTime<Local>) -> bool {
+    entry.expires_at.as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .is_some_and(|expires_at| expires_at <= now)
+}
+
+/// Checks a local time against a window, including windows that run past midnight
+pub fn window_contains(window: &TimeWindow, now: DateTime<Local>) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&window.start), parse_time(&window.end)) else {
+        return false;
+    };
+    let time = now.time();
+    let today = now.weekday();
+    
+    if start <= end {
+        day_allowed(window, today) && time >= start && time < end
+    } else {
+        // e.g. 22:00-06:00: the early-morning part belongs to the previous day's window
+        (day_allowed(window, today) && time >= start)
+            || (day_allowed(window, today.pred()) && time < end)
+    }
+}
+
+fn day_allowed(window: &TimeWindow, day: Weekday) -> bool {
+    window.days.is_empty() || window.days.iter().any(|d| parse_days(d).is_some_and(|days| days.contains(&day)))
+}
+
+/// Parses "mon".."sun" (any chrono-accepted spelling), "weekdays" or "weekends"
+fn parse_days(value: &str) -> Option<Vec<Weekday>> {
+    match value.trim().to_lowercase().as_str() {
+        "weekdays" => Some(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]),
+        "weekends" => Some(vec![Weekday::Sat, Weekday::Sun]),
+        other => other.parse::<Weekday>().ok().map(|d| vec![d]),
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Rejects expiry times and windows that could never be evaluated
+pub fn validate_schedule(expires_at: Option<&str>, windows: &[TimeWindow]) -> Result<(), String> {
+    if let Some(expires_at) = expires_at {
+        DateTime::parse_from_rfc3339(expires_at)
+            .map_err(|e| format!("Invalid expiry '{}': {}", expires_at, e))?;
+    }
+    for window in windows {
+        if parse_time(&window.start).is_none() || parse_time(&window.end).is_none() {
+            return Err(format!("Invalid time window {}-{} (expected HH:MM)", window.start, window.end));
+        }
+        if let Some(day) = window.days.iter().find(|d| parse_days(d).is_none()) {
+            return Err(format!("Invalid day '{}' in time window", day));
+        }
+    }
+    Ok(())
+}
+
+/// Removes entries whose expiry has passed, logging a WARN for each.
+/// Returns the removed entries so callers can re-block devices they covered.
+pub fn expire_entries() -> Result<Vec<WhitelistEntry>, String> {
+    let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    
+    let now = Local::now();
+    let (expired, kept): (Vec<WhitelistEntry>, Vec<WhitelistEntry>) = data.entries
+        .into_iter()
+        .partition(|e| is_expired(e, now));
+    
+    if expired.is_empty() {
+        return Ok(expired);
+    }
+    
+    data.entries = kept;
+    storage::write_json(WHITELIST_FILE, &data)
+        .map_err(|e| e.to_string())?;
+    
+    for entry in &expired {
+        let _ = crate::commands::logs::add_event_log(
+            "WARN".to_string(),
+            format!("Whitelist entry expired: {}", entry.friendly_name),
+            Some(entry.instance_id.clone()),
+        );
+        let _ = crate::commands::inventory::record_decision(&entry.instance_id, TrustDecisionKind::Untrusted);
+    }
+    
+    Ok(expired)
+}
+
+/// Evaluates a single entry: VID[/PID[/serial]], name and interface rules, or the exact instance ID
+pub fn entry_matches(entry: &WhitelistEntry, device: &UsbDevice) -> bool {
+    if entry.vendor_id.is_none() && entry.name.is_none() && entry.with_interface.is_none() {
+        return entry.instance_id == device.instance_id;
+    }
+    
+    // Unset rule fields are wildcards; set ones need the device to report a matching value
+    let field_matches = |rule: Option<&str>, actual: Option<&str>| match (rule, actual) {
+        (None, _) => true,
+        (Some(rule), Some(actual)) => rule.eq_ignore_ascii_case(actual),
+        (Some(_), None) => false,
+    };
+    
+    field_matches(entry.vendor_id.as_deref(), device.vendor_id.as_deref())
+        && field_matches(entry.product_id.as_deref(), device.product_id.as_deref())
+        && field_matches(entry.serial.as_deref(), device.serial.as_deref())
+        && entry.name.as_ref().is_none_or(|name| *name == device.friendly_name)
+        && entry.with_interface.as_ref().is_none_or(|m| usbguard::interfaces_match(m, &device.interfaces))
+}
+
+/// Builds the key used to identify a rule entry, e.g. `VID_1050&PID_0407`
+fn rule_key(vendor_id: &str, product_id: Option<&str>, serial: Option<&str>) -> String {
+    let mut key = format!("VID_{}", vendor_id);
+    if let Some(pid) = product_id {
+        key.push_str(&format!("&PID_{}", pid));
+    }
+    if let Some(serial) = serial {
+        key.push_str(&format!("\\{}", serial));
+    }
+    key
+}
+
+/// Adds a device to the whitelist, optionally until `expires_at` or only inside `allowed_windows`
 #[tauri::command]
-pub fn add_to_whitelist(device: UsbDevice) -> Result<(), String> {
+pub fn add_to_whitelist(
+    device: UsbDevice,
+    expires_at: Option<String>,
+    allowed_windows: Option<Vec<TimeWindow>>,
+) -> Result<(), String> {
+    let allowed_windows = allowed_windows.unwrap_or_default();
+    validate_schedule(expires_at.as_deref(), &allowed_windows)?;
+    
     let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
         .map_err(|e| e.to_string())?;
     
@@ -30,10 +171,32 @@ pub fn add_to_whitelist(device: UsbDevice) -> Result<(), String> {
         return Ok(()); // Already whitelisted
     }
     
+    // Match on VID:PID:serial when the device has a serial so the entry survives port changes
+    let identity = device_id::parse_instance_id(&device.instance_id);
+    let vendor_id = device.vendor_id.clone().or(identity.vendor_id);
+    let product_id = device.product_id.clone().or(identity.product_id);
+    let serial = device.serial.clone().or(identity.serial);
+    let (vendor_id, product_id, serial) = match (vendor_id, product_id, serial) {
+        (Some(vid), Some(pid), Some(serial)) => (Some(vid), Some(pid), Some(serial)),
+        _ => (None, None, None),
+    };
+    
     let entry = WhitelistEntry {
         instance_id: device.instance_id.clone(),
         friendly_name: device.friendly_name.clone(),
         added_at: Utc::now().to_rfc3339(),
+        vendor_id,
+        product_id,
+        serial,
+        name: None,
+        with_interface: None,
+        expires_at,
+        allowed_windows,
+        owner: None,
+        asset_tag: None,
+        notes: None,
+        tags: Vec::new(),
+        added_by: Some(current_user()),
     };
     
     data.entries.push(entry);
@@ -44,12 +207,70 @@ pub fn add_to_whitelist(device: UsbDevice) -> Result<(), String> {
     let _ = crate::commands::logs::add_event_log(
         "INFO".to_string(),
         format!("Device trusted: {}", device.friendly_name),
-        Some(device.instance_id),
+        Some(device.instance_id.clone()),
     );
+    let _ = crate::commands::inventory::record_decision(&device.instance_id, TrustDecisionKind::Trusted);
     
     Ok(())
 }
 
+/// Adds a rule trusting every device matching `VID`, `VID:PID` or `VID:PID:SERIAL`
+#[tauri::command]
+pub fn add_whitelist_rule(
+    spec: String,
+    friendly_name: String,
+    expires_at: Option<String>,
+    allowed_windows: Option<Vec<TimeWindow>>,
+) -> Result<WhitelistEntry, String> {
+    let (vendor_id, product_id, serial) = device_id::parse_rule_spec(&spec)?;
+    let allowed_windows = allowed_windows.unwrap_or_default();
+    validate_schedule(expires_at.as_deref(), &allowed_windows)?;
+    let key = rule_key(&vendor_id, product_id.as_deref(), serial.as_deref());
+    
+    let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    
+    if let Some(existing) = data.entries.iter().find(|e| e.instance_id == key) {
+        return Ok(existing.clone()); // Already whitelisted
+    }
+    
+    let entry = WhitelistEntry {
+        instance_id: key.clone(),
+        friendly_name: if friendly_name.trim().is_empty() { key.clone() } else { friendly_name },
+        added_at: Utc::now().to_rfc3339(),
+        vendor_id: Some(vendor_id),
+        product_id,
+        serial,
+        name: None,
+        with_interface: None,
+        expires_at,
+        allowed_windows,
+        owner: None,
+        asset_tag: None,
+        notes: None,
+        tags: Vec::new(),
+        added_by: Some(current_user()),
+    };
+    
+    data.entries.push(entry.clone());
+    storage::write_json(WHITELIST_FILE, &data)
+        .map_err(|e| e.to_string())?;
+    
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Whitelist rule added: {} ({})", entry.friendly_name, key),
+        None,
+    );
+    
+    Ok(entry)
+}
+
+/// Replaces every whitelist entry without logging (used by verified bundle imports)
+pub fn replace_entries(entries: Vec<WhitelistEntry>) -> Result<(), String> {
+    storage::write_json(WHITELIST_FILE, &WhitelistData { entries })
+        .map_err(|e| e.to_string())
+}
+
 /// Removes a device from the whitelist
 #[tauri::command]
 pub fn remove_from_whitelist(instance_id: String) -> Result<(), String> {
@@ -67,8 +288,9 @@ pub fn remove_from_whitelist(instance_id: String) -> Result<(), String> {
         let _ = crate::commands::logs::add_event_log(
             "WARN".to_string(),
             format!("Device removed from whitelist: {}", instance_id),
-            Some(instance_id),
+            Some(instance_id.clone()),
         );
+        let _ = crate::commands::inventory::record_decision(&instance_id, TrustDecisionKind::Untrusted);
     }
     
     Ok(())
@@ -89,3 +311,581 @@ pub fn clear_whitelist() -> Result<(), String> {
     
     Ok(())
 }
+
+/// Trims a free-text field, treating blank values as unset
+fn clean(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Trims tags and drops blanks and case-insensitive duplicates
+fn clean_tags(tags: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !cleaned.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            cleaned.push(tag.to_string());
+        }
+    }
+    cleaned
+}
+
+/// Updates the owner, asset tag, notes, tags and optionally the name of an entry
+#[tauri::command]
+pub fn update_whitelist_entry(instance_id: String, metadata: WhitelistMetadata) -> Result<WhitelistEntry, String> {
+    let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    
+    let entry = data.entries
+        .iter_mut()
+        .find(|e| e.instance_id == instance_id)
+        .ok_or_else(|| format!("Whitelist entry not found: {}", instance_id))?;
+    
+    if let Some(name) = clean(metadata.friendly_name) {
+        entry.friendly_name = name;
+    }
+    entry.owner = clean(metadata.owner);
+    entry.asset_tag = clean(metadata.asset_tag);
+    entry.notes = clean(metadata.notes);
+    entry.tags = clean_tags(metadata.tags);
+    let updated = entry.clone();
+    
+    storage::write_json(WHITELIST_FILE, &data)
+        .map_err(|e| e.to_string())?;
+    
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!("Whitelist entry updated by {}: {}", current_user(), updated.friendly_name),
+        Some(instance_id),
+    );
+    
+    Ok(updated)
+}
+
+/// Whether an entry satisfies every set field of a query
+pub fn entry_matches_query(entry: &WhitelistEntry, query: &WhitelistQuery) -> bool {
+    let contains = |field: Option<&String>, needle: &str| {
+        field.is_some_and(|f| f.to_lowercase().contains(&needle.to_lowercase()))
+    };
+    let equals = |field: Option<&String>, expected: &Option<String>| {
+        expected.as_deref().is_none_or(|e| field.is_some_and(|f| f.eq_ignore_ascii_case(e.trim())))
+    };
+    
+    let text_matches = query.text.as_deref().map(str::trim).is_none_or(|text| {
+        [
+            Some(&entry.instance_id),
+            Some(&entry.friendly_name),
+            entry.vendor_id.as_ref(),
+            entry.product_id.as_ref(),
+            entry.serial.as_ref(),
+            entry.name.as_ref(),
+            entry.owner.as_ref(),
+            entry.asset_tag.as_ref(),
+            entry.notes.as_ref(),
+            entry.added_by.as_ref(),
+        ]
+        .into_iter()
+        .chain(entry.tags.iter().map(Some))
+        .any(|field| contains(field, text))
+    });
+    let tag_matches = query.tag.as_deref().is_none_or(|tag| {
+        entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    });
+    
+    text_matches
+        && tag_matches
+        && equals(entry.owner.as_ref(), &query.owner)
+        && equals(entry.asset_tag.as_ref(), &query.asset_tag)
+        && equals(entry.added_by.as_ref(), &query.added_by)
+}
+
+/// Gets the whitelist entries matching a query
+#[tauri::command]
+pub fn search_whitelist(query: WhitelistQuery) -> Result<Vec<WhitelistEntry>, String> {
+    Ok(get_whitelist()?
+        .into_iter()
+        .filter(|e| entry_matches_query(e, &query))
+        .collect())
+}
+
+/// Removes every entry carrying a tag (e.g. when its owner is offboarded); returns how many were removed
+#[tauri::command]
+pub fn remove_whitelist_by_tag(tag: String) -> Result<usize, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    
+    let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    let (removed, kept): (Vec<WhitelistEntry>, Vec<WhitelistEntry>) = data.entries
+        .into_iter()
+        .partition(|e| e.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+    
+    if removed.is_empty() {
+        return Ok(0);
+    }
+    
+    data.entries = kept;
+    storage::write_json(WHITELIST_FILE, &data)
+        .map_err(|e| e.to_string())?;
+    
+    for entry in &removed {
+        let _ = crate::commands::inventory::record_decision(&entry.instance_id, TrustDecisionKind::Untrusted);
+    }
+    let _ = crate::commands::logs::add_event_log(
+        "WARN".to_string(),
+        format!("{} whitelist entries tagged '{}' removed by {}", removed.len(), tag, current_user()),
+        None,
+    );
+    
+    Ok(removed.len())
+}
+
+/// Imports the `allow` rules of a USBGuard rules file as whitelist entries.
+/// `block`/`reject` rules are skipped: devices the whitelist doesn't allow are
+/// already handled by the device policy.
+#[tauri::command]
+pub fn import_usbguard_rules(rules: String) -> Result<UsbGuardImport, String> {
+    let mut data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    let mut result = UsbGuardImport::default();
+    
+    for (line, text, parsed) in usbguard::parse_rules(&rules) {
+        let rule = match parsed {
+            Ok(rule) if rule.target == usbguard::RuleTarget::Allow => rule,
+            Ok(_) => {
+                result.skipped.push(SkippedRule {
+                    line,
+                    rule: text,
+                    reason: "Only allow rules can be imported into the whitelist".to_string(),
+                });
+                continue;
+            }
+            Err(reason) => {
+                result.skipped.push(SkippedRule { line, rule: text, reason });
+                continue;
+            }
+        };
+        
+        // Plain id/serial rules share keys with rules added by hand; anything else is keyed by its text
+        let key = match (&rule.vendor_id, &rule.name, &rule.with_interface) {
+            (Some(vid), None, None) => rule_key(vid, rule.product_id.as_deref(), rule.serial.as_deref()),
+            _ => usbguard::format_rule(&rule),
+        };
+        if data.entries.iter().any(|e| e.instance_id == key) {
+            continue;
+        }
+        
+        data.entries.push(WhitelistEntry {
+            instance_id: key.clone(),
+            friendly_name: rule.name.clone().unwrap_or(key),
+            added_at: Utc::now().to_rfc3339(),
+            vendor_id: rule.vendor_id,
+            product_id: rule.product_id,
+            serial: rule.serial,
+            name: rule.name,
+            with_interface: rule.with_interface,
+            expires_at: None,
+            allowed_windows: Vec::new(),
+            owner: None,
+            asset_tag: None,
+            notes: None,
+            tags: Vec::new(),
+            added_by: Some(current_user()),
+        });
+        result.imported += 1;
+    }
+    
+    storage::write_json(WHITELIST_FILE, &data)
+        .map_err(|e| e.to_string())?;
+    
+    let _ = crate::commands::logs::add_event_log(
+        if result.skipped.is_empty() { "INFO" } else { "WARN" }.to_string(),
+        format!(
+            "Imported {} USBGuard rule(s), skipped {}",
+            result.imported,
+            result.skipped.len()
+        ),
+        None,
+    );
+    
+    Ok(result)
+}
+
+/// Exports the whitelist as USBGuard `allow` rules.
+/// Entries tied to a port-specific instance ID have no USBGuard equivalent and are
+/// written as comments; expiries and time windows are noted in comments only.
+#[tauri::command]
+pub fn export_usbguard_rules() -> Result<String, String> {
+    let data: WhitelistData = storage::read_json(WHITELIST_FILE)
+        .map_err(|e| e.to_string())?;
+    
+    let mut lines = vec!["# Exported from SentinelGuard".to_string()];
+    for entry in &data.entries {
+        let mut rule = usbguard::UsbGuardRule {
+            target: usbguard::RuleTarget::Allow,
+            vendor_id: entry.vendor_id.clone(),
+            product_id: entry.product_id.clone(),
+            serial: entry.serial.clone(),
+            name: entry.name.clone(),
+            with_interface: entry.with_interface.clone(),
+        };
+        
+        if entry.vendor_id.is_none() && entry.name.is_none() && entry.with_interface.is_none() {
+            let identity = device_id::parse_instance_id(&entry.instance_id);
+            match (identity.vendor_id, identity.product_id, identity.serial) {
+                (Some(vid), Some(pid), Some(serial)) => {
+                    rule.vendor_id = Some(vid);
+                    rule.product_id = Some(pid);
+                    rule.serial = Some(serial);
+                }
+                _ => {
+                    lines.push(format!(
+                        "# {}: matched by instance ID {}, no USBGuard equivalent",
+                        entry.friendly_name, entry.instance_id
+                    ));
+                    continue;
+                }
+            }
+        }
+        
+        let mut comment = format!("# {}", entry.friendly_name);
+        if let Some(expires_at) = &entry.expires_at {
+            comment.push_str(&format!(" (expires {})", expires_at));
+        }
+        if !entry.allowed_windows.is_empty() {
+            comment.push_str(" (time-windowed in SentinelGuard)");
+        }
+        lines.push(comment);
+        lines.push(usbguard::format_rule(&rule));
+    }
+    
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{InterfaceMatch, SetOperator};
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+    use chrono::TimeZone;
+
+    fn entry(instance_id: &str) -> WhitelistEntry {
+        WhitelistEntry {
+            instance_id: instance_id.to_string(),
+            friendly_name: instance_id.to_string(),
+            added_at: "2026-01-05T09:00:00Z".to_string(),
+            vendor_id: None,
+            product_id: None,
+            serial: None,
+            name: None,
+            with_interface: None,
+            expires_at: None,
+            allowed_windows: Vec::new(),
+            owner: None,
+            asset_tag: None,
+            notes: None,
+            tags: Vec::new(),
+            added_by: None,
+        }
+    }
+
+    /// A rule entry built the way `add_whitelist_rule` builds it
+    fn rule(spec: &str) -> WhitelistEntry {
+        let (vendor_id, product_id, serial) = device_id::parse_rule_spec(spec).unwrap();
+        WhitelistEntry {
+            vendor_id: Some(vendor_id),
+            product_id,
+            serial,
+            ..entry(spec)
+        }
+    }
+
+    /// A device with its IDs filled from the instance ID, as the device listing does
+    fn device(instance_id: &str) -> UsbDevice {
+        let identity = device_id::parse_instance_id(instance_id);
+        UsbDevice {
+            instance_id: instance_id.to_string(),
+            friendly_name: "SanDisk Cruzer Blade".to_string(),
+            vendor_id: identity.vendor_id,
+            product_id: identity.product_id,
+            serial: identity.serial,
+            ..Default::default()
+        }
+    }
+
+    const CRUZER: &str = "USB\\VID_0781&PID_5567\\4C530001230822117433";
+    const CRUZER_2: &str = "USB\\VID_0781&PID_5567\\4C530001190605100221";
+    const ULTRA: &str = "USB\\VID_0781&PID_5581\\4C531001600614116142";
+    const NO_SERIAL: &str = "USB\\VID_0781&PID_5567\\5&2A3B4C5D&0&2";
+
+    #[test]
+    fn vendor_rule_matches_every_product() {
+        let vendor = rule("0781");
+        assert!(entry_matches(&vendor, &device(CRUZER)));
+        assert!(entry_matches(&vendor, &device(ULTRA)));
+        assert!(entry_matches(&vendor, &device(NO_SERIAL)));
+        assert!(!entry_matches(&vendor, &device("USB\\VID_1050&PID_0407\\0001")));
+    }
+
+    #[test]
+    fn product_rule_matches_one_model() {
+        let model = rule("781:5567");
+        assert!(entry_matches(&model, &device(CRUZER)));
+        assert!(entry_matches(&model, &device(CRUZER_2)));
+        assert!(!entry_matches(&model, &device(ULTRA)));
+    }
+
+    #[test]
+    fn serial_rule_matches_one_stick() {
+        let stick = rule("0781:5567:4C530001230822117433");
+        assert!(entry_matches(&stick, &device(CRUZER)));
+        assert!(!entry_matches(&stick, &device(CRUZER_2)));
+        // A device without a serial never satisfies a serial rule
+        assert!(!entry_matches(&stick, &device(NO_SERIAL)));
+    }
+
+    #[test]
+    fn rules_match_case_insensitively() {
+        assert!(entry_matches(&rule("0781:5567:4c530001230822117433"), &device(CRUZER)));
+        assert!(entry_matches(&rule("abcd:ef01"), &device("usb\\vid_abcd&pid_ef01\\1-4")));
+    }
+
+    #[test]
+    fn generated_instance_is_not_matched_as_a_serial() {
+        let by_instance = rule("0781:5567:5&2A3B4C5D&0&2");
+        assert!(!entry_matches(&by_instance, &device(NO_SERIAL)));
+        assert!(!entry_matches(&rule("0781:5567:1-2"), &device("USB\\VID_0781&PID_5567\\1-2")));
+    }
+
+    #[test]
+    fn plain_entries_match_the_exact_instance_id() {
+        let exact = entry(NO_SERIAL);
+        assert!(entry_matches(&exact, &device(NO_SERIAL)));
+        assert!(!entry_matches(&exact, &device("USB\\VID_0781&PID_5567\\5&2A3B4C5D&0&3")));
+        assert!(!entry_matches(&exact, &device(&NO_SERIAL.to_lowercase())));
+    }
+
+    #[test]
+    fn name_and_interface_rules_need_every_field() {
+        let rule = WhitelistEntry {
+            name: Some("SanDisk Cruzer Blade".to_string()),
+            with_interface: Some(InterfaceMatch { operator: SetOperator::Equals, patterns: vec!["08:*:*".to_string()] }),
+            ..entry("allow name \"SanDisk Cruzer Blade\" with-interface 08:*:*")
+        };
+        let mut stick = device(CRUZER);
+        assert!(!entry_matches(&rule, &stick));
+        stick.interfaces = vec!["08:06:50".to_string()];
+        assert!(entry_matches(&rule, &stick));
+        stick.interfaces.push("03:01:01".to_string());
+        assert!(!entry_matches(&rule, &stick));
+    }
+
+    #[test]
+    fn added_rules_decide_trust() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        assert!(!is_device_trusted(&device(CRUZER)).unwrap());
+
+        add_whitelist_rule("0781:5567".to_string(), String::new(), None, None).unwrap();
+        assert!(is_device_trusted(&device(CRUZER)).unwrap());
+        assert!(is_device_trusted(&device(NO_SERIAL)).unwrap());
+        assert!(!is_device_trusted(&device(ULTRA)).unwrap());
+        assert_eq!(get_whitelist().unwrap()[0].instance_id, "VID_0781&PID_5567");
+
+        assert!(add_whitelist_rule("0781:55678".to_string(), String::new(), None, None).is_err());
+        assert_eq!(get_whitelist().unwrap().len(), 1);
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        // January 2026: the 5th is a Monday, the 9th a Friday
+        Local.with_ymd_and_hms(2026, 1, day, hour, minute, 0).unwrap()
+    }
+
+    fn window(days: &[&str], start: &str, end: &str) -> TimeWindow {
+        TimeWindow { days: days.iter().map(|d| d.to_string()).collect(), start: start.to_string(), end: end.to_string() }
+    }
+
+    #[test]
+    fn daytime_window_respects_weekday_boundaries() {
+        let office = window(&["weekdays"], "09:00", "17:00");
+        assert!(window_contains(&office, at(5, 9, 0)));
+        assert!(window_contains(&office, at(9, 16, 59)));
+        assert!(!window_contains(&office, at(9, 17, 0)));
+        assert!(!window_contains(&office, at(5, 8, 59)));
+        assert!(!window_contains(&office, at(10, 10, 0)));
+        assert!(!window_contains(&office, at(11, 10, 0)));
+
+        let some_days = window(&["Mon", "wednesday", "weekends"], "00:00", "23:59");
+        let open: Vec<u32> = (5..12).filter(|&d| window_contains(&some_days, at(d, 12, 0))).collect();
+        assert_eq!(open, vec![5, 7, 10, 11]);
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_the_day_it_starts() {
+        let friday_night = window(&["fri"], "22:00", "06:00");
+        assert!(window_contains(&friday_night, at(9, 22, 0)));
+        assert!(window_contains(&friday_night, at(9, 23, 59)));
+        assert!(window_contains(&friday_night, at(10, 0, 0)));
+        assert!(window_contains(&friday_night, at(10, 5, 59)));
+        assert!(!window_contains(&friday_night, at(10, 6, 0)));
+        // Friday morning is Thursday night's part, Saturday night isn't listed
+        assert!(!window_contains(&friday_night, at(9, 5, 0)));
+        assert!(!window_contains(&friday_night, at(10, 22, 30)));
+
+        let sunday_night = window(&["sun"], "20:00", "02:00");
+        assert!(window_contains(&sunday_night, at(12, 1, 0)));
+        assert!(!window_contains(&sunday_night, at(5, 20, 0)));
+
+        let every_night = window(&[], "22:00", "06:00");
+        assert!((5..12).all(|d| window_contains(&every_night, at(d, 3, 0)) && !window_contains(&every_night, at(d, 12, 0))));
+    }
+
+    #[test]
+    fn unparseable_windows_never_apply() {
+        assert!(!window_contains(&window(&[], "9:00am", "17:00"), at(5, 12, 0)));
+        assert!(!window_contains(&window(&["someday"], "00:00", "23:59"), at(5, 12, 0)));
+    }
+
+    #[test]
+    fn expiry_and_windows_decide_whether_an_entry_is_active() {
+        let mut stick = entry(CRUZER);
+        assert!(!is_expired(&stick, at(5, 12, 0)));
+
+        stick.expires_at = Some("2026-01-05T12:00:00+00:00".to_string());
+        let expiry = DateTime::parse_from_rfc3339("2026-01-05T12:00:00+00:00").unwrap().with_timezone(&Local);
+        assert!(!is_expired(&stick, expiry - chrono::Duration::seconds(1)));
+        assert!(is_expired(&stick, expiry));
+        assert!(!entry_is_active(&stick, expiry));
+
+        // An expiry that doesn't parse is rejected when saved; here it's ignored rather than revoking trust
+        stick.expires_at = Some("tomorrow".to_string());
+        assert!(!is_expired(&stick, at(5, 12, 0)));
+
+        stick.expires_at = None;
+        stick.allowed_windows = vec![window(&["weekdays"], "09:00", "17:00"), window(&["sat"], "10:00", "12:00")];
+        assert!(entry_is_active(&stick, at(10, 11, 0)));
+        assert!(!entry_is_active(&stick, at(10, 13, 0)));
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        assert_eq!(validate_schedule(None, &[]), Ok(()));
+        assert_eq!(validate_schedule(Some("2026-02-01T00:00:00Z"), &[window(&["Weekdays", "sun"], "22:00", "06:00")]), Ok(()));
+        assert!(validate_schedule(Some("2026-02-01"), &[]).unwrap_err().starts_with("Invalid expiry '2026-02-01'"));
+        assert_eq!(validate_schedule(None, &[window(&[], "25:00", "06:00")]), Err("Invalid time window 25:00-06:00 (expected HH:MM)".to_string()));
+        assert_eq!(validate_schedule(None, &[window(&[], "09:00", "")]), Err("Invalid time window 09:00- (expected HH:MM)".to_string()));
+        assert_eq!(validate_schedule(None, &[window(&["mon", "funday"], "09:00", "17:00")]), Err("Invalid day 'funday' in time window".to_string()));
+
+        let _env = TestEnv::new(FixtureBackend::new());
+        assert!(add_to_whitelist(device(CRUZER), None, Some(vec![window(&[], "9", "17")])).is_err());
+        assert!(get_whitelist().unwrap().is_empty());
+    }
+
+    #[test]
+    fn expired_entries_are_removed_and_returned() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        let past = (Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        let future = (Utc::now() + chrono::Duration::days(1)).to_rfc3339();
+        add_to_whitelist(device(CRUZER), Some(past), None).unwrap();
+        add_to_whitelist(device(ULTRA), Some(future), None).unwrap();
+        add_to_whitelist(device(NO_SERIAL), None, None).unwrap();
+
+        let expired: Vec<String> = expire_entries().unwrap().into_iter().map(|e| e.instance_id).collect();
+        assert_eq!(expired, vec![CRUZER]);
+        let kept: Vec<String> = get_whitelist().unwrap().into_iter().map(|e| e.instance_id).collect();
+        assert_eq!(kept, vec![ULTRA, NO_SERIAL]);
+        assert!(expire_entries().unwrap().is_empty());
+        assert!(!is_device_trusted(&device(CRUZER)).unwrap());
+        assert!(is_device_trusted(&device(ULTRA)).unwrap());
+    }
+
+    fn metadata(owner: Option<&str>, tags: &[&str]) -> WhitelistMetadata {
+        WhitelistMetadata { owner: owner.map(str::to_string), tags: tags.iter().map(|t| t.to_string()).collect(), ..Default::default() }
+    }
+
+    fn search(query: WhitelistQuery) -> Vec<String> {
+        search_whitelist(query).unwrap().into_iter().map(|e| e.instance_id).collect()
+    }
+
+    #[test]
+    fn metadata_is_cleaned_and_saved() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        add_to_whitelist(device(CRUZER), None, None).unwrap();
+
+        let update = WhitelistMetadata {
+            friendly_name: Some("  Alice's Cruzer ".to_string()),
+            owner: Some(" alice ".to_string()),
+            asset_tag: Some("   ".to_string()),
+            notes: Some("Finance laptop".to_string()),
+            tags: vec![" finance".to_string(), "".to_string(), "Finance".to_string(), "loaner".to_string()],
+        };
+        let updated = update_whitelist_entry(CRUZER.to_string(), update).unwrap();
+        assert_eq!(updated.friendly_name, "Alice's Cruzer");
+        assert_eq!(updated.owner.as_deref(), Some("alice"));
+        assert_eq!(updated.asset_tag, None);
+        assert_eq!(updated.tags, vec!["finance", "loaner"]);
+        assert_eq!(get_whitelist().unwrap()[0].notes.as_deref(), Some("Finance laptop"));
+
+        // Unset fields are cleared, but an unset name keeps the current one
+        let cleared = update_whitelist_entry(CRUZER.to_string(), WhitelistMetadata::default()).unwrap();
+        assert_eq!(cleared.friendly_name, "Alice's Cruzer");
+        assert_eq!((cleared.owner, cleared.notes, cleared.tags.len()), (None, None, 0));
+        assert_eq!(update_whitelist_entry(ULTRA.to_string(), WhitelistMetadata::default()).unwrap_err(), format!("Whitelist entry not found: {}", ULTRA));
+    }
+
+    #[test]
+    fn search_matches_every_set_field() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        for id in [CRUZER, CRUZER_2, ULTRA] {
+            add_to_whitelist(device(id), None, None).unwrap();
+        }
+        update_whitelist_entry(CRUZER.to_string(), metadata(Some("alice"), &["Finance"])).unwrap();
+        update_whitelist_entry(CRUZER_2.to_string(), metadata(Some("bob"), &["finance", "loaner"])).unwrap();
+        update_whitelist_entry(ULTRA.to_string(), WhitelistMetadata { asset_tag: Some("IT-0042".to_string()), ..metadata(Some("alice"), &[]) }).unwrap();
+
+        assert_eq!(search(WhitelistQuery::default()), vec![CRUZER, CRUZER_2, ULTRA]);
+        assert_eq!(search(WhitelistQuery { tag: Some(" FINANCE ".to_string()), ..Default::default() }), vec![CRUZER, CRUZER_2]);
+        assert_eq!(search(WhitelistQuery { owner: Some("Alice".to_string()), ..Default::default() }), vec![CRUZER, ULTRA]);
+        assert_eq!(search(WhitelistQuery { owner: Some("alice".to_string()), tag: Some("finance".to_string()), ..Default::default() }), vec![CRUZER]);
+        assert_eq!(search(WhitelistQuery { text: Some("it-00".to_string()), ..Default::default() }), vec![ULTRA]);
+        assert_eq!(search(WhitelistQuery { text: Some("loan".to_string()), ..Default::default() }), vec![CRUZER_2]);
+        assert_eq!(search(WhitelistQuery { text: Some("5581".to_string()), ..Default::default() }), vec![ULTRA]);
+        assert_eq!(search(WhitelistQuery { added_by: Some(current_user()), ..Default::default() }).len(), 3);
+        assert!(search(WhitelistQuery { owner: Some("ali".to_string()), ..Default::default() }).is_empty());
+    }
+
+    #[test]
+    fn entries_are_removed_by_tag() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        for id in [CRUZER, CRUZER_2, ULTRA] {
+            add_to_whitelist(device(id), None, None).unwrap();
+        }
+        update_whitelist_entry(CRUZER.to_string(), metadata(None, &["Contractor"])).unwrap();
+        update_whitelist_entry(ULTRA.to_string(), metadata(None, &["contractor", "loaner"])).unwrap();
+
+        assert_eq!(remove_whitelist_by_tag(" CONTRACTOR ".to_string()), Ok(2));
+        let kept: Vec<String> = get_whitelist().unwrap().into_iter().map(|e| e.instance_id).collect();
+        assert_eq!(kept, vec![CRUZER_2]);
+        assert_eq!(remove_whitelist_by_tag("contractor".to_string()), Ok(0));
+        assert_eq!(remove_whitelist_by_tag("  ".to_string()), Err("Tag cannot be empty".to_string()));
+    }
+
+    #[test]
+    fn entries_without_metadata_still_load() {
+        let _env = TestEnv::new(FixtureBackend::new());
+        let legacy = r#"{"entries": [{"instance_id": "USB\\VID_0781&PID_5567\\4C530001230822117433", "friendly_name": "SanDisk Cruzer", "added_at": "2025-06-01T12:00:00Z"}]}"#;
+        std::fs::write(storage::get_app_data_dir().unwrap().join(WHITELIST_FILE), legacy).unwrap();
+
+        let entries = get_whitelist().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].owner.as_ref(), entries[0].added_by.as_ref(), entries[0].tags.len()), (None, None, 0));
+        assert!(is_device_trusted(&device(CRUZER)).unwrap());
+        assert_eq!(search(WhitelistQuery { text: Some("cruzer".to_string()), ..Default::default() }), vec![CRUZER]);
+
+        // Saving an entry without metadata doesn't write the new fields
+        update_whitelist_entry(CRUZER.to_string(), WhitelistMetadata::default()).unwrap();
+        let saved = std::fs::read_to_string(storage::get_app_data_dir().unwrap().join(WHITELIST_FILE)).unwrap();
+        assert!(!saved.contains("owner") && !saved.contains("tags"));
+    }
+}
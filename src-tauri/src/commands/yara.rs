@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use crate::models::UsbDevice;
+use crate::monitor::{YARA_MATCH_EVENT, YARA_PROGRESS_EVENT};
+use crate::services::storage;
+use crate::services::yara::{self, RuleSet, YaraScanReport};
+
+const RULES_DIR: &str = "yara_rules";
+
+fn rules_dir() -> Result<PathBuf, String> {
+    let dir = storage::get_app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(RULES_DIR);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// Compiles the rule files in the rules folder, logging files that don't compile
+pub fn load_rules() -> Result<RuleSet, String> {
+    let dir = rules_dir()?;
+    let rule_set = yara::compile_dir(&dir)
+        .map_err(|e| format!("Cannot read YARA rules from {}: {}", dir.display(), e))?;
+
+    for error in &rule_set.errors {
+        let _ = crate::commands::logs::add_event_log(
+            "WARN".to_string(),
+            format!("Skipped YARA rule file: {}", error),
+            None,
+        );
+    }
+    Ok(rule_set)
+}
+
+/// Scans a path with loaded rules, emitting progress and logging every match
+/// (tied to `device` when the path is on a removable drive)
+pub fn run_scan(
+    app: &AppHandle,
+    rule_set: &RuleSet,
+    path: &str,
+    device: Option<&UsbDevice>,
+) -> Result<YaraScanReport, String> {
+    let report = yara::scan(rule_set, Path::new(path), |progress| {
+        let _ = app.emit(YARA_PROGRESS_EVENT, progress);
+    })
+    .map_err(|e| format!("Cannot scan {}: {}", path, e))?;
+
+    let device_id = device.map(|d| d.instance_id.clone());
+    for found in &report.matches {
+        let _ = crate::commands::logs::add_event_log(
+            "WARN".to_string(),
+            format!("YARA rule {}:{} matched {}", found.namespace, found.rule, found.path),
+            device_id.clone(),
+        );
+        let _ = app.emit(YARA_MATCH_EVENT, serde_json::json!({ "device": device, "match": found }));
+    }
+
+    Ok(report)
+}
+
+/// Gets the folder YARA rules are loaded from, creating it if needed
+#[tauri::command]
+pub fn get_yara_rules_dir() -> Result<String, String> {
+    Ok(rules_dir()?.to_string_lossy().to_string())
+}
+
+/// Scans a file or folder with the rules in the rules folder, emitting progress events
+#[tauri::command(async)]
+pub fn scan_with_yara(app: AppHandle, path: String) -> Result<YaraScanReport, String> {
+    let rule_set = load_rules()?;
+    if rule_set.rule_count() == 0 {
+        return Err(format!("No YARA rules found in {}", rules_dir()?.display()));
+    }
+
+    let report = run_scan(&app, &rule_set, &path, None)?;
+    let _ = crate::commands::logs::add_event_log(
+        "INFO".to_string(),
+        format!(
+            "YARA scan of {}: {} files, {} matches",
+            report.root, report.files_scanned, report.matches.len()
+        ),
+        None,
+    );
+    Ok(report)
+}
@@ -1,24 +1,44 @@
 mod commands;
 mod models;
+mod monitor;
 mod services;
+#[cfg(test)]
+mod test_support;
 
-use commands::{devices, whitelist, logs, wifi, system, startup, network, cleanup, firewall, processes};
+use commands::{devices, whitelist, logs, wifi, system, startup, network, cleanup, firewall, processes, settings, policy, approvals, inventory, bundle, encryption, partitions, media, yara, bluetooth, capture, kernel_modules};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // Real-time USB arrival/removal events
+            monitor::start(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Device commands
             devices::get_connected_devices,
             devices::enable_device,
             devices::disable_device,
+            devices::set_default_deny,
             devices::get_dashboard_stats,
             // Whitelist commands
             whitelist::get_whitelist,
             whitelist::add_to_whitelist,
+            whitelist::add_whitelist_rule,
             whitelist::remove_from_whitelist,
             whitelist::clear_whitelist,
+            whitelist::update_whitelist_entry,
+            whitelist::search_whitelist,
+            whitelist::remove_whitelist_by_tag,
+            whitelist::import_usbguard_rules,
+            whitelist::export_usbguard_rules,
+            bundle::export_policy_bundle,
+            bundle::import_policy_bundle,
+            // Policy commands
+            policy::get_device_policy,
+            policy::set_device_policy,
             // Log commands
             logs::get_event_logs,
             logs::add_event_log,
@@ -50,6 +70,46 @@ pub fn run() {
             processes::get_critical_services,
             processes::restart_service,
             processes::start_service,
+            // Settings commands
+            settings::get_settings,
+            settings::set_enforcement_mode,
+            settings::set_keystroke_injection_blocking,
+            settings::set_approval_timeout,
+            settings::set_bundle_public_key,
+            settings::set_encryption_requirement,
+            settings::set_suspicious_media_blocking,
+            settings::set_privacy_mode,
+            // Approval queue commands
+            approvals::get_pending_devices,
+            approvals::approve_device,
+            approvals::reject_device,
+            // Inventory commands
+            inventory::get_device_inventory,
+            // Storage encryption commands
+            encryption::check_encryption,
+            // Partition inspection commands
+            partitions::inspect_partitions,
+            // Removable media scanning commands
+            media::scan_media,
+            // YARA commands
+            yara::get_yara_rules_dir,
+            yara::scan_with_yara,
+            // Bluetooth commands
+            bluetooth::get_bluetooth_devices,
+            bluetooth::unpair_bluetooth_device,
+            bluetooth::block_bluetooth_device,
+            bluetooth::unblock_bluetooth_device,
+            // Camera and microphone commands
+            capture::get_capture_devices,
+            capture::disable_capture_devices,
+            capture::enable_capture_devices,
+            // Kernel module commands
+            kernel_modules::get_kernel_modules,
+            kernel_modules::reset_module_baseline,
+            kernel_modules::get_module_denylist,
+            kernel_modules::add_module_denylist_entry,
+            kernel_modules::remove_module_denylist_entry,
+            kernel_modules::unload_kernel_module,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,21 +1,235 @@
 use serde::{Deserialize, Serialize};
 
-/// Represents a connected USB device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Represents a connected device (USB, or anything else externally attachable)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UsbDevice {
     pub instance_id: String,
     pub friendly_name: String,
     pub device_class: String,
     pub status: String,
     pub is_trusted: bool,
+    #[serde(default)]
+    pub vendor_id: Option<String>,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub serial: Option<String>,
+    /// USB interface class codes (e.g. 0x03 HID, 0x08 mass storage)
+    #[serde(default)]
+    pub interface_classes: Vec<u8>,
+    /// `class:subclass:protocol` of each interface in lowercase hex, e.g. `08:06:50`
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+    /// Outcome of the whitelist and class policies (unset until evaluated)
+    #[serde(default)]
+    pub policy_action: Option<PolicyAction>,
+    /// BadUSB heuristics that matched this device
+    #[serde(default)]
+    pub risk_flags: Vec<RiskFlag>,
+    /// Capture category: what the device can record (empty for everything else)
+    #[serde(default)]
+    pub capture: Vec<CaptureKind>,
+    /// Bus the device is attached through (`USB`, `Bluetooth`, `PCI`, ...)
+    #[serde(default)]
+    pub bus_type: Option<String>,
+    /// Instance ID of the parent device node
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Functions of a composite device (interfaces, HID collections, disks), grouped under it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<UsbDevice>,
 }
 
-/// Represents a device in the whitelist
+/// A paired or connected Bluetooth device. The embedded device carries the address as its
+/// serial, so whitelist entries and class policies apply to it as they do to USB devices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BluetoothDevice {
+    #[serde(flatten)]
+    pub device: UsbDevice,
+    /// `AA:BB:CC:DD:EE:FF`
+    pub address: String,
+    /// 24-bit Class of Device; Low Energy devices don't report one
+    pub class_of_device: Option<u32>,
+    pub paired: bool,
+    pub connected: bool,
+    pub blocked: bool,
+}
+
+/// BadUSB signature found on a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFlag {
+    /// Mass storage that also exposes a HID interface
+    StorageWithHid,
+    /// Network adapter that also exposes a HID interface
+    NetworkWithHid,
+    /// HID interface on a board from a vendor known for programmable boards
+    ProgrammableBoardKeyboard,
+}
+
+/// Video or audio capture a device is capable of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureKind {
+    Camera,
+    Microphone,
+}
+
+/// Represents a device in the whitelist.
+/// Rule entries (with a `vendor_id`, `name` or `with_interface`) match on those fields;
+/// other entries match the exact `instance_id`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhitelistEntry {
     pub instance_id: String,
     pub friendly_name: String,
     pub added_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    /// Exact device name, as in USBGuard's `name` attribute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Interface patterns, as in USBGuard's `with-interface` attribute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_interface: Option<InterfaceMatch>,
+    /// RFC 3339 time after which the entry no longer applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// When set, the entry only applies inside one of these windows
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_windows: Vec<TimeWindow>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// User logged in when the entry was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_by: Option<String>,
+}
+
+/// Editable descriptive fields of a whitelist entry; unset fields are cleared
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WhitelistMetadata {
+    /// New display name; unset keeps the current one
+    pub friendly_name: Option<String>,
+    pub owner: Option<String>,
+    pub asset_tag: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Whitelist filter; every set field must match
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WhitelistQuery {
+    /// Case-insensitive substring of any text field
+    pub text: Option<String>,
+    pub owner: Option<String>,
+    pub asset_tag: Option<String>,
+    pub tag: Option<String>,
+    pub added_by: Option<String>,
+}
+
+/// Result of importing a USBGuard rules file
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UsbGuardImport {
+    pub imported: usize,
+    pub skipped: Vec<SkippedRule>,
+}
+
+/// A rule line that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedRule {
+    pub line: usize,
+    pub rule: String,
+    pub reason: String,
+}
+
+/// How a set of interface patterns is compared with a device's interfaces (USBGuard semantics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SetOperator {
+    /// Every pattern matches some interface
+    AllOf,
+    /// At least one pattern matches some interface
+    OneOf,
+    /// No pattern matches any interface
+    NoneOf,
+    /// Every interface matches a pattern and every pattern matches an interface
+    #[default]
+    Equals,
+    /// Like `Equals`, pairwise in order
+    EqualsOrdered,
+}
+
+/// `with-interface` condition: `class:subclass:protocol` patterns where any part may be `*`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceMatch {
+    #[serde(default)]
+    pub operator: SetOperator,
+    pub patterns: Vec<String>,
+}
+
+/// A recurring local-time window, e.g. weekdays 09:00-18:00.
+/// `end` before `start` means the window runs past midnight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Days the window starts on ("mon" .. "sun"); empty means every day
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// "HH:MM"
+    pub start: String,
+    /// "HH:MM"
+    pub end: String,
+}
+
+/// What a device policy decides for a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    Allow,
+    Prompt,
+    Block,
+}
+
+/// Class-level rule, e.g. "block all mass storage".
+/// Matches when the device exposes `class_code` on any interface or reports `device_class`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_code: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_class: Option<String>,
+    pub action: PolicyAction,
+    #[serde(default)]
+    pub label: String,
+}
+
+/// Device policy stored in policies.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DevicePolicy {
+    pub class_rules: Vec<ClassRule>,
+    /// Applies to devices that are neither whitelisted nor matched by a class rule
+    pub default_action: PolicyAction,
+}
+
+impl Default for DevicePolicy {
+    fn default() -> Self {
+        // Untrusted devices count as blocked unless a rule says otherwise
+        Self {
+            class_rules: Vec::new(),
+            default_action: PolicyAction::Block,
+        }
+    }
 }
 
 /// Represents an event log entry
@@ -41,12 +255,128 @@ pub struct LogsData {
     pub events: Vec<EventLog>,
 }
 
+/// How newly connected devices that are not whitelisted are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EnforcementMode {
+    /// Log only
+    #[default]
+    Monitor,
+    /// Ask the user to approve or block the device
+    Prompt,
+    /// Disable the device immediately
+    Enforce,
+}
+
+/// What happens when a mass-storage device carries unencrypted volumes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EncryptionRequirement {
+    #[default]
+    Off,
+    /// Log a WARN entry and alert the frontend
+    Warn,
+    /// Disable the device
+    Block,
+}
+
+/// A disk behind a USB device, with the partitions the OS found on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDevice {
+    /// Raw disk path, e.g. `/dev/sdb` or `\\.\PhysicalDrive2`
+    pub path: String,
+    /// Empty when the disk isn't partitioned
+    #[serde(default)]
+    pub volumes: Vec<DiskVolume>,
+}
+
+/// A partition's number and its start on the disk in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskVolume {
+    pub partition: usize,
+    pub offset: u64,
+}
+
+/// Schedule that keeps cameras and microphones disabled outside the allowed hours
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PrivacyMode {
+    pub enabled: bool,
+    /// Hours in which capture devices may be used; with none they stay disabled all the time
+    #[serde(default)]
+    pub allowed_windows: Vec<TimeWindow>,
+}
+
+/// Application settings stored in settings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub enforcement_mode: EnforcementMode,
+    /// Disable new keyboards whose typing cadence looks like keystroke injection
+    pub block_keystroke_injection: bool,
+    /// Seconds a device waits in the approval queue before it is denied
+    pub approval_timeout_secs: u64,
+    /// Ed25519 public key (PEM or base64) that policy bundles must be signed with
+    pub bundle_public_key: Option<String>,
+    pub encryption_requirement: EncryptionRequirement,
+    /// Disable storage devices carrying high-severity payloads (autorun commands, disguised executables)
+    pub block_suspicious_media: bool,
+    pub privacy_mode: PrivacyMode,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enforcement_mode: EnforcementMode::default(),
+            block_keystroke_injection: false,
+            approval_timeout_secs: 300,
+            bundle_public_key: None,
+            encryption_requirement: EncryptionRequirement::default(),
+            block_suspicious_media: false,
+            privacy_mode: PrivacyMode::default(),
+        }
+    }
+}
+
+/// A capture device turned off by the capture controls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisabledCaptureDevice {
+    pub instance_id: String,
+    pub friendly_name: String,
+    pub capture: Vec<CaptureKind>,
+    pub disabled_at: String,
+    /// Disabled by privacy mode, so it comes back on when the allowed hours start
+    pub by_privacy_mode: bool,
+}
+
+/// Capture devices disabled by the capture controls, stored in capture.json.
+/// Disabled devices drop out of the Windows device list, so this is how they are found again.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CaptureData {
+    pub disabled: Vec<DisabledCaptureDevice>,
+}
+
+/// A device held disabled until an operator approves or rejects it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDevice {
+    pub device: UsbDevice,
+    pub requested_at: String,
+    /// RFC 3339 time at which the request is denied automatically
+    pub expires_at: String,
+}
+
+/// Approval queue stored in pending.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PendingData {
+    pub devices: Vec<PendingDevice>,
+}
+
 /// Dashboard statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashboardStats {
     pub total_devices: usize,
     pub trusted_devices: usize,
     pub blocked_devices: usize,
+    pub pending_devices: usize,
     pub total_scans: usize,
     pub blocked_threats: usize,
     pub is_secure: bool,
@@ -155,3 +485,202 @@ pub struct ServiceInfo {
     pub status: String,
     pub start_type: String,
 }
+
+/// Kind of trust decision recorded against an inventory record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustDecisionKind {
+    Trusted,
+    Untrusted,
+    Enabled,
+    Blocked,
+    Approved,
+    Rejected,
+    TimedOut,
+}
+
+/// A trust decision and the user logged in when it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustDecision {
+    pub timestamp: String,
+    pub decision: TrustDecisionKind,
+    pub user: String,
+}
+
+/// One connection of an inventoried device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionRecord {
+    pub connected_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disconnected_at: Option<String>,
+    pub user: String,
+}
+
+/// Everything known about a device that has ever been connected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryRecord {
+    pub instance_id: String,
+    pub friendly_name: String,
+    pub device_class: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub connection_count: u32,
+    /// Most recent connections, oldest first
+    #[serde(default)]
+    pub connections: Vec<ConnectionRecord>,
+    /// Most recent trust decisions, oldest first
+    #[serde(default)]
+    pub decisions: Vec<TrustDecision>,
+    /// Partition layout of each disk from the latest inspection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disk_layouts: Vec<DiskLayout>,
+}
+
+/// Device inventory stored in inventory.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InventoryData {
+    pub devices: Vec<InventoryRecord>,
+}
+
+/// Filter for inventory queries; every field is optional
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct InventoryQuery {
+    /// Case-insensitive match on name, instance ID, VID, PID or serial
+    pub search: Option<String>,
+    pub device_class: Option<String>,
+    /// RFC 3339; only devices connected at or after this time
+    pub seen_since: Option<String>,
+    /// RFC 3339; only devices connected at or before this time
+    pub seen_until: Option<String>,
+}
+
+/// Kind of partition table found at the start of a disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableKind {
+    Mbr,
+    Gpt,
+    /// No table: the filesystem (or encrypted volume) starts at byte 0
+    None,
+}
+
+/// A data partition; offsets and sizes are in bytes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Partition {
+    /// 1-based position (MBR logical partitions continue after the four primaries)
+    pub index: usize,
+    pub start: u64,
+    pub size: u64,
+    /// MBR type byte (`0x07`) or GPT type GUID
+    pub type_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    /// GPT partition label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// GPT attribute flags (0 for MBR)
+    #[serde(default)]
+    pub attributes: u64,
+    /// Plaintext filesystem found at the start of the partition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>,
+}
+
+/// Partition layout oddity that can be used to hide data or masquerade as another device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionAnomaly {
+    /// Hidden MBR type or GPT "hidden" attribute
+    HiddenPartition,
+    /// Partition runs past the end of the disk
+    BeyondDiskEnd,
+    OverlappingPartitions,
+    /// Filesystem claims a size that doesn't fit its partition, or leaves a large unused tail
+    FilesystemSizeMismatch,
+    /// ISO 9660 volume on a flash drive (U3-style virtual CD-ROM)
+    CdromEmulation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyFinding {
+    pub anomaly: PartitionAnomaly,
+    /// Partition number, `None` for the whole disk
+    pub partition: Option<usize>,
+    pub detail: String,
+}
+
+/// Partition table and anomalies of one disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskLayout {
+    pub path: String,
+    pub inspected_at: String,
+    /// Disk size in bytes when the OS reports it
+    pub size: Option<u64>,
+    pub table: TableKind,
+    pub partitions: Vec<Partition>,
+    pub anomalies: Vec<AnomalyFinding>,
+}
+
+/// A loaded kernel module (Linux) or running kernel driver (Windows)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KernelModule {
+    pub name: String,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Modules that depend on this one (Linux)
+    #[serde(default)]
+    pub used_by: Vec<String>,
+    /// `Live`/`Loading`/`Unloading` on Linux, `Running` on Windows
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Display name of the driver (Windows)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Driver file (Windows)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Loaded when the baseline was taken (unset until evaluated)
+    #[serde(default)]
+    pub in_baseline: bool,
+    /// What the denylist says to do with it, if it is listed
+    #[serde(default)]
+    pub denied: Option<ModuleAction>,
+}
+
+/// What happens to a loaded module on the denylist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleAction {
+    /// Log and alert only
+    #[default]
+    Flag,
+    /// Unload it (requires admin rights; modules in use can't be unloaded)
+    Unload,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDenylistEntry {
+    pub name: String,
+    pub action: ModuleAction,
+    pub added_at: String,
+}
+
+/// Module baseline and denylist stored in modules.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModuleData {
+    /// Names of the modules loaded when the baseline was taken
+    pub baseline: Vec<String>,
+    pub baseline_taken_at: Option<String>,
+    pub denylist: Vec<ModuleDenylistEntry>,
+    /// Normalized names of loaded modules the monitor has already reported, so a restart
+    /// doesn't report them again
+    #[serde(default)]
+    pub reported: Vec<String>,
+}
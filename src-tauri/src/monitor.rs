@@ -0,0 +1,837 @@
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use crate::commands::{approvals, bluetooth, capture, devices, kernel_modules, encryption, inventory, logs, media, partitions, policy, settings, whitelist, yara};
+use crate::models::WhitelistEntry;
+use crate::models::{BlockDevice, BluetoothDevice, EncryptionRequirement, EnforcementMode, KernelModule, ModuleAction, PolicyAction, UsbDevice};
+use crate::services::{backend, badusb};
+use crate::services::media_scan::FindingSeverity;
+
+/// Event emitted with the `UsbDevice` payload when a device appears
+pub const DEVICE_CONNECTED_EVENT: &str = "device-connected";
+/// Event emitted with the `UsbDevice` payload when a device goes away
+pub const DEVICE_REMOVED_EVENT: &str = "device-removed";
+/// Event emitted with the `PendingDevice` payload when an untrusted device is queued for a decision
+pub const APPROVAL_REQUIRED_EVENT: &str = "device-approval-required";
+/// Event emitted when a new device matches a BadUSB heuristic
+pub const RISK_DETECTED_EVENT: &str = "device-risk-detected";
+/// Event emitted with `{ device, volumes }` when a storage device has unencrypted volumes
+pub const UNENCRYPTED_STORAGE_EVENT: &str = "unencrypted-storage-detected";
+/// Event emitted with the device and disk layout when a drive's partitions look suspicious
+pub const PARTITION_ANOMALY_EVENT: &str = "partition-anomaly-detected";
+/// Event emitted with the device and scan report when a drive carries autorun or executable payloads
+pub const SUSPICIOUS_MEDIA_EVENT: &str = "suspicious-media-detected";
+/// Event emitted with the device (if any) and match when a YARA rule matches a file
+pub const YARA_MATCH_EVENT: &str = "yara-match";
+/// Event emitted with scan progress while YARA scans a path
+pub const YARA_PROGRESS_EVENT: &str = "yara-scan-progress";
+/// Event emitted with the `BluetoothDevice` payload when a new pairing appears
+pub const BLUETOOTH_PAIRED_EVENT: &str = "bluetooth-device-paired";
+/// Event emitted with `{ active }` when privacy mode starts or stops keeping cameras and microphones off
+pub const PRIVACY_MODE_EVENT: &str = "privacy-mode-changed";
+/// Event emitted with the `KernelModule` payload when a module outside the baseline is loaded
+pub const KERNEL_MODULE_LOADED_EVENT: &str = "kernel-module-loaded";
+/// Event emitted with `{ module, unloaded }` when a denylisted module is loaded
+pub const KERNEL_MODULE_DENIED_EVENT: &str = "kernel-module-denied";
+/// Event emitted with `{ device, report }` when a new keyboard types like an injector
+#[cfg(target_os = "linux")]
+pub const KEYSTROKE_INJECTION_EVENT: &str = "keystroke-injection-detected";
+
+// How often the device list is re-read when no OS notification source is available
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+// How often the Bluetooth pairings are re-read; pairing takes user interaction, so this can be slow
+const BLUETOOTH_POLL_INTERVAL: Duration = Duration::from_secs(10);
+// How often privacy mode checks whether the allowed hours started or ended
+const PRIVACY_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// How often the loaded kernel modules are re-read (driverquery takes a moment on Windows)
+const MODULE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+// Upper bound between re-reads even when notifications are available
+#[cfg(target_os = "linux")]
+const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+// Give the kernel time to finish probing interfaces before re-reading
+#[cfg(target_os = "linux")]
+const SETTLE_DELAY: Duration = Duration::from_millis(300);
+// How long a new keyboard's typing is watched after it is connected
+#[cfg(target_os = "linux")]
+const KEYSTROKE_WATCH_WINDOW: Duration = Duration::from_secs(15);
+
+/// Devices that appeared and disappeared between two snapshots
+#[derive(Debug, Clone, Default)]
+pub struct DeviceDiff {
+    pub connected: Vec<UsbDevice>,
+    pub removed: Vec<UsbDevice>,
+}
+
+impl DeviceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.connected.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Tracks the known device set and reports arrivals/removals, keyed by instance ID
+#[derive(Debug, Default)]
+pub struct DeviceTracker {
+    known: HashMap<String, UsbDevice>,
+}
+
+impl DeviceTracker {
+    /// Starts from an existing snapshot so already-attached devices are not reported
+    pub fn with_devices(devices: Vec<UsbDevice>) -> Self {
+        let mut tracker = Self::default();
+        tracker.update(devices);
+        tracker
+    }
+
+    /// Replaces the known set with a new snapshot and returns what changed
+    pub fn update(&mut self, devices: Vec<UsbDevice>) -> DeviceDiff {
+        let mut current: HashMap<String, UsbDevice> = devices
+            .into_iter()
+            .map(|d| (d.instance_id.clone(), d))
+            .collect();
+
+        let mut diff = DeviceDiff::default();
+        for (id, device) in &current {
+            if !self.known.contains_key(id) {
+                diff.connected.push(device.clone());
+            }
+        }
+        for (id, device) in self.known.drain() {
+            if !current.contains_key(&id) {
+                diff.removed.push(device);
+            }
+        }
+        diff.connected.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+        diff.removed.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+
+        std::mem::swap(&mut self.known, &mut current);
+        diff
+    }
+}
+
+/// Wakes the monitor when the device set may have changed
+enum ChangeSource {
+    #[cfg(target_os = "linux")]
+    Uevent(crate::services::uevent::UeventSocket),
+    Poll,
+}
+
+impl ChangeSource {
+    #[cfg(target_os = "linux")]
+    fn open() -> Self {
+        match crate::services::uevent::UeventSocket::open(RESYNC_INTERVAL) {
+            Ok(socket) => Self::Uevent(socket),
+            Err(e) => {
+                let _ = logs::add_event_log(
+                    "WARN".to_string(),
+                    format!("USB monitor: netlink unavailable ({}), polling instead", e),
+                    None,
+                );
+                Self::Poll
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn open() -> Self {
+        Self::Poll
+    }
+
+    fn wait(&mut self) {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Uevent(socket) => {
+                if let Err(e) = socket.wait_for_device_change() {
+                    let _ = logs::add_event_log(
+                        "WARN".to_string(),
+                        format!("USB monitor: netlink error ({}), polling instead", e),
+                        None,
+                    );
+                    *self = Self::Poll;
+                    return;
+                }
+                thread::sleep(SETTLE_DELAY);
+            }
+            Self::Poll => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Starts the background USB monitor thread
+pub fn start(app: AppHandle) {
+    let bluetooth_app = app.clone();
+    let privacy_app = app.clone();
+    let modules_app = app.clone();
+    thread::spawn(move || run(app));
+    thread::spawn(move || watch_bluetooth(bluetooth_app));
+    thread::spawn(move || watch_privacy_mode(privacy_app));
+    thread::spawn(move || watch_kernel_modules(modules_app));
+}
+
+fn run(app: AppHandle) {
+    #[cfg(target_os = "linux")]
+    check_thunderbolt_security();
+    let mut source = ChangeSource::open();
+    let initial = list_devices().unwrap_or_default();
+    let _ = inventory::record_present(&initial);
+    let mut tracker = DeviceTracker::with_devices(initial);
+    // Expired entries are held until a listing succeeds, since they're gone from the whitelist by then
+    let mut expired: Vec<WhitelistEntry> = Vec::new();
+
+    loop {
+        source.wait();
+        expired.extend(whitelist::expire_entries().unwrap_or_default());
+        let _ = approvals::expire_pending();
+
+        // Keep the previous snapshot on errors so a failed read doesn't look like removals
+        let Ok(current) = list_devices() else {
+            continue;
+        };
+        reblock_expired(&std::mem::take(&mut expired), &current);
+        let diff = tracker.update(current);
+        if !diff.is_empty() {
+            publish(&app, &diff);
+        }
+    }
+}
+
+/// Warns about Thunderbolt controllers that let devices do DMA without being authorized,
+/// since the whitelist can't hold those devices back
+#[cfg(target_os = "linux")]
+fn check_thunderbolt_security() {
+    let domains = crate::services::thunderbolt::SysfsThunderbolt::default().domains().unwrap_or_default();
+    for domain in domains.iter().filter(|d| d.allows_unauthorized_dma()) {
+        let _ = logs::add_event_log(
+            "WARN".to_string(),
+            format!(
+                "Thunderbolt {} has security level 'none'; devices are authorized by the kernel and can't be blocked{}",
+                domain.name,
+                if domain.iommu_dma_protection { "" } else { " (no IOMMU DMA protection)" }
+            ),
+            None,
+        );
+    }
+}
+
+/// Physical devices to track; functions of composite devices (HID collections, disks)
+/// come and go with their parent, so they're tracked as part of it
+fn list_devices() -> Result<Vec<UsbDevice>, String> {
+    devices::list_devices(backend::current().as_ref()).map(devices::group_devices)
+}
+
+/// Re-blocks connected devices that lost their trust because a whitelist entry expired,
+/// including functions of composite devices
+fn reblock_expired(expired: &[WhitelistEntry], current: &[UsbDevice]) {
+    if expired.is_empty() {
+        return;
+    }
+    for device in current {
+        if device.is_trusted || !expired.iter().any(|e| whitelist::entry_matches(e, device)) {
+            reblock_expired(expired, &device.children);
+            continue;
+        }
+        // Blocking the device takes its functions with it
+        if let Err(e) = devices::disable_device(device.instance_id.clone()) {
+            let _ = logs::add_event_log(
+                "ERROR".to_string(),
+                format!("Failed to re-block {} after whitelist expiry: {}", device.friendly_name, e),
+                Some(device.instance_id.clone()),
+            );
+        }
+    }
+}
+
+/// Logs each change and forwards it to the frontend
+fn publish(app: &AppHandle, diff: &DeviceDiff) {
+    for device in &diff.connected {
+        let _ = inventory::record_connection(device);
+        let _ = logs::add_event_log(
+            "INFO".to_string(),
+            format!("Device connected: {}", device.friendly_name),
+            Some(device.instance_id.clone()),
+        );
+        let _ = app.emit(DEVICE_CONNECTED_EVENT, device);
+        report_risks(app, device);
+        apply_enforcement(app, device);
+        // A camera plugged in outside the allowed hours goes off right away
+        if !crate::services::capture::kinds(device).is_empty() {
+            let _ = capture::enforce_privacy_mode();
+        }
+        inspect_storage(app, device);
+        #[cfg(target_os = "linux")]
+        watch_keystrokes(app, device);
+    }
+
+    for device in &diff.removed {
+        let _ = logs::add_event_log(
+            "INFO".to_string(),
+            format!("Device removed: {}", device.friendly_name),
+            Some(device.instance_id.clone()),
+        );
+        let _ = app.emit(DEVICE_REMOVED_EVENT, device);
+        let _ = approvals::withdraw(&device.instance_id);
+        let _ = inventory::record_disconnection(device);
+    }
+}
+
+/// Keeps privacy mode applied as its allowed hours start and end, logging and emitting each switch
+fn watch_privacy_mode(app: AppHandle) {
+    let mut was_active = None;
+    loop {
+        if let Ok(active) = capture::enforce_privacy_mode() {
+            if was_active.is_some_and(|was| was != active) {
+                let message = if active {
+                    "Privacy mode active: cameras and microphones disabled until the next allowed window"
+                } else {
+                    "Privacy mode inactive: cameras and microphones re-enabled"
+                };
+                let _ = logs::add_event_log("INFO".to_string(), message.to_string(), None);
+            }
+            if was_active != Some(active) {
+                let _ = app.emit(PRIVACY_MODE_EVENT, serde_json::json!({ "active": active }));
+            }
+            was_active = Some(active);
+        }
+        thread::sleep(PRIVACY_CHECK_INTERVAL);
+    }
+}
+
+/// Watches kernel modules. The first run takes the baseline; after that every module loaded
+/// outside it is reported, and denylisted modules are flagged or unloaded.
+fn watch_kernel_modules(app: AppHandle) {
+    loop {
+        match kernel_modules::read_module_data() {
+            Ok(data) if data.baseline_taken_at.is_none() => {
+                if let Ok(count) = kernel_modules::save_baseline() {
+                    let _ = logs::add_event_log(
+                        "INFO".to_string(),
+                        format!("Kernel module baseline taken: {} modules", count),
+                        None,
+                    );
+                }
+            }
+            // Each load is reported once, across restarts too
+            Ok(_) => {
+                if let Ok(modules) = kernel_modules::take_unreported(backend::current().as_ref()) {
+                    for module in &modules {
+                        report_module(&app, module);
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+        thread::sleep(MODULE_POLL_INTERVAL);
+    }
+}
+
+fn report_module(app: &AppHandle, module: &KernelModule) {
+    if !module.in_baseline {
+        let _ = logs::add_event_log(
+            "WARN".to_string(),
+            format!("New kernel module loaded: {}", module.name),
+            None,
+        );
+        let _ = app.emit(KERNEL_MODULE_LOADED_EVENT, module);
+    }
+
+    let Some(action) = module.denied else {
+        return;
+    };
+    let unloaded = match action {
+        ModuleAction::Flag => {
+            let _ = logs::add_event_log(
+                "WARN".to_string(),
+                format!("Denylisted kernel module loaded: {}", module.name),
+                None,
+            );
+            false
+        }
+        // unload_kernel_module logs the BLOCK entry itself
+        ModuleAction::Unload => match kernel_modules::unload_kernel_module(module.name.clone()) {
+            Ok(()) => true,
+            Err(e) => {
+                let _ = logs::add_event_log(
+                    "ERROR".to_string(),
+                    format!("Failed to unload denylisted kernel module {}: {}", module.name, e),
+                    None,
+                );
+                false
+            }
+        },
+    };
+    let _ = app.emit(
+        KERNEL_MODULE_DENIED_EVENT,
+        serde_json::json!({ "module": module, "unloaded": unloaded }),
+    );
+}
+
+/// Watches Bluetooth pairings. Devices paired before the monitor started are the baseline;
+/// each later pairing is logged, emitted and, in enforce mode, blocked when policy blocks it.
+fn watch_bluetooth(app: AppHandle) {
+    let list = || bluetooth::list_devices(backend::current().as_ref());
+    let mut known: HashSet<String> = HashSet::new();
+    // Wait for a first successful read so an adapter that is still starting doesn't look like new pairings
+    loop {
+        if let Ok(devices) = list() {
+            known.extend(devices.into_iter().filter(|d| d.paired).map(|d| d.address));
+            break;
+        }
+        thread::sleep(BLUETOOTH_POLL_INTERVAL);
+    }
+
+    loop {
+        thread::sleep(BLUETOOTH_POLL_INTERVAL);
+        let Ok(devices) = list() else {
+            continue;
+        };
+        let paired: Vec<BluetoothDevice> = devices.into_iter().filter(|d| d.paired).collect();
+        for device in paired.iter().filter(|d| !known.contains(&d.address)) {
+            publish_pairing(&app, device);
+        }
+        // Forget unpaired devices so pairing them again is reported again
+        known = paired.into_iter().map(|d| d.address).collect();
+    }
+}
+
+fn publish_pairing(app: &AppHandle, bt: &BluetoothDevice) {
+    let device = &bt.device;
+    let _ = inventory::record_connection(device);
+    let (level, trust) = if device.is_trusted { ("INFO", "trusted") } else { ("WARN", "untrusted") };
+    let _ = logs::add_event_log(
+        level.to_string(),
+        format!("New Bluetooth pairing: {} ({}, {}, {})", device.friendly_name, bt.address, device.device_class, trust),
+        Some(device.instance_id.clone()),
+    );
+    let _ = app.emit(BLUETOOTH_PAIRED_EVENT, bt);
+
+    let mode = settings::read_settings().unwrap_or_default().enforcement_mode;
+    if mode != EnforcementMode::Enforce || policy::outcome(device) != PolicyAction::Block {
+        return;
+    }
+    // block_bluetooth_device logs the BLOCK entry itself
+    if let Err(e) = bluetooth::block_bluetooth_device(bt.address.clone()) {
+        let _ = logs::add_event_log(
+            "ERROR".to_string(),
+            format!("Failed to block untrusted Bluetooth device {}: {}", device.friendly_name, e),
+            Some(device.instance_id.clone()),
+        );
+    }
+}
+
+/// Raises a WARN entry and a frontend alert for each BadUSB signature on a new device
+fn report_risks(app: &AppHandle, device: &UsbDevice) {
+    if device.risk_flags.is_empty() {
+        return;
+    }
+
+    for flag in &device.risk_flags {
+        let _ = logs::add_event_log(
+            "WARN".to_string(),
+            format!("Possible BadUSB device {}: {}", device.friendly_name, badusb::describe(*flag)),
+            Some(device.instance_id.clone()),
+        );
+    }
+    let _ = app.emit(RISK_DETECTED_EVENT, device);
+}
+
+/// Applies the configured enforcement mode to a newly connected device.
+/// Prompt mode queues anything policy doesn't allow for approval; enforce mode blocks
+/// what policy blocks and still queues devices the policy wants a decision on.
+/// Queued devices stay disabled until approved.
+fn apply_enforcement(app: &AppHandle, device: &UsbDevice) {
+    let action = policy::outcome(device);
+    if action == PolicyAction::Allow {
+        authorize_thunderbolt(device);
+        return;
+    }
+
+    let mode = settings::read_settings().unwrap_or_default().enforcement_mode;
+    match (mode, action) {
+        (EnforcementMode::Monitor, _) => {}
+        (EnforcementMode::Prompt, _) | (EnforcementMode::Enforce, PolicyAction::Prompt) => {
+            match approvals::enqueue(device) {
+                Ok(pending) => {
+                    let _ = app.emit(APPROVAL_REQUIRED_EVENT, &pending);
+                }
+                Err(e) => {
+                    let _ = logs::add_event_log(
+                        "ERROR".to_string(),
+                        format!("Failed to hold {} for approval: {}", device.friendly_name, e),
+                        Some(device.instance_id.clone()),
+                    );
+                }
+            }
+        }
+        (EnforcementMode::Enforce, _) => {
+            // disable_device logs the BLOCK entry itself
+            if let Err(e) = devices::disable_device(device.instance_id.clone()) {
+                let _ = logs::add_event_log(
+                    "ERROR".to_string(),
+                    format!("Failed to block untrusted device {}: {}", device.friendly_name, e),
+                    Some(device.instance_id.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// Thunderbolt devices on a domain with `user` or `secure` security arrive unauthorized
+/// and can't do DMA until user space lets them in; allowed ones are authorized here
+fn authorize_thunderbolt(device: &UsbDevice) {
+    if device.bus_type.as_deref() != Some("Thunderbolt") || device.status != "Disabled" {
+        return;
+    }
+    // enable_device logs the INFO entry itself
+    if let Err(e) = devices::enable_device(device.instance_id.clone()) {
+        let _ = logs::add_event_log(
+            "ERROR".to_string(),
+            format!("Failed to authorize Thunderbolt device {}: {}", device.friendly_name, e),
+            Some(device.instance_id.clone()),
+        );
+    }
+}
+
+/// Inspects the disks of a new mass-storage device: partition layouts are checked for
+/// anomalies and stored in the inventory, volumes are checked against the encryption
+/// requirement, and the mounted filesystems are scanned for payloads
+fn inspect_storage(app: &AppHandle, device: &UsbDevice) {
+    const CLASS_MASS_STORAGE: u8 = 0x08;
+    if !device.interface_classes.contains(&CLASS_MASS_STORAGE) {
+        return;
+    }
+
+    let app = app.clone();
+    let device = device.clone();
+    thread::spawn(move || {
+        // Disks show up once the storage driver has probed the device; blocked devices never get one
+        let mut disks = Vec::new();
+        for _ in 0..10 {
+            disks = devices::block_devices(&device.instance_id).unwrap_or_default();
+            if !disks.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+
+        check_partitions(&app, &device, &disks);
+        check_storage_encryption(&app, &device, &disks);
+        if !disks.is_empty() {
+            scan_media(&app, &device);
+        }
+    });
+}
+
+/// Logs and emits partition anomalies, and records each disk's layout on the device's inventory record
+fn check_partitions(app: &AppHandle, device: &UsbDevice, disks: &[BlockDevice]) {
+    let mut layouts = Vec::new();
+    for disk in disks {
+        match partitions::inspect_partitions(disk.path.clone()) {
+            Ok(layout) => layouts.push(layout),
+            Err(e) => {
+                let _ = logs::add_event_log(
+                    "ERROR".to_string(),
+                    format!("Partition inspection of {} failed: {}", device.friendly_name, e),
+                    Some(device.instance_id.clone()),
+                );
+            }
+        }
+    }
+
+    for layout in layouts.iter().filter(|l| !l.anomalies.is_empty()) {
+        for finding in &layout.anomalies {
+            let _ = logs::add_event_log(
+                "WARN".to_string(),
+                format!("Suspicious partition layout on {} ({}): {}", device.friendly_name, layout.path, finding.detail),
+                Some(device.instance_id.clone()),
+            );
+        }
+        let _ = app.emit(
+            PARTITION_ANOMALY_EVENT,
+            serde_json::json!({ "device": device, "layout": layout }),
+        );
+    }
+
+    if !layouts.is_empty() {
+        let _ = inventory::record_disk_layouts(&device.instance_id, layouts);
+    }
+}
+
+/// Scans the mounted volumes of a storage device for autorun and executable payloads,
+/// then with the YARA rules. High-severity findings disable the device when suspicious
+/// media blocking is on.
+fn scan_media(app: &AppHandle, device: &UsbDevice) {
+    // Desktop automounters take a few seconds after the disk appears
+    let mut roots = Vec::new();
+    for _ in 0..10 {
+        roots = media::mount_points(&device.instance_id).unwrap_or_default();
+        if !roots.is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    let block = settings::read_settings().unwrap_or_default().block_suspicious_media;
+    let mut high_severity = false;
+    for root in &roots {
+        let report = match media::scan_media(root.clone()) {
+            Ok(report) => report,
+            Err(e) => {
+                let _ = logs::add_event_log("ERROR".to_string(), e, Some(device.instance_id.clone()));
+                continue;
+            }
+        };
+        if report.findings.is_empty() {
+            continue;
+        }
+
+        for finding in &report.findings {
+            let level = if block && finding.severity == FindingSeverity::High { "BLOCK" } else { "WARN" };
+            let _ = logs::add_event_log(
+                level.to_string(),
+                format!("Suspicious file on {}: {} ({})", device.friendly_name, finding.path, finding.detail),
+                Some(device.instance_id.clone()),
+            );
+        }
+        high_severity |= report.has_high_severity();
+        let _ = app.emit(
+            SUSPICIOUS_MEDIA_EVENT,
+            serde_json::json!({ "device": device, "report": &report }),
+        );
+    }
+
+    scan_with_yara(app, device, &roots);
+
+    if block && high_severity {
+        if let Err(e) = devices::disable_device(device.instance_id.clone()) {
+            let _ = logs::add_event_log(
+                "ERROR".to_string(),
+                format!("Failed to block suspicious storage {}: {}", device.friendly_name, e),
+                Some(device.instance_id.clone()),
+            );
+        }
+    }
+}
+
+/// Runs the YARA rules from the rules folder over a storage device's mounted volumes
+fn scan_with_yara(app: &AppHandle, device: &UsbDevice, roots: &[String]) {
+    let rule_set = match yara::load_rules() {
+        Ok(rule_set) if rule_set.rule_count() > 0 => rule_set,
+        Ok(_) => return,
+        Err(e) => {
+            let _ = logs::add_event_log("ERROR".to_string(), e, Some(device.instance_id.clone()));
+            return;
+        }
+    };
+
+    for root in roots {
+        if let Err(e) = yara::run_scan(app, &rule_set, root, Some(device)) {
+            let _ = logs::add_event_log("ERROR".to_string(), e, Some(device.instance_id.clone()));
+        }
+    }
+}
+
+/// Checks the volumes of a new mass-storage device against the encryption requirement.
+/// In block mode a device whose disks can't be read is blocked too, since it can't be verified.
+fn check_storage_encryption(app: &AppHandle, device: &UsbDevice, disks: &[BlockDevice]) {
+    let requirement = settings::read_settings().unwrap_or_default().encryption_requirement;
+    if requirement == EncryptionRequirement::Off {
+        return;
+    }
+
+    let mut problems = Vec::new();
+    for disk in disks {
+        match encryption::check_disk(disk) {
+            Ok(report) if report.encrypted => {}
+            Ok(report) => problems.extend(encryption::unencrypted_volumes(&report)),
+            Err(e) => problems.push(e),
+        }
+    }
+    if problems.is_empty() {
+        return;
+    }
+
+    let level = if requirement == EncryptionRequirement::Block { "BLOCK" } else { "WARN" };
+    let _ = logs::add_event_log(
+        level.to_string(),
+        format!("Unencrypted storage on {}: {}", device.friendly_name, problems.join(", ")),
+        Some(device.instance_id.clone()),
+    );
+    let _ = app.emit(
+        UNENCRYPTED_STORAGE_EVENT,
+        serde_json::json!({ "device": device, "volumes": &problems }),
+    );
+
+    if requirement == EncryptionRequirement::Block {
+        if let Err(e) = devices::disable_device(device.instance_id.clone()) {
+            let _ = logs::add_event_log(
+                "ERROR".to_string(),
+                format!("Failed to block unencrypted storage {}: {}", device.friendly_name, e),
+                Some(device.instance_id.clone()),
+            );
+        }
+    }
+}
+
+/// Watches the typing cadence of a new untrusted keyboard and reports (and optionally
+/// blocks) it if it looks like a keystroke injector
+#[cfg(target_os = "linux")]
+fn watch_keystrokes(app: &AppHandle, device: &UsbDevice) {
+    use crate::services::{evdev::Evdev, keystroke};
+
+    const CLASS_HID: u8 = 0x03;
+    if device.is_trusted || !device.interface_classes.contains(&CLASS_HID) {
+        return;
+    }
+
+    let app = app.clone();
+    let device = device.clone();
+    thread::spawn(move || {
+        let evdev = Evdev::default();
+
+        // Input nodes can show up shortly after the USB device itself
+        let mut nodes = Vec::new();
+        for _ in 0..10 {
+            nodes = evdev.event_nodes_for(&device.instance_id).unwrap_or_default();
+            if !nodes.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+        if nodes.is_empty() {
+            return;
+        }
+
+        let events = match evdev.capture(&nodes, KEYSTROKE_WATCH_WINDOW, |events| {
+            keystroke::analyze(events).injection_suspected
+        }) {
+            Ok(events) => events,
+            Err(e) => {
+                let _ = logs::add_event_log(
+                    "WARN".to_string(),
+                    format!("Keystroke monitor: cannot read {:?}: {}", nodes, e),
+                    Some(device.instance_id.clone()),
+                );
+                return;
+            }
+        };
+
+        let report = keystroke::analyze(&events);
+        if !report.injection_suspected {
+            return;
+        }
+
+        let _ = logs::add_event_log(
+            "WARN".to_string(),
+            format!(
+                "Keystroke injection suspected on {}: {} keys, {:.1} ms apart (score {})",
+                device.friendly_name, report.keystrokes, report.mean_interval_ms, report.score
+            ),
+            Some(device.instance_id.clone()),
+        );
+        let _ = app.emit(
+            KEYSTROKE_INJECTION_EVENT,
+            serde_json::json!({ "device": &device, "report": &report }),
+        );
+
+        if settings::read_settings().unwrap_or_default().block_keystroke_injection {
+            if let Err(e) = devices::disable_device(device.instance_id.clone()) {
+                let _ = logs::add_event_log(
+                    "ERROR".to_string(),
+                    format!("Failed to block keystroke injector {}: {}", device.friendly_name, e),
+                    Some(device.instance_id.clone()),
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fixtures::FixtureBackend;
+    use crate::test_support::TestEnv;
+
+    fn device(instance_id: &str) -> UsbDevice {
+        UsbDevice {
+            instance_id: instance_id.to_string(),
+            friendly_name: instance_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(devices: &[UsbDevice]) -> Vec<&str> {
+        devices.iter().map(|d| d.instance_id.as_str()).collect()
+    }
+
+    #[test]
+    fn initial_snapshot_is_not_reported() {
+        let mut tracker = DeviceTracker::with_devices(vec![device("USB\\A"), device("USB\\B")]);
+        assert!(tracker.update(vec![device("USB\\B"), device("USB\\A")]).is_empty());
+    }
+
+    #[test]
+    fn new_devices_are_reported_as_connected() {
+        let mut tracker = DeviceTracker::with_devices(vec![device("USB\\A")]);
+        let diff = tracker.update(vec![device("USB\\A"), device("USB\\C"), device("USB\\B")]);
+        assert_eq!(ids(&diff.connected), vec!["USB\\B", "USB\\C"]);
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn missing_devices_are_reported_as_removed() {
+        let mut tracker = DeviceTracker::with_devices(vec![device("USB\\A"), device("USB\\B")]);
+        let diff = tracker.update(vec![device("USB\\B")]);
+        assert!(diff.connected.is_empty());
+        assert_eq!(ids(&diff.removed), vec!["USB\\A"]);
+
+        // Once reported, a removal isn't repeated and a re-plug is a new connection
+        assert!(tracker.update(vec![device("USB\\B")]).is_empty());
+        assert_eq!(ids(&tracker.update(vec![device("USB\\A"), device("USB\\B")]).connected), vec!["USB\\A"]);
+    }
+
+    #[test]
+    fn removed_devices_keep_their_last_known_details() {
+        let mut stick = device("USB\\A");
+        stick.friendly_name = "Cruzer Blade".to_string();
+        let mut tracker = DeviceTracker::with_devices(vec![stick]);
+        let diff = tracker.update(vec![]);
+        assert_eq!(diff.removed[0].friendly_name, "Cruzer Blade");
+    }
+
+    fn expired_rule(instance_id: &str) -> WhitelistEntry {
+        serde_json::from_value(serde_json::json!({
+            "instance_id": instance_id,
+            "friendly_name": "Expired",
+            "added_at": "2026-01-05T09:00:00Z",
+            "expires_at": "2026-01-05T10:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn expired_devices_are_reblocked_unless_still_trusted() {
+        let env = TestEnv::new(FixtureBackend::new());
+        let mut trusted_elsewhere = device("USB\\B");
+        trusted_elsewhere.is_trusted = true;
+
+        reblock_expired(&[expired_rule("USB\\A"), expired_rule("USB\\B")], &[device("USB\\A"), trusted_elsewhere, device("USB\\C")]);
+        assert_eq!(env.backend.actions(), vec!["disable_device:USB\\A"]);
+    }
+
+    #[test]
+    fn expired_functions_of_composite_devices_are_reblocked() {
+        let env = TestEnv::new(FixtureBackend::new());
+        let mut hub = device("USB\\HUB");
+        let mut dock = device("USB\\DOCK");
+        dock.children = vec![device("USB\\DOCK&MI_00"), device("USB\\DOCK&MI_01")];
+        hub.children = vec![dock];
+
+        reblock_expired(&[expired_rule("USB\\DOCK&MI_01")], &[hub.clone()]);
+        assert_eq!(env.backend.actions(), vec!["disable_device:USB\\DOCK&MI_01"]);
+
+        // An expired parent is blocked as a whole
+        reblock_expired(&[expired_rule("USB\\DOCK"), expired_rule("USB\\DOCK&MI_00")], &[hub]);
+        assert_eq!(env.backend.actions(), vec!["disable_device:USB\\DOCK&MI_01", "disable_device:USB\\DOCK"]);
+    }
+
+}
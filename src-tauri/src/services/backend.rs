@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use crate::services::fixtures::FixtureBackend;
+use crate::services::powershell::PowerShellError;
+#[cfg(target_os = "linux")]
+use crate::services::linux::LinuxBackend;
+#[cfg(not(target_os = "linux"))]
+use crate::services::powershell::PowerShellBackend;
+
+/// Environment variable pointing at a directory of recorded outputs.
+/// When set, the fixture backend replaces PowerShell.
+pub const FIXTURES_ENV: &str = "SENTINELGUARD_FIXTURES";
+
+#[derive(Error, Debug)]
+pub enum BackendError {
+    #[error(transparent)]
+    PowerShell(#[from] PowerShellError),
+    #[error("Fixture error: {0}")]
+    Fixture(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(target_os = "linux")]
+    #[error("D-Bus error: {0}")]
+    DBus(#[from] zbus::Error),
+}
+
+/// Operating system access used by the command modules.
+/// Query methods return the raw JSON produced by the backend; the commands parse it.
+pub trait SystemBackend: Send + Sync {
+    // Devices
+    fn usb_devices_json(&self) -> Result<String, BackendError>;
+    fn disable_device(&self, instance_id: &str) -> Result<(), BackendError>;
+    fn enable_device(&self, instance_id: &str) -> Result<(), BackendError>;
+    /// Makes newly attached devices start blocked until explicitly enabled
+    fn set_default_deny(&self, enabled: bool) -> Result<(), BackendError>;
+    /// JSON array of the disks (`/dev/sdb`, `\\.\PhysicalDrive2`) behind a USB device and their partition offsets
+    fn block_devices_json(&self, instance_id: &str) -> Result<String, BackendError>;
+    /// JSON array of the directories (`/media/user/STICK`, `E:\`) where a USB device's volumes are mounted
+    fn mount_points_json(&self, instance_id: &str) -> Result<String, BackendError>;
+
+    // Bluetooth
+    /// JSON array of paired and connected Bluetooth devices (`BluetoothRecord`)
+    fn bluetooth_devices_json(&self) -> Result<String, BackendError>;
+    /// Removes the pairing with the device at `address` (`AA:BB:CC:DD:EE:FF`)
+    fn unpair_bluetooth_device(&self, address: &str) -> Result<(), BackendError>;
+    /// Blocks (or unblocks) connections from the device at `address`
+    fn set_bluetooth_blocked(&self, address: &str, blocked: bool) -> Result<(), BackendError>;
+
+    // Kernel modules
+    /// JSON array of loaded kernel modules or running kernel drivers (`KernelModule`)
+    fn kernel_modules_json(&self) -> Result<String, BackendError>;
+    /// Unloads a kernel module (Linux) or stops a kernel driver (Windows)
+    fn unload_kernel_module(&self, name: &str) -> Result<(), BackendError>;
+
+    // System
+    fn system_info_json(&self) -> Result<String, BackendError>;
+    fn startup_programs_json(&self) -> Result<String, BackendError>;
+
+    // Network & WiFi
+    fn network_info_json(&self) -> Result<String, BackendError>;
+    fn connected_wifi_json(&self) -> Result<String, BackendError>;
+    fn wifi_profiles_json(&self) -> Result<String, BackendError>;
+    fn wifi_password_json(&self, ssid: &str) -> Result<String, BackendError>;
+
+    // Cleanup
+    fn temp_info_json(&self) -> Result<String, BackendError>;
+    fn clean_temp_files_json(&self) -> Result<String, BackendError>;
+
+    // Firewall
+    fn firewall_status_json(&self) -> Result<String, BackendError>;
+    fn firewall_rules_json(&self) -> Result<String, BackendError>;
+    fn block_port(&self, port: u16, protocol: &str, rule_name: &str) -> Result<(), BackendError>;
+    fn remove_firewall_rule(&self, rule_name: &str) -> Result<(), BackendError>;
+    fn enable_firewall_logging(&self) -> Result<(), BackendError>;
+
+    // Processes & services
+    fn high_memory_processes_json(&self) -> Result<String, BackendError>;
+    fn kill_process(&self, process_id: u32) -> Result<(), BackendError>;
+    fn critical_services_json(&self) -> Result<String, BackendError>;
+    fn restart_service(&self, service_name: &str) -> Result<(), BackendError>;
+    fn start_service(&self, service_name: &str) -> Result<(), BackendError>;
+}
+
+static BACKEND: OnceLock<RwLock<Arc<dyn SystemBackend>>> = OnceLock::new();
+
+fn slot() -> &'static RwLock<Arc<dyn SystemBackend>> {
+    BACKEND.get_or_init(|| RwLock::new(default_backend()))
+}
+
+/// Picks the fixture backend when SENTINELGUARD_FIXTURES is set, the native one otherwise
+fn default_backend() -> Arc<dyn SystemBackend> {
+    if let Some(dir) = std::env::var_os(FIXTURES_ENV) {
+        match FixtureBackend::from_dir(PathBuf::from(dir)) {
+            Ok(fixtures) => return Arc::new(fixtures),
+            Err(e) => {
+                let _ = crate::commands::logs::add_event_log(
+                    "WARN".to_string(),
+                    format!("Ignoring {}, using the system backend: {}", FIXTURES_ENV, e),
+                    None,
+                );
+            }
+        }
+    }
+    native_backend()
+}
+
+#[cfg(target_os = "linux")]
+fn native_backend() -> Arc<dyn SystemBackend> {
+    Arc::new(LinuxBackend::default())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn native_backend() -> Arc<dyn SystemBackend> {
+    Arc::new(PowerShellBackend)
+}
+
+/// Gets the backend currently used by the commands
+pub fn current() -> Arc<dyn SystemBackend> {
+    slot().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Replaces the backend used by the commands (e.g. with a fixture backend)
+#[cfg(test)]
+pub fn install(backend: Arc<dyn SystemBackend>) {
+    *slot().write().unwrap_or_else(|e| e.into_inner()) = backend;
+}
+
+/// Parses backend JSON that may be empty, "null", a single object or an array
+pub fn parse_list<T: DeserializeOwned>(json_output: &str) -> Result<Vec<T>, String> {
+    if json_output.is_empty() || json_output == "null" {
+        return Ok(vec![]);
+    }
+
+    // ConvertTo-Json emits a bare object when there is only one result
+    if json_output.starts_with('[') {
+        serde_json::from_str(json_output).map_err(|e| format!("JSON parse error: {}", e))
+    } else {
+        let single: T =
+            serde_json::from_str(json_output).map_err(|e| format!("JSON parse error: {}", e))?;
+        Ok(vec![single])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        name: String,
+    }
+
+    #[test]
+    fn parse_list_accepts_an_array() {
+        let items: Vec<Item> = parse_list(r#"[{"name":"a"},{"name":"b"}]"#).unwrap();
+        assert_eq!(items, vec![Item { name: "a".into() }, Item { name: "b".into() }]);
+    }
+
+    #[test]
+    fn parse_list_wraps_a_single_object() {
+        let items: Vec<Item> = parse_list(r#"{"name":"only"}"#).unwrap();
+        assert_eq!(items, vec![Item { name: "only".into() }]);
+    }
+
+    #[test]
+    fn parse_list_treats_empty_and_null_as_no_items() {
+        assert!(parse_list::<Item>("").unwrap().is_empty());
+        assert!(parse_list::<Item>("null").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_list_reports_malformed_json() {
+        assert!(parse_list::<Item>("[{").unwrap_err().starts_with("JSON parse error"));
+    }
+}
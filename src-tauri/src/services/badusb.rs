@@ -0,0 +1,114 @@
+use crate::models::{RiskFlag, UsbDevice};
+
+const CLASS_COMMUNICATIONS: u8 = 0x02;
+const CLASS_HID: u8 = 0x03;
+const CLASS_MASS_STORAGE: u8 = 0x08;
+const CLASS_CDC_DATA: u8 = 0x0a;
+const CLASS_WIRELESS: u8 = 0xe0;
+
+/// Vendor IDs of boards that are easy to reprogram into keystroke injectors
+const PROGRAMMABLE_BOARD_VENDORS: &[(&str, &str)] = &[
+    ("03EB", "Atmel"),
+    ("1209", "pid.codes (open hardware)"),
+    ("16C0", "Van Ooijen / PJRC Teensy"),
+    ("16D0", "MCS / Digispark"),
+    ("1B4F", "SparkFun"),
+    ("2341", "Arduino"),
+    ("2A03", "Arduino.org"),
+    ("239A", "Adafruit"),
+    ("2E8A", "Raspberry Pi (RP2040)"),
+    ("303A", "Espressif"),
+];
+
+/// Looks up a vendor ID in the programmable-board list
+pub fn programmable_board_vendor(vendor_id: &str) -> Option<&'static str> {
+    PROGRAMMABLE_BOARD_VENDORS
+        .iter()
+        .find(|(vid, _)| vid.eq_ignore_ascii_case(vendor_id))
+        .map(|(_, name)| *name)
+}
+
+/// Checks a device's interfaces for BadUSB signatures
+pub fn assess(device: &UsbDevice) -> Vec<RiskFlag> {
+    let has = |code: u8| device.interface_classes.contains(&code);
+    let class_is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(&device.device_class));
+
+    let hid = has(CLASS_HID) || class_is(&["HID", "HIDClass", "Keyboard", "Mouse"]);
+    let storage = has(CLASS_MASS_STORAGE) || class_is(&["MassStorage", "DiskDrive"]);
+    let network = has(CLASS_COMMUNICATIONS) || has(CLASS_CDC_DATA) || has(CLASS_WIRELESS) || class_is(&["Net"]);
+
+    let mut flags = Vec::new();
+    if hid && storage {
+        flags.push(RiskFlag::StorageWithHid);
+    }
+    if hid && network {
+        flags.push(RiskFlag::NetworkWithHid);
+    }
+    if hid && device.vendor_id.as_deref().and_then(programmable_board_vendor).is_some() {
+        flags.push(RiskFlag::ProgrammableBoardKeyboard);
+    }
+    flags
+}
+
+/// Human-readable description of a flag for event logs
+pub fn describe(flag: RiskFlag) -> &'static str {
+    match flag {
+        RiskFlag::StorageWithHid => "storage device also presents a keyboard/HID interface",
+        RiskFlag::NetworkWithHid => "network adapter also presents a keyboard/HID interface",
+        RiskFlag::ProgrammableBoardKeyboard => "keyboard/HID from a programmable-board vendor",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(device_class: &str, vendor_id: &str, interface_classes: &[u8]) -> UsbDevice {
+        UsbDevice {
+            instance_id: format!("USB\\VID_{}&PID_0001\\1-2", vendor_id),
+            device_class: device_class.to_string(),
+            vendor_id: Some(vendor_id.to_string()),
+            interface_classes: interface_classes.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn storage_that_also_types_is_flagged() {
+        // A "flash drive" enumerating mass storage plus a keyboard, as Rubber Ducky clones do
+        assert_eq!(assess(&device("DiskDrive", "0781", &[CLASS_MASS_STORAGE, CLASS_HID])), vec![RiskFlag::StorageWithHid]);
+        assert_eq!(assess(&device("MassStorage", "0781", &[CLASS_HID])), vec![RiskFlag::StorageWithHid]);
+        assert_eq!(assess(&device("HIDClass", "0781", &[CLASS_MASS_STORAGE])), vec![RiskFlag::StorageWithHid]);
+    }
+
+    #[test]
+    fn network_adapter_that_also_types_is_flagged() {
+        assert_eq!(assess(&device("Net", "0BDA", &[CLASS_COMMUNICATIONS, CLASS_CDC_DATA, CLASS_HID])), vec![RiskFlag::NetworkWithHid]);
+        assert_eq!(assess(&device("Keyboard", "0BDA", &[CLASS_WIRELESS])), vec![RiskFlag::NetworkWithHid]);
+    }
+
+    #[test]
+    fn keyboard_on_a_programmable_board_is_flagged() {
+        assert_eq!(assess(&device("Keyboard", "2341", &[CLASS_HID])), vec![RiskFlag::ProgrammableBoardKeyboard]);
+        assert_eq!(assess(&device("HID", "16c0", &[CLASS_HID])), vec![RiskFlag::ProgrammableBoardKeyboard]);
+        // A Digispark-style board that also shows up as storage hits both checks
+        assert_eq!(assess(&device("HIDClass", "16D0", &[CLASS_HID, CLASS_MASS_STORAGE])), vec![RiskFlag::StorageWithHid, RiskFlag::ProgrammableBoardKeyboard]);
+    }
+
+    #[test]
+    fn ordinary_devices_are_not_flagged() {
+        assert!(assess(&device("Keyboard", "046D", &[CLASS_HID])).is_empty());
+        assert!(assess(&device("Mouse", "046D", &[CLASS_HID, CLASS_HID])).is_empty());
+        assert!(assess(&device("DiskDrive", "0781", &[CLASS_MASS_STORAGE])).is_empty());
+        assert!(assess(&device("Net", "0BDA", &[CLASS_COMMUNICATIONS, CLASS_CDC_DATA])).is_empty());
+        // A programmable board that doesn't present a keyboard (a serial Arduino) is fine
+        assert!(assess(&device("Ports", "2341", &[CLASS_COMMUNICATIONS, CLASS_CDC_DATA])).is_empty());
+        assert!(assess(&UsbDevice::default()).is_empty());
+    }
+
+    #[test]
+    fn vendors_are_looked_up_case_insensitively() {
+        assert_eq!(programmable_board_vendor("2e8a"), Some("Raspberry Pi (RP2040)"));
+        assert_eq!(programmable_board_vendor("046D"), None);
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use crate::services::device_id;
+
+/// A paired or connected Bluetooth device as reported by the backend
+/// (BlueZ on Linux, the PnP `Bluetooth` class on Windows)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BluetoothRecord {
+    /// `AA:BB:CC:DD:EE:FF`
+    pub address: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// User-assigned name, falls back to the device name
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// 24-bit Class of Device from the inquiry response; LE devices don't have one
+    #[serde(default)]
+    pub class_of_device: Option<u32>,
+    #[serde(default)]
+    pub paired: bool,
+    #[serde(default)]
+    pub connected: bool,
+    /// Connections from the device are rejected
+    #[serde(default)]
+    pub blocked: bool,
+    /// Device ID profile record, e.g. `usb:v046DpB33Bd0012`
+    #[serde(default)]
+    pub modalias: Option<String>,
+    /// Adapter the device is paired with (`hci0`)
+    #[serde(default)]
+    pub adapter: Option<String>,
+}
+
+/// Normalizes an address to `AA:BB:CC:DD:EE:FF`, accepting `-` separators or none
+pub fn normalize_address(address: &str) -> Option<String> {
+    let digits: String = address.trim().chars().filter(|c| !matches!(c, ':' | '-')).collect();
+    if digits.len() != 12 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits = digits.to_uppercase();
+    let pairs: Vec<&str> = (0..12).step_by(2).map(|i| &digits[i..i + 2]).collect();
+    Some(pairs.join(":"))
+}
+
+/// Instance ID of a Bluetooth device, built from its address so it is the same on every platform
+pub fn instance_id(address: &str) -> String {
+    format!("BTHENUM\\DEV_{}", address.replace(':', ""))
+}
+
+/// Vendor and product IDs from a Device ID modalias (`usb:v046DpB33Bd0012`, `bluetooth:v004Cp200Ed0100`)
+pub fn parse_modalias(modalias: &str) -> (Option<String>, Option<String>) {
+    let Some((_, ids)) = modalias.split_once(':') else {
+        return (None, None);
+    };
+    let field = |tag: char| {
+        let start = ids.find(tag)? + 1;
+        ids.get(start..start + 4).and_then(device_id::normalize_hex_id)
+    };
+    (field('v'), field('p'))
+}
+
+/// Human-readable major device class from a Class of Device. Peripherals are split into
+/// keyboards and pointing devices, since a keyboard can type commands.
+pub fn device_class_name(class_of_device: u32) -> &'static str {
+    let minor = (class_of_device >> 2) & 0x3F;
+    match (class_of_device >> 8) & 0x1F {
+        0x01 => "Computer",
+        0x02 => "Phone",
+        0x03 => "Network Access Point",
+        0x04 => "Audio/Video",
+        0x05 => match minor >> 4 {
+            0b01 => "Keyboard",
+            0b10 => "Pointing Device",
+            0b11 => "Keyboard/Pointing Device",
+            _ => "Peripheral",
+        },
+        0x06 => "Imaging",
+        0x07 => "Wearable",
+        0x08 => "Toy",
+        0x09 => "Health",
+        _ => "Uncategorized",
+    }
+}
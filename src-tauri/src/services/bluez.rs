@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+use crate::services::bluetooth::{normalize_address, BluetoothRecord};
+
+const SERVICE: &str = "org.bluez";
+const DEVICE_INTERFACE: &str = "org.bluez.Device1";
+const ADAPTER_INTERFACE: &str = "org.bluez.Adapter1";
+
+type ManagedObjects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+
+/// BlueZ client over D-Bus. Talks to the system bus by default; pointing it at another
+/// bus lets a fake `org.bluez` service stand in for tests.
+pub struct Bluez {
+    connection: Connection,
+}
+
+/// A device object and the adapter it belongs to
+struct DeviceObject {
+    path: OwnedObjectPath,
+    adapter: Option<OwnedObjectPath>,
+    record: BluetoothRecord,
+}
+
+fn string_prop(props: &HashMap<String, OwnedValue>, name: &str) -> Option<String> {
+    props.get(name).and_then(|v| String::try_from(v.try_clone().ok()?).ok())
+}
+
+fn bool_prop(props: &HashMap<String, OwnedValue>, name: &str) -> bool {
+    props.get(name).and_then(|v| bool::try_from(v).ok()).unwrap_or(false)
+}
+
+impl Bluez {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+
+    /// Connects to BlueZ on the system bus
+    pub fn system() -> zbus::Result<Self> {
+        Ok(Self::new(Connection::system()?))
+    }
+
+    /// Connects to BlueZ on the bus at a D-Bus address (e.g. `unix:path=/tmp/test-bus`)
+    pub fn at_address(address: &str) -> zbus::Result<Self> {
+        Ok(Self::new(zbus::blocking::connection::Builder::address(address)?.build()?))
+    }
+
+    fn device_objects(&self) -> zbus::Result<Vec<DeviceObject>> {
+        let proxy = Proxy::new(&self.connection, SERVICE, "/", "org.freedesktop.DBus.ObjectManager")?;
+        let objects: ManagedObjects = proxy.call("GetManagedObjects", &())?;
+
+        let mut devices: Vec<DeviceObject> = objects
+            .into_iter()
+            .filter_map(|(path, interfaces)| {
+                let props = interfaces.get(DEVICE_INTERFACE)?;
+                let address = string_prop(props, "Address").and_then(|a| normalize_address(&a))?;
+                let adapter = props
+                    .get("Adapter")
+                    .and_then(|v| OwnedObjectPath::try_from(v.try_clone().ok()?).ok());
+                let record = BluetoothRecord {
+                    address,
+                    name: string_prop(props, "Name"),
+                    alias: string_prop(props, "Alias"),
+                    class_of_device: props.get("Class").and_then(|v| u32::try_from(v).ok()),
+                    paired: bool_prop(props, "Paired"),
+                    connected: bool_prop(props, "Connected"),
+                    blocked: bool_prop(props, "Blocked"),
+                    modalias: string_prop(props, "Modalias"),
+                    adapter: adapter.as_ref().and_then(|a| a.rsplit('/').next()).map(String::from),
+                };
+                Some(DeviceObject { path, adapter, record })
+            })
+            .collect();
+        devices.sort_by(|a, b| a.record.address.cmp(&b.record.address));
+        Ok(devices)
+    }
+
+    fn find(&self, address: &str) -> zbus::Result<DeviceObject> {
+        let address = normalize_address(address)
+            .ok_or_else(|| zbus::Error::Failure(format!("Invalid Bluetooth address: {}", address)))?;
+        self.device_objects()?
+            .into_iter()
+            .find(|d| d.record.address == address)
+            .ok_or_else(|| zbus::Error::Failure(format!("Bluetooth device not found: {}", address)))
+    }
+
+    /// Devices that are paired or currently connected; devices only seen during discovery are left out
+    pub fn devices(&self) -> zbus::Result<Vec<BluetoothRecord>> {
+        Ok(self
+            .device_objects()?
+            .into_iter()
+            .map(|d| d.record)
+            .filter(|r| r.paired || r.connected)
+            .collect())
+    }
+
+    /// Removes the pairing (and the stored keys) through the device's adapter
+    pub fn unpair(&self, address: &str) -> zbus::Result<()> {
+        let device = self.find(address)?;
+        let adapter = device
+            .adapter
+            .ok_or_else(|| zbus::Error::Failure(format!("No adapter for {}", device.record.address)))?;
+        let proxy = Proxy::new(&self.connection, SERVICE, adapter, ADAPTER_INTERFACE)?;
+        proxy.call_method("RemoveDevice", &(ObjectPath::from(&device.path),))?;
+        Ok(())
+    }
+
+    /// Sets the device's `Blocked` property; BlueZ disconnects a blocked device and rejects its connections
+    pub fn set_blocked(&self, address: &str, blocked: bool) -> zbus::Result<()> {
+        let device = self.find(address)?;
+        let proxy = Proxy::new(&self.connection, SERVICE, device.path, "org.freedesktop.DBus.Properties")?;
+        proxy.call_method("Set", &(DEVICE_INTERFACE, "Blocked", Value::from(blocked)))?;
+        Ok(())
+    }
+}
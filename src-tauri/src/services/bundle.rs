@@ -0,0 +1,182 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::models::{DevicePolicy, WhitelistEntry};
+
+/// Current bundle payload format
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum BundleError {
+    #[error("Bundle is not signed")]
+    Unsigned,
+    #[error("Bundle signature does not match the configured public key")]
+    InvalidSignature,
+    #[error("Malformed bundle: {0}")]
+    Malformed(String),
+    #[error("Unsupported bundle version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+}
+
+/// What a bundle distributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundlePayload {
+    pub version: u32,
+    pub created_at: String,
+    pub whitelist: Vec<WhitelistEntry>,
+    pub policy: DevicePolicy,
+}
+
+/// On-disk bundle: the payload JSON in base64 and an Ed25519 signature over those exact bytes
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SignedBundle {
+    pub payload: String,
+    pub signature: String,
+}
+
+fn decode_key_bytes(text: &str) -> Result<[u8; 32], BundleError> {
+    let bytes = STANDARD
+        .decode(text)
+        .map_err(|e| BundleError::InvalidKey(e.to_string()))?;
+    bytes
+        .try_into()
+        .map_err(|_| BundleError::InvalidKey("expected 32 bytes".to_string()))
+}
+
+/// Reads a private key from PKCS#8 PEM (`openssl genpkey -algorithm ed25519`) or a base64 seed
+pub fn parse_signing_key(text: &str) -> Result<SigningKey, BundleError> {
+    let text = text.trim();
+    if text.starts_with("-----BEGIN") {
+        return SigningKey::from_pkcs8_pem(text).map_err(|e| BundleError::InvalidKey(e.to_string()));
+    }
+    Ok(SigningKey::from_bytes(&decode_key_bytes(text)?))
+}
+
+/// Reads a public key from SPKI PEM (`openssl pkey -pubout`) or base64 bytes
+pub fn parse_verifying_key(text: &str) -> Result<VerifyingKey, BundleError> {
+    let text = text.trim();
+    if text.starts_with("-----BEGIN") {
+        return VerifyingKey::from_public_key_pem(text).map_err(|e| BundleError::InvalidKey(e.to_string()));
+    }
+    VerifyingKey::from_bytes(&decode_key_bytes(text)?).map_err(|e| BundleError::InvalidKey(e.to_string()))
+}
+
+/// Signs a payload and returns the bundle as pretty JSON
+pub fn sign(payload: &BundlePayload, key: &SigningKey) -> Result<String, BundleError> {
+    let bytes = serde_json::to_vec(payload).map_err(|e| BundleError::Malformed(e.to_string()))?;
+    let bundle = SignedBundle {
+        payload: STANDARD.encode(&bytes),
+        signature: STANDARD.encode(key.sign(&bytes).to_bytes()),
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| BundleError::Malformed(e.to_string()))
+}
+
+/// Checks a bundle's signature and returns its payload; nothing is parsed from the
+/// payload until the signature is known to be good
+pub fn verify(text: &str, key: &VerifyingKey) -> Result<BundlePayload, BundleError> {
+    let bundle: SignedBundle = serde_json::from_str(text).map_err(|e| BundleError::Malformed(e.to_string()))?;
+    if bundle.signature.trim().is_empty() {
+        return Err(BundleError::Unsigned);
+    }
+
+    let bytes = STANDARD
+        .decode(bundle.payload.trim())
+        .map_err(|e| BundleError::Malformed(format!("payload: {}", e)))?;
+    let signature = STANDARD
+        .decode(bundle.signature.trim())
+        .ok()
+        .and_then(|s| Signature::from_slice(&s).ok())
+        .ok_or(BundleError::InvalidSignature)?;
+    key.verify_strict(&bytes, &signature)
+        .map_err(|_| BundleError::InvalidSignature)?;
+
+    let payload: BundlePayload = serde_json::from_slice(&bytes).map_err(|e| BundleError::Malformed(e.to_string()))?;
+    if payload.version != BUNDLE_VERSION {
+        return Err(BundleError::UnsupportedVersion(payload.version));
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PolicyAction;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn payload(version: u32) -> BundlePayload {
+        let entry = serde_json::from_value(serde_json::json!({
+            "instance_id": "USB\\VID_0781&PID_5567\\4C530001",
+            "friendly_name": "SanDisk Cruzer",
+            "added_at": "2026-01-05T09:00:00+00:00",
+        })).unwrap();
+        BundlePayload {
+            version,
+            created_at: "2026-01-05T09:00:00+00:00".to_string(),
+            whitelist: vec![entry],
+            policy: DevicePolicy { class_rules: Vec::new(), default_action: PolicyAction::Prompt },
+        }
+    }
+
+    fn with_payload(text: &str, change: impl FnOnce(&mut SignedBundle)) -> String {
+        let mut bundle: SignedBundle = serde_json::from_str(text).unwrap();
+        change(&mut bundle);
+        serde_json::to_string(&bundle).unwrap()
+    }
+
+    #[test]
+    fn signed_bundle_round_trips() {
+        let text = sign(&payload(BUNDLE_VERSION), &key(1)).unwrap();
+        let verified = verify(&text, &key(1).verifying_key()).unwrap();
+        assert_eq!(verified.created_at, "2026-01-05T09:00:00+00:00");
+        assert_eq!(verified.whitelist[0].instance_id, "USB\\VID_0781&PID_5567\\4C530001");
+        assert_eq!(verified.policy.default_action, PolicyAction::Prompt);
+    }
+
+    #[test]
+    fn unsigned_bundle_is_rejected() {
+        let text = with_payload(&sign(&payload(BUNDLE_VERSION), &key(1)).unwrap(), |b| b.signature.clear());
+        assert!(matches!(verify(&text, &key(1).verifying_key()), Err(BundleError::Unsigned)));
+        assert!(matches!(verify("{}", &key(1).verifying_key()), Err(BundleError::Unsigned)));
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let original = sign(&payload(BUNDLE_VERSION), &key(1)).unwrap();
+        let mut changed = payload(BUNDLE_VERSION);
+        changed.policy.default_action = PolicyAction::Allow;
+        let bytes = serde_json::to_vec(&changed).unwrap();
+
+        let text = with_payload(&original, |b| b.payload = STANDARD.encode(&bytes));
+        assert!(matches!(verify(&text, &key(1).verifying_key()), Err(BundleError::InvalidSignature)));
+        let text = with_payload(&original, |b| b.signature = "bm90IGEgc2lnbmF0dXJl".to_string());
+        assert!(matches!(verify(&text, &key(1).verifying_key()), Err(BundleError::InvalidSignature)));
+    }
+
+    #[test]
+    fn wrong_public_key_is_rejected() {
+        let text = sign(&payload(BUNDLE_VERSION), &key(1)).unwrap();
+        assert!(matches!(verify(&text, &key(2).verifying_key()), Err(BundleError::InvalidSignature)));
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let text = sign(&payload(BUNDLE_VERSION + 1), &key(1)).unwrap();
+        assert!(matches!(verify(&text, &key(1).verifying_key()), Err(BundleError::UnsupportedVersion(2))));
+    }
+
+    #[test]
+    fn keys_are_read_from_base64() {
+        let public = STANDARD.encode(key(1).verifying_key().to_bytes());
+        assert_eq!(parse_verifying_key(&public).unwrap(), key(1).verifying_key());
+        assert_eq!(parse_signing_key(&STANDARD.encode([1u8; 32])).unwrap().to_bytes(), key(1).to_bytes());
+        assert!(matches!(parse_verifying_key("AAAA"), Err(BundleError::InvalidKey(_))));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::models::{CaptureKind, UsbDevice};
+
+const CLASS_AUDIO: u8 = 0x01;
+const CLASS_VIDEO: u8 = 0x0e;
+
+/// What a device can record, from its USB interface classes or its Windows device class.
+/// USB audio interfaces don't say whether they record or play, so every USB audio device
+/// (headsets, but also speakers) counts as a microphone.
+pub fn classify(device: &UsbDevice) -> Vec<CaptureKind> {
+    let has = |code: u8| device.interface_classes.contains(&code);
+    let class_is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(&device.device_class));
+    let usb = device.bus_type.as_deref() == Some("USB");
+
+    let mut kinds = Vec::new();
+    if has(CLASS_VIDEO) || class_is(&["Camera", "Image"]) {
+        kinds.push(CaptureKind::Camera);
+    }
+    if has(CLASS_AUDIO) || class_is(&["AudioEndpoint"]) || (usb && class_is(&["MEDIA"])) {
+        kinds.push(CaptureKind::Microphone);
+    }
+    kinds
+}
+
+/// Capture kinds of a device and the functions grouped under it
+pub fn kinds(device: &UsbDevice) -> Vec<CaptureKind> {
+    let mut all = device.capture.clone();
+    all.extend(device.children.iter().flat_map(kinds));
+    all.sort();
+    all.dedup();
+    all
+}
+
+/// "Camera", "Microphone" or "Camera and microphone"
+pub fn describe(kinds: &[CaptureKind]) -> &'static str {
+    match (kinds.contains(&CaptureKind::Camera), kinds.contains(&CaptureKind::Microphone)) {
+        (true, true) => "Camera and microphone",
+        (true, false) => "Camera",
+        (false, true) => "Microphone",
+        (false, false) => "Device",
+    }
+}
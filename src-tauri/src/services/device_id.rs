@@ -0,0 +1,180 @@
+/// Identity fields parsed out of a device instance ID
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceIdentity {
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    /// Interface number of a composite device child (`MI_xx`)
+    pub interface: Option<String>,
+    pub serial: Option<String>,
+}
+
+/// Parses `USB\VID_0781&PID_5567\4C530001230822117433` style instance IDs.
+///
+/// The last segment is only a serial when the device reports one; Windows generates
+/// IDs containing `&` (e.g. `5&2A3B4C5D&0&2`) otherwise, and the sysfs backend falls
+/// back to the port path (e.g. `1-2.3`).
+pub fn parse_instance_id(instance_id: &str) -> DeviceIdentity {
+    let mut parts = instance_id.split('\\');
+    let _enumerator = parts.next();
+    let hardware = parts.next().unwrap_or("");
+    let instance = parts.next().unwrap_or("");
+
+    let mut identity = DeviceIdentity::default();
+    for token in hardware.split('&') {
+        if let Some(vid) = strip_prefix_ci(token, "VID_") {
+            identity.vendor_id = normalize_hex_id(vid);
+        } else if let Some(pid) = strip_prefix_ci(token, "PID_") {
+            identity.product_id = normalize_hex_id(pid);
+        } else if let Some(mi) = strip_prefix_ci(token, "MI_") {
+            identity.interface = Some(mi.to_uppercase());
+        }
+    }
+
+    if identity.vendor_id.is_some() && !instance.is_empty() && !is_generated_instance(instance) {
+        identity.serial = Some(instance.to_string());
+    }
+
+    identity
+}
+
+/// Bus named by the enumerator at the start of an instance ID (`USB\...`, `BTHENUM\...`).
+/// Function drivers such as `HID` or `SWD` return `None`; their bus is their parent's.
+pub fn enumerator_bus(instance_id: &str) -> Option<&'static str> {
+    let enumerator = instance_id.split('\\').next()?.to_uppercase();
+    Some(match enumerator.as_str() {
+        "USB" | "USBSTOR" | "USBPRINT" => "USB",
+        "BTHENUM" | "BTHLE" | "BTHLEDEVICE" | "BTH" => "Bluetooth",
+        "THUNDERBOLT" => "Thunderbolt",
+        "PCI" => "PCI",
+        "SD" | "SDBUS" => "SD",
+        "ACPI" => "ACPI",
+        "HDAUDIO" => "HDAudio",
+        _ => return None,
+    })
+}
+
+/// Parses a whitelist rule of the form `VID`, `VID:PID` or `VID:PID:SERIAL`
+pub fn parse_rule_spec(spec: &str) -> Result<(String, Option<String>, Option<String>), String> {
+    let mut parts = spec.trim().splitn(3, ':');
+    let vendor_id = parts
+        .next()
+        .and_then(normalize_hex_id)
+        .ok_or_else(|| format!("Invalid vendor ID in '{}'", spec))?;
+
+    let product_id = match parts.next() {
+        None => None,
+        Some(pid) => Some(normalize_hex_id(pid).ok_or_else(|| format!("Invalid product ID in '{}'", spec))?),
+    };
+
+    let serial = match parts.next().map(str::trim) {
+        None => None,
+        Some("") => return Err(format!("Empty serial in '{}'", spec)),
+        Some(serial) => Some(serial.to_string()),
+    };
+
+    Ok((vendor_id, product_id, serial))
+}
+
+/// Normalizes a 16-bit USB ID to four uppercase hex digits
+pub fn normalize_hex_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 4 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("{:0>4}", value.to_uppercase()))
+}
+
+fn strip_prefix_ci<'a>(token: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = token.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&token[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Whether the instance segment was made up by the OS rather than read from the device
+fn is_generated_instance(instance: &str) -> bool {
+    // Windows: "5&2A3B4C5D&0&2"
+    if instance.contains('&') {
+        return true;
+    }
+    // sysfs port path: "<bus>-<port>[.<port>...]"
+    match instance.split_once('-') {
+        Some((bus, ports)) => {
+            !bus.is_empty()
+                && bus.chars().all(|c| c.is_ascii_digit())
+                && !ports.is_empty()
+                && ports.chars().all(|c| c.is_ascii_digit() || c == '.')
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(identity: &DeviceIdentity) -> (Option<&str>, Option<&str>, Option<&str>) {
+        (identity.vendor_id.as_deref(), identity.product_id.as_deref(), identity.serial.as_deref())
+    }
+
+    #[test]
+    fn instance_id_with_serial() {
+        let identity = parse_instance_id("USB\\VID_0781&PID_5567\\4C530001230822117433");
+        assert_eq!(ids(&identity), (Some("0781"), Some("5567"), Some("4C530001230822117433")));
+        assert_eq!(identity.interface, None);
+    }
+
+    #[test]
+    fn instance_id_fields_are_case_insensitive() {
+        let identity = parse_instance_id("usb\\vid_046d&pid_c52b&mi_02\\7&1A2B3C4D&0&0002");
+        assert_eq!(ids(&identity), (Some("046D"), Some("C52B"), None));
+        assert_eq!(identity.interface.as_deref(), Some("02"));
+    }
+
+    #[test]
+    fn generated_instances_are_not_serials() {
+        // Windows makes these up for devices without a serial
+        assert_eq!(parse_instance_id("USB\\VID_1A2C&PID_2124\\5&2A3B4C5D&0&2").serial, None);
+        // The sysfs backend falls back to the port path
+        assert_eq!(parse_instance_id("USB\\VID_1A2C&PID_2124\\1-2.3").serial, None);
+        assert_eq!(parse_instance_id("USB\\VID_1A2C&PID_2124\\3-10").serial, None);
+        // Serials that only look a bit like port paths are kept
+        assert_eq!(parse_instance_id("USB\\VID_1A2C&PID_2124\\A-1").serial.as_deref(), Some("A-1"));
+        assert_eq!(parse_instance_id("USB\\VID_1A2C&PID_2124\\1-").serial.as_deref(), Some("1-"));
+    }
+
+    #[test]
+    fn instance_ids_without_a_vendor_have_no_serial() {
+        let identity = parse_instance_id("ROOT\\SYSTEM\\0001");
+        assert_eq!(identity, DeviceIdentity::default());
+        assert_eq!(parse_instance_id("USB\\VID_ZZZZ&PID_0001\\ABC"), DeviceIdentity { product_id: Some("0001".to_string()), ..Default::default() });
+        assert_eq!(parse_instance_id(""), DeviceIdentity::default());
+    }
+
+    #[test]
+    fn rule_specs_at_each_level() {
+        assert_eq!(parse_rule_spec("1050"), Ok(("1050".to_string(), None, None)));
+        assert_eq!(parse_rule_spec(" 1050:407 "), Ok(("1050".to_string(), Some("0407".to_string()), None)));
+        assert_eq!(parse_rule_spec("1050:0407:ccid:1"), Ok(("1050".to_string(), Some("0407".to_string()), Some("ccid:1".to_string()))));
+        assert_eq!(parse_rule_spec("abcd:ef01"), Ok(("ABCD".to_string(), Some("EF01".to_string()), None)));
+    }
+
+    #[test]
+    fn malformed_rule_specs_are_rejected() {
+        assert_eq!(parse_rule_spec(""), Err("Invalid vendor ID in ''".to_string()));
+        assert_eq!(parse_rule_spec("10500"), Err("Invalid vendor ID in '10500'".to_string()));
+        assert_eq!(parse_rule_spec("VID_1050"), Err("Invalid vendor ID in 'VID_1050'".to_string()));
+        assert_eq!(parse_rule_spec("1050:"), Err("Invalid product ID in '1050:'".to_string()));
+        assert_eq!(parse_rule_spec("1050:xyz"), Err("Invalid product ID in '1050:xyz'".to_string()));
+        assert_eq!(parse_rule_spec("1050:0407: "), Err("Empty serial in '1050:0407: '".to_string()));
+    }
+
+    #[test]
+    fn enumerators_name_their_bus() {
+        assert_eq!(enumerator_bus("USBSTOR\\Disk&Ven_SanDisk\\123"), Some("USB"));
+        assert_eq!(enumerator_bus("bthle\\dev_aabbccddeeff"), Some("Bluetooth"));
+        assert_eq!(enumerator_bus("HID\\VID_046D&PID_C52B&MI_00\\8&1"), None);
+    }
+}
@@ -0,0 +1,289 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use serde::Serialize;
+use crate::models::DiskVolume;
+
+/// Bytes read from the start of a volume; enough to reach the ISO 9660 descriptor at 32 KiB
+pub const HEADER_LEN: usize = 36 * 1024;
+// The VeraCrypt salt and the start of its encrypted header
+const VERACRYPT_HEADER_LEN: usize = 512;
+// Shannon entropy (bits/byte) above which a header with no known signature is taken as encrypted
+const RANDOM_ENTROPY: f64 = 7.0;
+
+/// Encryption found on a volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeEncryption {
+    Luks,
+    BitLocker,
+    /// VeraCrypt headers have no plaintext signature; reported when the header looks random
+    VeraCrypt,
+    None,
+}
+
+impl VolumeEncryption {
+    pub fn is_encrypted(self) -> bool {
+        self != Self::None
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeReport {
+    /// Partition number, `None` for a volume that spans the whole disk
+    pub partition: Option<usize>,
+    pub offset: u64,
+    pub encryption: VolumeEncryption,
+    /// Plaintext filesystem found instead, e.g. `FAT32`
+    pub filesystem: Option<String>,
+}
+
+/// Result of inspecting a block device or disk image
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionReport {
+    pub path: String,
+    pub volumes: Vec<VolumeReport>,
+    /// True when there is at least one volume and every volume is encrypted
+    pub encrypted: bool,
+}
+
+/// Reads up to `len` bytes at `offset`, returning fewer at end of file.
+/// `len` may come from on-disk fields, so the buffer only grows with what is actually read.
+pub fn read_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Recognizes plaintext filesystems by their superblock/boot sector signatures
+pub fn filesystem_signature(header: &[u8]) -> Option<&'static str> {
+    let at = |offset: usize, magic: &[u8]| header.get(offset..offset + magic.len()) == Some(magic);
+
+    if at(3, b"NTFS    ") {
+        Some("NTFS")
+    } else if at(3, b"EXFAT   ") {
+        Some("exFAT")
+    } else if at(82, b"FAT32") {
+        Some("FAT32")
+    } else if at(54, b"FAT") {
+        Some("FAT")
+    } else if at(1080, &[0x53, 0xEF]) {
+        Some("ext")
+    } else if at(0, b"XFSB") {
+        Some("XFS")
+    } else if at(1024, b"H+") || at(1024, b"HX") {
+        Some("HFS+")
+    } else if at(32, b"NXSB") {
+        Some("APFS")
+    } else if at(32769, b"CD001") {
+        Some("ISO 9660")
+    } else {
+        None
+    }
+}
+
+fn entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for &b in bytes {
+        counts[b as usize] += 1;
+    }
+    let len = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Classifies a volume from its first bytes
+pub fn inspect_header(header: &[u8]) -> (VolumeEncryption, Option<&'static str>) {
+    if header.starts_with(b"LUKS\xBA\xBE") {
+        return (VolumeEncryption::Luks, None);
+    }
+    if header.get(3..11) == Some(b"-FVE-FS-") {
+        return (VolumeEncryption::BitLocker, None);
+    }
+    if let Some(filesystem) = filesystem_signature(header) {
+        return (VolumeEncryption::None, Some(filesystem));
+    }
+    // VeraCrypt: 64-byte salt followed by an encrypted header, indistinguishable from noise
+    let first_sector = &header[..header.len().min(VERACRYPT_HEADER_LEN)];
+    if entropy(first_sector) >= RANDOM_ENTROPY {
+        return (VolumeEncryption::VeraCrypt, None);
+    }
+    (VolumeEncryption::None, None)
+}
+
+/// Inspects the volumes of a disk at the partition offsets the OS reports
+/// (the whole disk when there are none)
+pub fn inspect<R: Read + Seek>(reader: &mut R, path: &str, volumes: &[DiskVolume]) -> io::Result<EncryptionReport> {
+    let regions: Vec<(Option<usize>, u64)> = if volumes.is_empty() {
+        vec![(None, 0)]
+    } else {
+        volumes.iter().map(|v| (Some(v.partition), v.offset)).collect()
+    };
+
+    let mut volumes = Vec::new();
+    for (partition, offset) in regions {
+        let header = read_at(reader, offset, HEADER_LEN)?;
+        let (encryption, filesystem) = inspect_header(&header);
+        volumes.push(VolumeReport {
+            partition,
+            offset,
+            encryption,
+            filesystem: filesystem.map(String::from),
+        });
+    }
+
+    let encrypted = !volumes.is_empty() && volumes.iter().all(|v| v.encryption.is_encrypted());
+    Ok(EncryptionReport {
+        path: path.to_string(),
+        volumes,
+        encrypted,
+    })
+}
+
+/// Inspects a block device (`/dev/sdb`, `\\.\PhysicalDrive2`) or a disk image file
+pub fn inspect_path(path: &Path, volumes: &[DiskVolume]) -> io::Result<EncryptionReport> {
+    let mut file = File::open(path)?;
+    inspect(&mut file, &path.to_string_lossy(), volumes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    const MIB: u64 = 1024 * 1024;
+
+    /// Deterministic bytes that look like ciphertext (xorshift64)
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        }).collect()
+    }
+
+    fn luks_header() -> Vec<u8> {
+        let mut header = vec![0u8; 4096];
+        header[..6].copy_from_slice(b"LUKS\xBA\xBE");
+        header[6..8].copy_from_slice(&2u16.to_be_bytes());
+        header
+    }
+
+    fn bitlocker_header() -> Vec<u8> {
+        let mut header = vec![0u8; 512];
+        header[..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        header[3..11].copy_from_slice(b"-FVE-FS-");
+        header[510..512].copy_from_slice(&[0x55, 0xAA]);
+        header
+    }
+
+    fn fat32_header() -> Vec<u8> {
+        let mut header = vec![0u8; 512];
+        header[..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        header[3..11].copy_from_slice(b"MSDOS5.0");
+        header[82..90].copy_from_slice(b"FAT32   ");
+        header[510..512].copy_from_slice(&[0x55, 0xAA]);
+        header
+    }
+
+    /// A sparse disk image with `volumes` written at their offsets
+    fn image(size: u64, volumes: &[(u64, Vec<u8>)]) -> Cursor<Vec<u8>> {
+        let mut disk = vec![0u8; size as usize];
+        for (offset, bytes) in volumes {
+            let start = *offset as usize;
+            disk[start..start + bytes.len()].copy_from_slice(bytes);
+        }
+        Cursor::new(disk)
+    }
+
+    fn volume(partition: usize, offset: u64) -> DiskVolume {
+        DiskVolume { partition, offset }
+    }
+
+    #[test]
+    fn headers_are_classified() {
+        assert_eq!(inspect_header(&luks_header()), (VolumeEncryption::Luks, None));
+        assert_eq!(inspect_header(&bitlocker_header()), (VolumeEncryption::BitLocker, None));
+        assert_eq!(inspect_header(&noise(HEADER_LEN, 7)), (VolumeEncryption::VeraCrypt, None));
+        assert_eq!(inspect_header(&fat32_header()), (VolumeEncryption::None, Some("FAT32")));
+        assert_eq!(inspect_header(&[0u8; 512]), (VolumeEncryption::None, None));
+        assert_eq!(inspect_header(&[]), (VolumeEncryption::None, None));
+    }
+
+    #[test]
+    fn luks_disk_without_partitions_is_encrypted() {
+        let mut disk = image(MIB, &[(0, luks_header())]);
+        let report = inspect(&mut disk, "/dev/sdb", &[]).unwrap();
+
+        assert!(report.encrypted);
+        assert_eq!(report.volumes.len(), 1);
+        assert_eq!(report.volumes[0].partition, None);
+        assert_eq!(report.volumes[0].encryption, VolumeEncryption::Luks);
+    }
+
+    #[test]
+    fn volumes_are_read_at_reported_offsets() {
+        let mut disk = image(4 * MIB, &[(MIB, bitlocker_header()), (2 * MIB, noise(HEADER_LEN, 42)), (3 * MIB, luks_header())]);
+        let report = inspect(&mut disk, "disk.img", &[volume(1, MIB), volume(2, 2 * MIB), volume(3, 3 * MIB)]).unwrap();
+
+        let found: Vec<_> = report.volumes.iter().map(|v| (v.partition, v.offset, v.encryption)).collect();
+        assert_eq!(found, vec![
+            (Some(1), MIB, VolumeEncryption::BitLocker),
+            (Some(2), 2 * MIB, VolumeEncryption::VeraCrypt),
+            (Some(3), 3 * MIB, VolumeEncryption::Luks),
+        ]);
+        assert!(report.encrypted);
+    }
+
+    #[test]
+    fn one_plaintext_volume_makes_the_disk_unencrypted() {
+        let mut disk = image(3 * MIB, &[(MIB, luks_header()), (2 * MIB, fat32_header())]);
+        let report = inspect(&mut disk, "/dev/sdc", &[volume(1, MIB), volume(2, 2 * MIB)]).unwrap();
+
+        assert!(!report.encrypted);
+        assert_eq!(report.volumes[1].encryption, VolumeEncryption::None);
+        assert_eq!(report.volumes[1].filesystem.as_deref(), Some("FAT32"));
+    }
+
+    #[test]
+    fn volume_at_end_of_image_reads_a_short_header() {
+        let mut disk = image(MIB + 512, &[(MIB, bitlocker_header())]);
+        let report = inspect(&mut disk, "/dev/sdd", &[volume(1, MIB), volume(2, 4 * MIB)]).unwrap();
+
+        assert_eq!(report.volumes[0].encryption, VolumeEncryption::BitLocker);
+        assert_eq!(report.volumes[1].encryption, VolumeEncryption::None);
+        assert!(!report.encrypted);
+    }
+
+    #[test]
+    fn read_at_does_not_allocate_the_requested_length() {
+        let mut disk = image(4096, &[(0, luks_header())]);
+        let bytes = read_at(&mut disk, 4000, usize::MAX).unwrap();
+        assert_eq!(bytes.len(), 96);
+        assert!(bytes.capacity() < 1024 * 1024);
+    }
+
+    #[test]
+    fn image_files_are_inspected_from_disk() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&vec![0u8; MIB as usize]).unwrap();
+        file.write_all(&noise(MIB as usize, 99)).unwrap();
+
+        let report = inspect_path(file.path(), &[volume(1, MIB)]).unwrap();
+        assert_eq!(report.path, file.path().to_string_lossy());
+        assert_eq!(report.volumes[0].encryption, VolumeEncryption::VeraCrypt);
+        assert!(report.encrypted);
+    }
+}
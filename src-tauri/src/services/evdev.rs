@@ -0,0 +1,197 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::mem;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::services::keystroke::KeyEvent;
+use crate::services::sysfs::{self, DEFAULT_SYSFS_ROOT};
+
+const EV_KEY: u16 = 0x01;
+const DEFAULT_DEV_ROOT: &str = "/dev";
+// How long to sleep when a non-blocking read finds nothing
+const READ_IDLE: Duration = Duration::from_millis(10);
+
+/// Locates and reads evdev nodes for USB devices
+#[derive(Debug, Clone)]
+pub struct Evdev {
+    sysfs_root: PathBuf,
+    dev_root: PathBuf,
+}
+
+impl Default for Evdev {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYSFS_ROOT, DEFAULT_DEV_ROOT)
+    }
+}
+
+impl Evdev {
+    pub fn new(sysfs_root: impl Into<PathBuf>, dev_root: impl Into<PathBuf>) -> Self {
+        Self {
+            sysfs_root: sysfs_root.into(),
+            dev_root: dev_root.into(),
+        }
+    }
+
+    /// Finds the `/dev/input/eventN` nodes created for a USB device
+    pub fn event_nodes_for(&self, instance_id: &str) -> io::Result<Vec<PathBuf>> {
+        let mut nodes = Vec::new();
+
+        for entry in fs::read_dir(self.sysfs_root.join("class").join("input"))? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("event") {
+                continue;
+            }
+            let Ok(device_dir) = fs::canonicalize(entry.path().join("device")) else {
+                continue;
+            };
+            if sysfs::owning_instance_id(&device_dir).as_deref() == Some(instance_id) {
+                nodes.push(self.dev_root.join("input").join(&name));
+            }
+        }
+
+        nodes.sort();
+        Ok(nodes)
+    }
+
+    /// Collects key events from the given nodes until `duration` has elapsed or `stop`
+    /// returns true for the events gathered so far (requires root)
+    pub fn capture(
+        &self,
+        nodes: &[PathBuf],
+        duration: Duration,
+        mut stop: impl FnMut(&[KeyEvent]) -> bool,
+    ) -> io::Result<Vec<KeyEvent>> {
+        let mut files: Vec<File> = nodes
+            .iter()
+            .map(|node| OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(node))
+            .collect::<io::Result<_>>()?;
+
+        let deadline = Instant::now() + duration;
+        let mut events = Vec::new();
+        let mut buf = vec![0u8; mem::size_of::<libc::input_event>() * 64];
+
+        while Instant::now() < deadline {
+            let mut idle = true;
+            for file in &mut files {
+                match file.read(&mut buf) {
+                    Ok(0) => {}
+                    Ok(len) => {
+                        idle = false;
+                        events.extend(parse_input_events(&buf[..len]));
+                        if stop(&events) {
+                            return Ok(events);
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                    // The device was unplugged or deauthorized mid-capture
+                    Err(_) => return Ok(events),
+                }
+            }
+            if idle {
+                thread::sleep(READ_IDLE);
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+/// Decodes raw `struct input_event` records (native layout) and keeps the key events
+pub fn parse_input_events(bytes: &[u8]) -> Vec<KeyEvent> {
+    let size = mem::size_of::<libc::input_event>();
+    bytes
+        .chunks_exact(size)
+        .filter_map(|chunk| {
+            // SAFETY: chunk is exactly size_of::<input_event>() bytes and every bit pattern is valid
+            let event: libc::input_event = unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const _) };
+            if event.type_ != EV_KEY {
+                return None;
+            }
+            let timestamp_us = (event.time.tv_sec as u64)
+                .saturating_mul(1_000_000)
+                .saturating_add(event.time.tv_usec as u64);
+            Some(KeyEvent {
+                timestamp_us,
+                code: event.code,
+                value: event.value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::keystroke;
+
+    /// Encodes events the way the kernel writes them to `/dev/input/event*`
+    fn record(events: &[(u64, u16, u16, i32)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for &(timestamp_us, type_, code, value) in events {
+            // SAFETY: input_event is plain old data
+            let mut event: libc::input_event = unsafe { mem::zeroed() };
+            event.time.tv_sec = (timestamp_us / 1_000_000) as _;
+            event.time.tv_usec = (timestamp_us % 1_000_000) as _;
+            event.type_ = type_;
+            event.code = code;
+            event.value = value;
+            // SAFETY: reading the bytes of a fully initialized POD struct
+            let raw = unsafe {
+                std::slice::from_raw_parts(
+                    &event as *const _ as *const u8,
+                    mem::size_of::<libc::input_event>(),
+                )
+            };
+            bytes.extend_from_slice(raw);
+        }
+        bytes
+    }
+
+    #[test]
+    fn key_events_are_decoded_and_others_skipped() {
+        const EV_SYN: u16 = 0x00;
+        const EV_MSC: u16 = 0x04;
+        let bytes = record(&[
+            (1_500_000, EV_MSC, 4, 0x70004),
+            (1_500_000, EV_KEY, 30, 1),
+            (1_500_000, EV_SYN, 0, 0),
+            (1_560_000, EV_KEY, 30, 0),
+        ]);
+
+        let events = parse_input_events(&bytes);
+        assert_eq!(
+            events,
+            vec![
+                KeyEvent {
+                    timestamp_us: 1_500_000,
+                    code: 30,
+                    value: 1
+                },
+                KeyEvent {
+                    timestamp_us: 1_560_000,
+                    code: 30,
+                    value: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn recorded_injection_is_scored_from_raw_input() {
+        let mut raw = Vec::new();
+        for i in 0..100u64 {
+            raw.push((2_000_000 + i * 6_000, EV_KEY, 30 + (i % 10) as u16, 1));
+            raw.push((2_001_000 + i * 6_000, EV_KEY, 30 + (i % 10) as u16, 0));
+        }
+        let mut bytes = record(&raw);
+        // A capture cut off mid-event keeps the complete ones
+        bytes.extend_from_slice(&[0; 5]);
+
+        let report = keystroke::analyze(&parse_input_events(&bytes));
+        assert_eq!(report.keystrokes, 100);
+        assert!(report.injection_suspected);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use crate::services::backend::{BackendError, SystemBackend};
+
+/// Fake system backend that replays recorded JSON outputs.
+///
+/// Outputs are keyed by query name (`usb_devices`, `system_info`, `firewall_rules`, ...).
+/// When loaded from a directory each `<key>.json` file provides one output.
+/// Actions such as `disable_device` succeed without side effects and are recorded.
+#[derive(Default)]
+pub struct FixtureBackend {
+    outputs: HashMap<String, String>,
+    actions: Mutex<Vec<String>>,
+}
+
+impl FixtureBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every `<key>.json` file in a directory as a recorded output
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| BackendError::Fixture(format!("{}: {}", dir.display(), e)))?;
+
+        let mut backend = Self::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(key) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)
+                .map_err(|e| BackendError::Fixture(format!("{}: {}", path.display(), e)))?;
+            backend.outputs.insert(key.to_string(), content.trim().to_string());
+        }
+        Ok(backend)
+    }
+
+    /// Sets the recorded output for a query
+    #[cfg(test)]
+    pub fn with_output(mut self, key: &str, json: &str) -> Self {
+        self.outputs.insert(key.to_string(), json.trim().to_string());
+        self
+    }
+
+    /// Actions performed so far, e.g. `disable_device:USB\VID_0781&PID_5567\123`
+    #[cfg(test)]
+    pub fn actions(&self) -> Vec<String> {
+        self.actions.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn output(&self, key: &str) -> Result<String, BackendError> {
+        self.outputs
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BackendError::Fixture(format!("No recorded output for '{}'", key)))
+    }
+
+    fn record(&self, action: String) -> Result<(), BackendError> {
+        self.actions.lock().unwrap_or_else(|e| e.into_inner()).push(action);
+        Ok(())
+    }
+}
+
+impl SystemBackend for FixtureBackend {
+    fn usb_devices_json(&self) -> Result<String, BackendError> {
+        self.output("usb_devices")
+    }
+
+    fn disable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        self.record(format!("disable_device:{}", instance_id))
+    }
+
+    fn enable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        self.record(format!("enable_device:{}", instance_id))
+    }
+
+    fn set_default_deny(&self, enabled: bool) -> Result<(), BackendError> {
+        self.record(format!("set_default_deny:{}", enabled))
+    }
+
+    fn block_devices_json(&self, _instance_id: &str) -> Result<String, BackendError> {
+        self.output("block_devices")
+    }
+
+    fn mount_points_json(&self, _instance_id: &str) -> Result<String, BackendError> {
+        self.output("mount_points")
+    }
+
+    fn bluetooth_devices_json(&self) -> Result<String, BackendError> {
+        self.output("bluetooth_devices")
+    }
+
+    fn unpair_bluetooth_device(&self, address: &str) -> Result<(), BackendError> {
+        self.record(format!("unpair_bluetooth_device:{}", address))
+    }
+
+    fn set_bluetooth_blocked(&self, address: &str, blocked: bool) -> Result<(), BackendError> {
+        self.record(format!("set_bluetooth_blocked:{}:{}", address, blocked))
+    }
+
+    fn kernel_modules_json(&self) -> Result<String, BackendError> {
+        self.output("kernel_modules")
+    }
+
+    fn unload_kernel_module(&self, name: &str) -> Result<(), BackendError> {
+        self.record(format!("unload_kernel_module:{}", name))
+    }
+
+    fn system_info_json(&self) -> Result<String, BackendError> {
+        self.output("system_info")
+    }
+
+    fn startup_programs_json(&self) -> Result<String, BackendError> {
+        self.output("startup_programs")
+    }
+
+    fn network_info_json(&self) -> Result<String, BackendError> {
+        self.output("network_info")
+    }
+
+    fn connected_wifi_json(&self) -> Result<String, BackendError> {
+        self.output("connected_wifi")
+    }
+
+    fn wifi_profiles_json(&self) -> Result<String, BackendError> {
+        self.output("wifi_profiles")
+    }
+
+    fn wifi_password_json(&self, _ssid: &str) -> Result<String, BackendError> {
+        self.output("wifi_password")
+    }
+
+    fn temp_info_json(&self) -> Result<String, BackendError> {
+        self.output("temp_info")
+    }
+
+    fn clean_temp_files_json(&self) -> Result<String, BackendError> {
+        self.output("clean_temp_files")
+    }
+
+    fn firewall_status_json(&self) -> Result<String, BackendError> {
+        self.output("firewall_status")
+    }
+
+    fn firewall_rules_json(&self) -> Result<String, BackendError> {
+        self.output("firewall_rules")
+    }
+
+    fn block_port(&self, port: u16, protocol: &str, rule_name: &str) -> Result<(), BackendError> {
+        self.record(format!("block_port:{}:{}:{}", port, protocol, rule_name))
+    }
+
+    fn remove_firewall_rule(&self, rule_name: &str) -> Result<(), BackendError> {
+        self.record(format!("remove_firewall_rule:{}", rule_name))
+    }
+
+    fn enable_firewall_logging(&self) -> Result<(), BackendError> {
+        self.record("enable_firewall_logging".to_string())
+    }
+
+    fn high_memory_processes_json(&self) -> Result<String, BackendError> {
+        self.output("high_memory_processes")
+    }
+
+    fn kill_process(&self, process_id: u32) -> Result<(), BackendError> {
+        self.record(format!("kill_process:{}", process_id))
+    }
+
+    fn critical_services_json(&self) -> Result<String, BackendError> {
+        self.output("critical_services")
+    }
+
+    fn restart_service(&self, service_name: &str) -> Result<(), BackendError> {
+        self.record(format!("restart_service:{}", service_name))
+    }
+
+    fn start_service(&self, service_name: &str) -> Result<(), BackendError> {
+        self.record(format!("start_service:{}", service_name))
+    }
+}
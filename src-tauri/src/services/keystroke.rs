@@ -0,0 +1,175 @@
+use serde::Serialize;
+
+/// Fewer key presses than this is never treated as an injection
+pub const MIN_KEYSTROKES: usize = 20;
+/// Score at or above which a device is reported as a keystroke injector
+pub const SUSPECT_SCORE: u8 = 70;
+
+/// A single key press or release with its kernel timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub timestamp_us: u64,
+    pub code: u16,
+    /// 0 = release, 1 = press, 2 = autorepeat (evdev semantics)
+    pub value: i32,
+}
+
+/// Cadence statistics for the key presses seen from one device
+#[derive(Debug, Clone, Serialize)]
+pub struct CadenceReport {
+    pub keystrokes: usize,
+    pub mean_interval_ms: f64,
+    pub stddev_interval_ms: f64,
+    /// 0 (human-like) .. 100 (machine-like)
+    pub score: u8,
+    pub injection_suspected: bool,
+}
+
+/// Scores a recorded key event stream.
+///
+/// People type with intervals of roughly 80-300 ms that vary a lot from key to key;
+/// injectors type hundreds of keys a few milliseconds apart at an almost constant rate.
+/// Speed and regularity each contribute to the score.
+pub fn analyze(events: &[KeyEvent]) -> CadenceReport {
+    let mut presses: Vec<u64> = events
+        .iter()
+        .filter(|e| e.value == 1)
+        .map(|e| e.timestamp_us)
+        .collect();
+    presses.sort_unstable();
+
+    let intervals: Vec<f64> = presses
+        .windows(2)
+        .map(|w| (w[1] - w[0]) as f64 / 1000.0)
+        .collect();
+
+    let (mean, stddev) = mean_and_stddev(&intervals);
+    let mut report = CadenceReport {
+        keystrokes: presses.len(),
+        mean_interval_ms: mean,
+        stddev_interval_ms: stddev,
+        score: 0,
+        injection_suspected: false,
+    };
+
+    if presses.len() < MIN_KEYSTROKES {
+        return report;
+    }
+
+    let speed = match mean {
+        m if m < 15.0 => 60,
+        m if m < 35.0 => 40,
+        m if m < 60.0 => 20,
+        _ => 0,
+    };
+
+    // Coefficient of variation; a zero mean means every key arrived in the same instant
+    let variation = if mean > 0.0 { stddev / mean } else { 0.0 };
+    let regularity = match variation {
+        v if v < 0.05 => 40,
+        v if v < 0.15 => 25,
+        v if v < 0.30 => 10,
+        _ => 0,
+    };
+
+    report.score = (speed + regularity).min(100) as u8;
+    report.injection_suspected = report.score >= SUSPECT_SCORE;
+    report
+}
+
+fn mean_and_stddev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Gaps between key presses (ms) recorded from a person typing a sentence
+    const HUMAN_GAPS_MS: [u64; 32] = [
+        142, 95, 231, 180, 88, 305, 120, 167, 99, 254, 131, 176, 84, 212, 148, 119, 290, 103, 158,
+        187, 92, 266, 139, 110, 201, 97, 173, 244, 126, 89, 318, 152,
+    ];
+
+    /// Builds a press/release stream from gaps between presses, holding each key for `hold_us`
+    fn stream(gaps_us: impl IntoIterator<Item = u64>, hold_us: u64) -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        let mut t = 1_000_000;
+        for (i, gap) in gaps_us.into_iter().enumerate() {
+            t += gap;
+            let code = 16 + (i % 26) as u16;
+            events.push(KeyEvent {
+                timestamp_us: t,
+                code,
+                value: 1,
+            });
+            events.push(KeyEvent {
+                timestamp_us: t + hold_us,
+                code,
+                value: 0,
+            });
+        }
+        events
+    }
+
+    #[test]
+    fn human_typing_is_not_suspected() {
+        let report = analyze(&stream(HUMAN_GAPS_MS.iter().map(|ms| ms * 1000), 60_000));
+        assert_eq!(report.keystrokes, 32);
+        assert!(report.mean_interval_ms > 100.0);
+        assert_eq!(report.score, 0);
+        assert!(!report.injection_suspected);
+    }
+
+    #[test]
+    fn regular_injected_typing_is_suspected() {
+        // A Rubber Ducky-style payload: a key every 8 ms with a few microseconds of jitter
+        let gaps = (0..200).map(|i| 8_000 + (i % 3) * 5);
+        let report = analyze(&stream(gaps, 2_000));
+        assert_eq!(report.keystrokes, 200);
+        assert!(report.mean_interval_ms < 15.0);
+        assert_eq!(report.score, 100);
+        assert!(report.injection_suspected);
+    }
+
+    #[test]
+    fn slower_but_perfectly_regular_injection_is_suspected() {
+        // Injectors throttled to dodge speed checks still type like a metronome
+        let report = analyze(&stream(std::iter::repeat_n(30_000, 60), 5_000));
+        assert_eq!(report.score, 80);
+        assert!(report.injection_suspected);
+    }
+
+    #[test]
+    fn fast_irregular_typing_stays_below_the_threshold() {
+        // A fast typist: about 50 ms per key but uneven
+        let report = analyze(&stream(HUMAN_GAPS_MS.iter().map(|ms| ms * 300), 30_000));
+        assert!(report.score < SUSPECT_SCORE);
+        assert!(!report.injection_suspected);
+    }
+
+    #[test]
+    fn short_bursts_are_never_suspected() {
+        let report = analyze(&stream(std::iter::repeat_n(1_000, MIN_KEYSTROKES - 1), 500));
+        assert_eq!(report.keystrokes, MIN_KEYSTROKES - 1);
+        assert_eq!(report.score, 0);
+        assert!(!report.injection_suspected);
+    }
+
+    #[test]
+    fn releases_and_autorepeat_are_not_counted() {
+        let mut events = stream(std::iter::repeat_n(8_000, 30), 2_000);
+        events.push(KeyEvent {
+            timestamp_us: 9_000_000,
+            code: 30,
+            value: 2,
+        });
+        assert_eq!(analyze(&events).keystrokes, 30);
+    }
+}
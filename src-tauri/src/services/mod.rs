@@ -1,2 +1,31 @@
+pub mod backend;
+pub mod badusb;
+pub mod bluetooth;
+#[cfg(target_os = "linux")]
+pub mod bluez;
+pub mod bundle;
+pub mod capture;
+pub mod device_id;
+pub mod encryption;
+#[cfg(target_os = "linux")]
+pub mod evdev;
+pub mod fixtures;
+pub mod kernel_modules;
+#[cfg(target_os = "linux")]
+pub mod keystroke;
+#[cfg(target_os = "linux")]
+pub mod linux;
+pub mod media_scan;
+#[cfg(target_os = "linux")]
+pub mod mounts;
+pub mod partition;
 pub mod powershell;
 pub mod storage;
+#[cfg(target_os = "linux")]
+pub mod sysfs;
+#[cfg(target_os = "linux")]
+pub mod thunderbolt;
+#[cfg(target_os = "linux")]
+pub mod uevent;
+pub mod usbguard;
+pub mod yara;
@@ -0,0 +1,133 @@
+use std::fs;
+use std::io;
+
+/// Mount table of the running system
+pub const PROC_MOUNTS: &str = "/proc/mounts";
+
+/// A line of `/proc/mounts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    pub source: String,
+    pub target: String,
+}
+
+/// Undoes the octal escapes `/proc/mounts` uses for spaces, tabs and backslashes
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).filter(|d| d.iter().all(|b| (b'0'..=b'7').contains(b)));
+        match (bytes[i], octal) {
+            (b'\\', Some(digits)) => {
+                out.push(digits.iter().fold(0u8, |acc, d| acc.wrapping_mul(8).wrapping_add(d - b'0')));
+                i += 4;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+pub fn parse_mounts(text: &str) -> Vec<MountEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(MountEntry {
+                source: unescape(fields.next()?),
+                target: unescape(fields.next()?),
+            })
+        })
+        .collect()
+}
+
+/// Whether `source` is the disk `/dev/<disk>` itself or one of its partitions (`sdb1`, `nvme0n1p2`)
+fn is_on_disk(source: &str, disk: &str) -> bool {
+    let Some(rest) = source.strip_prefix("/dev/").and_then(|s| s.strip_prefix(disk)) else {
+        return false;
+    };
+    if rest.is_empty() {
+        return true;
+    }
+    // Disks whose name ends in a digit separate the partition number with a 'p'
+    let digits = if disk.ends_with(|c: char| c.is_ascii_digit()) { rest.strip_prefix('p') } else { Some(rest) };
+    digits.is_some_and(|d| !d.is_empty() && d.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Mount points of the given disks (kernel names such as `sdb`) and their partitions,
+/// in mount table order and each listed once
+pub fn mount_points_in(text: &str, disks: &[String]) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for mount in parse_mounts(text) {
+        if disks.iter().any(|d| is_on_disk(&mount.source, d)) && !targets.contains(&mount.target) {
+            targets.push(mount.target);
+        }
+    }
+    targets
+}
+
+/// Mount points of the given disks on the running system
+pub fn mount_points(disks: &[String]) -> io::Result<Vec<String>> {
+    Ok(mount_points_in(&fs::read_to_string(PROC_MOUNTS)?, disks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = r"/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+/dev/sdb1 /media/alice/USB\040DISK vfat rw,nosuid,nodev 0 0
+/dev/sdb2 /media/alice/tab\011and\134slash exfat rw 0 0
+/dev/sdbb1 /media/alice/other vfat rw 0 0
+/dev/sdb1 /mnt/bind vfat rw 0 0
+/dev/sdb1 /media/alice/USB\040DISK vfat rw 0 0
+tmpfs /run tmpfs rw 0 0
+";
+
+    fn disks(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn partitions_are_matched_by_name() {
+        assert!(is_on_disk("/dev/sdb", "sdb"));
+        assert!(is_on_disk("/dev/sdb1", "sdb"));
+        assert!(is_on_disk("/dev/sdb12", "sdb"));
+        assert!(!is_on_disk("/dev/sdbb1", "sdb"));
+        assert!(!is_on_disk("/dev/sdb1x", "sdb"));
+        assert!(!is_on_disk("sdb1", "sdb"));
+        assert!(!is_on_disk("/dev/sdc1", "sdb"));
+    }
+
+    #[test]
+    fn disks_ending_in_a_digit_need_the_p_separator() {
+        assert!(is_on_disk("/dev/nvme0n1", "nvme0n1"));
+        assert!(is_on_disk("/dev/nvme0n1p2", "nvme0n1"));
+        assert!(is_on_disk("/dev/mmcblk0p1", "mmcblk0"));
+        assert!(!is_on_disk("/dev/nvme0n12", "nvme0n1"));
+        assert!(!is_on_disk("/dev/nvme0n1p", "nvme0n1"));
+        assert!(!is_on_disk("/dev/sdbp1", "sdb"));
+    }
+
+    #[test]
+    fn octal_escapes_are_undone() {
+        assert_eq!(unescape("/media/USB\\040DISK"), "/media/USB DISK");
+        assert_eq!(unescape("a\\011b\\134c\\012"), "a\tb\\c\n");
+        assert_eq!(unescape("trailing\\04"), "trailing\\04");
+        assert_eq!(unescape("not\\089octal"), "not\\089octal");
+
+        let entries = parse_mounts(MOUNTS);
+        assert_eq!(entries[2], MountEntry { source: "/dev/sdb2".to_string(), target: "/media/alice/tab\tand\\slash".to_string() });
+    }
+
+    #[test]
+    fn mount_points_are_listed_once_in_table_order() {
+        assert_eq!(mount_points_in(MOUNTS, &disks(&["sdb"])), vec!["/media/alice/USB DISK", "/media/alice/tab\tand\\slash", "/mnt/bind"]);
+        assert_eq!(mount_points_in(MOUNTS, &disks(&["nvme0n1", "sdbb"])), vec!["/", "/media/alice/other"]);
+        assert!(mount_points_in(MOUNTS, &disks(&["sdc"])).is_empty());
+        assert!(mount_points_in(MOUNTS, &[]).is_empty());
+    }
+}
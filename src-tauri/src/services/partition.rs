@@ -0,0 +1,659 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use chrono::Utc;
+use crate::models::{AnomalyFinding, DiskLayout, Partition, PartitionAnomaly, TableKind};
+use crate::services::encryption::{filesystem_signature, read_at, HEADER_LEN};
+
+/// Logical sector size assumed for partition tables (4Kn USB disks are rare)
+pub const SECTOR_SIZE: u64 = 512;
+
+// Bounds so a corrupt or hostile table can't make us read forever
+const MAX_GPT_ENTRIES: u32 = 256;
+// The spec allows 128 * 2^n byte entries; nothing real uses more than 128
+const MAX_GPT_ENTRY_SIZE: usize = 4096;
+const MAX_LOGICAL_PARTITIONS: usize = 128;
+
+const MBR_PROTECTIVE_GPT: u8 = 0xEE;
+const MBR_EXTENDED_TYPES: [u8; 3] = [0x05, 0x0F, 0x85];
+// Hidden FAT/NTFS variants (0x1x) and the Windows recovery type
+const MBR_HIDDEN_TYPES: [u8; 8] = [0x11, 0x14, 0x16, 0x17, 0x1B, 0x1C, 0x1E, 0x27];
+// Solaris x86 type byte, also what U3-style "virtual CD" drives hand to their ISO partition
+const MBR_ISO_TYPE: u8 = 0x96;
+// GPT attribute bit 62: Microsoft basic data "hidden"
+const GPT_ATTR_HIDDEN: u64 = 1 << 62;
+// Unused space at the end of a partition that's worth reporting
+const SLACK_THRESHOLD: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionTable {
+    pub kind: TableKind,
+    pub partitions: Vec<Partition>,
+}
+
+fn u32_le(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_le(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+fn mbr_type_name(kind: u8) -> Option<&'static str> {
+    Some(match kind {
+        0x01 | 0x04 | 0x06 | 0x0E => "FAT",
+        0x07 => "NTFS/exFAT",
+        0x0B | 0x0C => "FAT32",
+        0x11 | 0x14 | 0x16 | 0x1E => "Hidden FAT",
+        0x17 => "Hidden NTFS/exFAT",
+        0x1B | 0x1C => "Hidden FAT32",
+        0x27 => "Windows recovery",
+        0x82 => "Linux swap",
+        0x83 => "Linux",
+        0x8E => "Linux LVM",
+        0x96 => "ISO 9660",
+        0xA5 | 0xA6 | 0xA9 => "BSD",
+        0xAF => "HFS+",
+        0xEF => "EFI system",
+        0xFD => "Linux RAID",
+        _ => return None,
+    })
+}
+
+fn gpt_type_name(guid: &str) -> Option<&'static str> {
+    Some(match guid {
+        "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" => "EFI system",
+        "E3C9E316-0B5C-4DB8-817D-F92DF00215AE" => "Microsoft reserved",
+        "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7" => "Basic data",
+        "DE94BBA4-06D1-4D40-A16A-BFD50179D6AC" => "Windows recovery",
+        "0FC63DAF-8483-4772-8E79-3D69D8477DE4" => "Linux",
+        "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" => "Linux swap",
+        "E6D6D379-F507-44C2-A23C-238F2A3DF928" => "Linux LVM",
+        "CA7D7CCB-63ED-4C53-861C-1742536059CC" => "LUKS",
+        "48465300-0000-11AA-AA11-00306543ECAC" => "HFS+",
+        "7C3457EF-0000-11AA-AA11-00306543ECAC" => "APFS",
+        _ => return None,
+    })
+}
+
+/// Size in bytes the filesystem's boot sector claims, for the formats that record one
+pub fn filesystem_size(header: &[u8], filesystem: &str) -> Option<u64> {
+    if header.len() < SECTOR_SIZE as usize {
+        return None;
+    }
+    let bytes_per_sector = u16::from_le_bytes([header[11], header[12]]) as u64;
+    match filesystem {
+        "FAT" | "FAT32" => {
+            let small = u16::from_le_bytes([header[19], header[20]]) as u64;
+            let sectors = if small != 0 { small } else { u32_le(header, 32) as u64 };
+            Some(sectors.saturating_mul(bytes_per_sector))
+        }
+        // NTFS doesn't count the backup boot sector at the very end
+        "NTFS" => Some(u64_le(header, 0x28).saturating_add(1).saturating_mul(bytes_per_sector)),
+        "exFAT" => {
+            let shift = header[108];
+            (shift < 32).then(|| u64_le(header, 72).saturating_mul(1 << shift))
+        }
+        _ => None,
+    }
+}
+
+/// Whether sector 0 is a volume boot record rather than a partitioned MBR
+fn is_boot_record(sector: &[u8]) -> bool {
+    let oem = &sector[3..11];
+    oem == b"NTFS    " || oem == b"EXFAT   " || oem == b"-FVE-FS-" || &sector[82..87] == b"FAT32" || &sector[54..57] == b"FAT"
+}
+
+/// Formats a GPT GUID (first three fields are little-endian)
+pub fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        u32_le(bytes, 0),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]
+    )
+}
+
+/// Reads the partition table of a disk or disk image
+pub fn read_table<R: Read + Seek>(reader: &mut R) -> io::Result<PartitionTable> {
+    let mbr = read_at(reader, 0, SECTOR_SIZE as usize)?;
+    let none = PartitionTable { kind: TableKind::None, partitions: Vec::new() };
+    if mbr.len() < SECTOR_SIZE as usize || mbr[510..512] != [0x55, 0xAA] || is_boot_record(&mbr) {
+        return Ok(none);
+    }
+
+    let entries: Vec<&[u8]> = (0..4).map(|i| &mbr[446 + i * 16..446 + (i + 1) * 16]).collect();
+    if entries.iter().any(|e| e[4] == MBR_PROTECTIVE_GPT) {
+        if let Some(table) = read_gpt(reader)? {
+            return Ok(table);
+        }
+    }
+
+    let mut partitions = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let kind = entry[4];
+        let start = u32_le(entry, 8) as u64;
+        let sectors = u32_le(entry, 12) as u64;
+        if kind == 0 || sectors == 0 {
+            continue;
+        }
+        if MBR_EXTENDED_TYPES.contains(&kind) {
+            read_logical(reader, start, &mut partitions)?;
+            continue;
+        }
+        partitions.push(Partition {
+            index: i + 1,
+            start: start * SECTOR_SIZE,
+            size: sectors * SECTOR_SIZE,
+            type_id: format!("0x{:02X}", kind),
+            type_name: mbr_type_name(kind).map(String::from),
+            name: None,
+            attributes: 0,
+            filesystem: None,
+        });
+    }
+
+    // Primaries keep slots 1-4; logical partitions are numbered from 5
+    for (index, partition) in (5..).zip(partitions.iter_mut().filter(|p| p.index == 0)) {
+        partition.index = index;
+    }
+    partitions.sort_by_key(|p| p.index);
+
+    Ok(PartitionTable { kind: TableKind::Mbr, partitions })
+}
+
+/// Follows the chain of extended boot records starting at `extended_start` (in sectors)
+fn read_logical<R: Read + Seek>(reader: &mut R, extended_start: u64, out: &mut Vec<Partition>) -> io::Result<()> {
+    let mut ebr_start = extended_start;
+    for _ in 0..MAX_LOGICAL_PARTITIONS {
+        let ebr = read_at(reader, ebr_start * SECTOR_SIZE, SECTOR_SIZE as usize)?;
+        if ebr.len() < SECTOR_SIZE as usize || ebr[510..512] != [0x55, 0xAA] {
+            break;
+        }
+
+        let logical = &ebr[446..462];
+        let sectors = u32_le(logical, 12) as u64;
+        if logical[4] != 0 && sectors > 0 {
+            out.push(Partition {
+                index: 0,
+                start: (ebr_start + u32_le(logical, 8) as u64) * SECTOR_SIZE,
+                size: sectors * SECTOR_SIZE,
+                type_id: format!("0x{:02X}", logical[4]),
+                type_name: mbr_type_name(logical[4]).map(String::from),
+                name: None,
+                attributes: 0,
+                filesystem: None,
+            });
+        }
+
+        let next = &ebr[462..478];
+        let offset = u32_le(next, 8) as u64;
+        if next[4] == 0 || offset == 0 {
+            break;
+        }
+        ebr_start = extended_start + offset;
+    }
+    Ok(())
+}
+
+fn read_gpt<R: Read + Seek>(reader: &mut R) -> io::Result<Option<PartitionTable>> {
+    let header = read_at(reader, SECTOR_SIZE, SECTOR_SIZE as usize)?;
+    if header.len() < 92 || &header[0..8] != b"EFI PART" {
+        return Ok(None);
+    }
+
+    let entries_lba = u64_le(&header, 72);
+    let count = u32_le(&header, 80).min(MAX_GPT_ENTRIES);
+    let entry_size = u32_le(&header, 84) as usize;
+    if !(128..=MAX_GPT_ENTRY_SIZE).contains(&entry_size) || !entry_size.is_power_of_two() {
+        return Ok(None);
+    }
+    let (Some(table_offset), Some(table_len)) =
+        (entries_lba.checked_mul(SECTOR_SIZE), (count as usize).checked_mul(entry_size))
+    else {
+        return Ok(None);
+    };
+
+    let table = read_at(reader, table_offset, table_len)?;
+    let partitions = table
+        .chunks_exact(entry_size)
+        .enumerate()
+        .filter(|(_, entry)| entry[0..16].iter().any(|&b| b != 0))
+        .filter_map(|(i, entry)| {
+            let first = u64_le(entry, 32);
+            let last = u64_le(entry, 40);
+            if last < first {
+                return None;
+            }
+            let name: Vec<u16> = entry[56..128]
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .take_while(|&c| c != 0)
+                .collect();
+            let name = String::from_utf16_lossy(&name);
+            let type_id = format_guid(&entry[0..16]);
+            Some(Partition {
+                index: i + 1,
+                start: first.saturating_mul(SECTOR_SIZE),
+                size: (last - first).saturating_add(1).saturating_mul(SECTOR_SIZE),
+                type_name: gpt_type_name(&type_id).map(String::from),
+                type_id,
+                name: (!name.is_empty()).then_some(name),
+                attributes: u64_le(entry, 48),
+                filesystem: None,
+            })
+        })
+        .collect();
+
+    Ok(Some(PartitionTable { kind: TableKind::Gpt, partitions }))
+}
+
+fn finding(anomaly: PartitionAnomaly, partition: Option<usize>, detail: String) -> AnomalyFinding {
+    AnomalyFinding { anomaly, partition, detail }
+}
+
+/// Checks one volume's own layout: hidden flags, disk bounds, filesystem size and ISO images
+fn volume_anomalies(
+    partition: Option<&Partition>,
+    filesystem: Option<&str>,
+    fs_size: Option<u64>,
+    region_size: Option<u64>,
+    disk_size: Option<u64>,
+) -> Vec<AnomalyFinding> {
+    let index = partition.map(|p| p.index);
+    let label = index.map_or_else(|| "Disk".to_string(), |i| format!("Partition {}", i));
+    let mut findings = Vec::new();
+
+    if let Some(p) = partition {
+        let mbr_kind = p.type_id.strip_prefix("0x").and_then(|t| u8::from_str_radix(t, 16).ok());
+        if mbr_kind.is_some_and(|k| MBR_HIDDEN_TYPES.contains(&k)) || p.attributes & GPT_ATTR_HIDDEN != 0 {
+            findings.push(finding(
+                PartitionAnomaly::HiddenPartition,
+                index,
+                format!("{} is marked hidden ({})", label, p.type_name.as_deref().unwrap_or(&p.type_id)),
+            ));
+        }
+        if let Some(disk) = disk_size.filter(|&d| p.start.saturating_add(p.size) > d) {
+            findings.push(finding(
+                PartitionAnomaly::BeyondDiskEnd,
+                index,
+                format!("{} ends at byte {} but the disk is {} bytes", label, p.start.saturating_add(p.size), disk),
+            ));
+        }
+        if mbr_kind == Some(MBR_ISO_TYPE) && filesystem != Some("ISO 9660") {
+            findings.push(finding(
+                PartitionAnomaly::CdromEmulation,
+                index,
+                format!("{} has the ISO 9660 partition type", label),
+            ));
+        }
+    }
+
+    if filesystem == Some("ISO 9660") {
+        findings.push(finding(
+            PartitionAnomaly::CdromEmulation,
+            index,
+            format!("{} holds an ISO 9660 (CD-ROM) image", label),
+        ));
+    }
+
+    if let (Some(fs), Some(region)) = (fs_size, region_size) {
+        let name = filesystem.unwrap_or("filesystem");
+        if fs > region {
+            findings.push(finding(
+                PartitionAnomaly::FilesystemSizeMismatch,
+                index,
+                format!("{} {} claims {} bytes but the volume is {} bytes", label, name, fs, region),
+            ));
+        } else if region - fs > SLACK_THRESHOLD {
+            findings.push(finding(
+                PartitionAnomaly::FilesystemSizeMismatch,
+                index,
+                format!("{} has {} bytes after the end of its {} filesystem", label, region - fs, name),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// Reports every pair of partitions that share sectors
+fn overlaps(partitions: &[Partition]) -> Vec<AnomalyFinding> {
+    let mut findings = Vec::new();
+    for (i, a) in partitions.iter().enumerate() {
+        for b in &partitions[i + 1..] {
+            if a.start < b.start.saturating_add(b.size) && b.start < a.start.saturating_add(a.size) {
+                findings.push(finding(
+                    PartitionAnomaly::OverlappingPartitions,
+                    Some(b.index),
+                    format!("Partitions {} and {} overlap", a.index, b.index),
+                ));
+            }
+        }
+    }
+    findings
+}
+
+/// Reads the partition table and the filesystem of each volume, flagging layouts that can hide data
+pub fn inspect_layout<R: Read + Seek>(reader: &mut R, path: &str) -> io::Result<DiskLayout> {
+    let size = reader.seek(SeekFrom::End(0)).ok().filter(|&s| s > 0);
+    let table = read_table(reader)?;
+    let mut partitions = table.partitions;
+    let mut anomalies = Vec::new();
+
+    if table.kind == TableKind::None {
+        let header = read_at(reader, 0, HEADER_LEN)?;
+        let filesystem = filesystem_signature(&header);
+        let fs_size = filesystem.and_then(|fs| filesystem_size(&header, fs));
+        anomalies.extend(volume_anomalies(None, filesystem, fs_size, size, size));
+    }
+
+    for partition in &mut partitions {
+        let header = read_at(reader, partition.start, HEADER_LEN)?;
+        let filesystem = filesystem_signature(&header);
+        let fs_size = filesystem.and_then(|fs| filesystem_size(&header, fs));
+        partition.filesystem = filesystem.map(String::from);
+        anomalies.extend(volume_anomalies(Some(partition), filesystem, fs_size, Some(partition.size), size));
+    }
+    anomalies.extend(overlaps(&partitions));
+
+    Ok(DiskLayout {
+        path: path.to_string(),
+        inspected_at: Utc::now().to_rfc3339(),
+        size,
+        table: table.kind,
+        partitions,
+        anomalies,
+    })
+}
+
+/// Inspects the layout of a block device (`/dev/sdb`, `\\.\PhysicalDrive2`) or a disk image file
+pub fn inspect_layout_path(path: &Path) -> io::Result<DiskLayout> {
+    let mut file = File::open(path)?;
+    inspect_layout(&mut file, &path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const SECTOR: usize = SECTOR_SIZE as usize;
+    const BASIC_DATA: &str = "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7";
+    const LINUX: &str = "0FC63DAF-8483-4772-8E79-3D69D8477DE4";
+
+    fn blank_disk(sectors: usize) -> Vec<u8> {
+        vec![0u8; sectors * SECTOR]
+    }
+
+    fn put_u32(disk: &mut [u8], offset: usize, value: u32) {
+        disk[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u64(disk: &mut [u8], offset: usize, value: u64) {
+        disk[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a partition entry into the MBR or EBR at `sector` and marks the sector valid
+    fn mbr_entry(disk: &mut [u8], sector: usize, slot: usize, kind: u8, start: u32, sectors: u32) {
+        let base = sector * SECTOR;
+        let entry = base + 446 + slot * 16;
+        disk[entry + 4] = kind;
+        put_u32(disk, entry + 8, start);
+        put_u32(disk, entry + 12, sectors);
+        disk[base + 510..base + 512].copy_from_slice(&[0x55, 0xAA]);
+    }
+
+    /// Writes a FAT32 boot sector claiming `sectors` sectors
+    fn fat32(disk: &mut [u8], sector: usize, sectors: u32) {
+        let base = sector * SECTOR;
+        disk[base..base + 3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        disk[base + 3..base + 11].copy_from_slice(b"MSDOS5.0");
+        disk[base + 11..base + 13].copy_from_slice(&512u16.to_le_bytes());
+        put_u32(disk, base + 32, sectors);
+        disk[base + 82..base + 87].copy_from_slice(b"FAT32");
+        disk[base + 510..base + 512].copy_from_slice(&[0x55, 0xAA]);
+    }
+
+    fn guid_bytes(guid: &str) -> [u8; 16] {
+        let hex: String = guid.chars().filter(|c| *c != '-').collect();
+        let mut bytes: [u8; 16] = std::array::from_fn(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap());
+        bytes[0..4].reverse();
+        bytes[4..6].reverse();
+        bytes[6..8].reverse();
+        bytes
+    }
+
+    struct GptEntry<'a> {
+        type_guid: &'a str,
+        first: u64,
+        last: u64,
+        attributes: u64,
+        name: &'a str,
+    }
+
+    /// Writes a protective MBR, a GPT header at LBA 1 and the entry array at LBA 2
+    fn gpt(disk: &mut [u8], entry_size: u32, entries: &[GptEntry]) {
+        mbr_entry(disk, 0, 0, MBR_PROTECTIVE_GPT, 1, (disk.len() / SECTOR - 1) as u32);
+        let header = SECTOR;
+        disk[header..header + 8].copy_from_slice(b"EFI PART");
+        put_u64(disk, header + 72, 2);
+        put_u32(disk, header + 80, entries.len() as u32);
+        put_u32(disk, header + 84, entry_size);
+
+        for (i, e) in entries.iter().enumerate() {
+            let at = 2 * SECTOR + i * entry_size as usize;
+            disk[at..at + 16].copy_from_slice(&guid_bytes(e.type_guid));
+            disk[at + 16] = i as u8 + 1;
+            put_u64(disk, at + 32, e.first);
+            put_u64(disk, at + 40, e.last);
+            put_u64(disk, at + 48, e.attributes);
+            for (j, unit) in e.name.encode_utf16().enumerate() {
+                disk[at + 56 + j * 2..at + 58 + j * 2].copy_from_slice(&unit.to_le_bytes());
+            }
+        }
+    }
+
+    fn layout(disk: Vec<u8>) -> DiskLayout {
+        inspect_layout(&mut Cursor::new(disk), "disk.img").unwrap()
+    }
+
+    fn anomalies(layout: &DiskLayout) -> Vec<(PartitionAnomaly, Option<usize>)> {
+        layout.anomalies.iter().map(|a| (a.anomaly, a.partition)).collect()
+    }
+
+    fn extents(table: &PartitionTable) -> Vec<(usize, u64, u64, &str)> {
+        table.partitions.iter().map(|p| (p.index, p.start / SECTOR_SIZE, p.size / SECTOR_SIZE, p.type_id.as_str())).collect()
+    }
+
+    #[test]
+    fn guids_are_formatted_mixed_endian() {
+        assert_eq!(format_guid(&guid_bytes(BASIC_DATA)), BASIC_DATA);
+    }
+
+    #[test]
+    fn mbr_primaries_are_read() {
+        let mut disk = blank_disk(4096);
+        mbr_entry(&mut disk, 0, 0, 0x0C, 64, 2048);
+        mbr_entry(&mut disk, 0, 2, 0x83, 2112, 1984);
+        fat32(&mut disk, 64, 2048);
+
+        let table = read_table(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(table.kind, TableKind::Mbr);
+        assert_eq!(extents(&table), vec![(1, 64, 2048, "0x0C"), (3, 2112, 1984, "0x83")]);
+        assert_eq!(table.partitions[0].type_name.as_deref(), Some("FAT32"));
+
+        let layout = layout(disk);
+        assert_eq!(layout.partitions[0].filesystem.as_deref(), Some("FAT32"));
+        assert_eq!(layout.partitions[1].filesystem, None);
+        assert!(layout.anomalies.is_empty());
+    }
+
+    #[test]
+    fn ebr_chain_yields_logical_partitions_from_five() {
+        let mut disk = blank_disk(4096);
+        mbr_entry(&mut disk, 0, 0, 0x0C, 64, 960);
+        mbr_entry(&mut disk, 0, 1, 0x0F, 1024, 3072);
+        // Each EBR: the logical partition relative to itself, the next EBR relative to the extended start
+        mbr_entry(&mut disk, 1024, 0, 0x83, 64, 960);
+        mbr_entry(&mut disk, 1024, 1, 0x05, 1024, 2048);
+        mbr_entry(&mut disk, 2048, 0, 0x07, 64, 1984);
+
+        let table = read_table(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(extents(&table), vec![(1, 64, 960, "0x0C"), (5, 1088, 960, "0x83"), (6, 2112, 1984, "0x07")]);
+    }
+
+    #[test]
+    fn looping_ebr_chain_is_bounded() {
+        let mut disk = blank_disk(2048);
+        mbr_entry(&mut disk, 0, 0, 0x05, 1024, 1024);
+        mbr_entry(&mut disk, 1024, 0, 0x83, 64, 64);
+        mbr_entry(&mut disk, 1024, 1, 0x05, 512, 512);
+        // The second EBR links back to itself
+        mbr_entry(&mut disk, 1536, 0, 0x83, 64, 64);
+        mbr_entry(&mut disk, 1536, 1, 0x05, 512, 512);
+
+        let table = read_table(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(table.partitions.len(), MAX_LOGICAL_PARTITIONS);
+        assert_eq!(table.partitions[0].start, 1088 * SECTOR_SIZE);
+        assert!(table.partitions[1..].iter().all(|p| p.start == 1600 * SECTOR_SIZE));
+    }
+
+    #[test]
+    fn gpt_entries_are_read() {
+        let mut disk = blank_disk(4096);
+        gpt(&mut disk, 128, &[
+            GptEntry { type_guid: BASIC_DATA, first: 64, last: 2111, attributes: 0, name: "Data" },
+            GptEntry { type_guid: LINUX, first: 2112, last: 4031, attributes: 0, name: "" },
+        ]);
+        fat32(&mut disk, 64, 2048);
+
+        let layout = layout(disk);
+        assert_eq!(layout.table, TableKind::Gpt);
+        assert_eq!(layout.partitions.len(), 2);
+        let data = &layout.partitions[0];
+        assert_eq!((data.index, data.start, data.size), (1, 64 * SECTOR_SIZE, 2048 * SECTOR_SIZE));
+        assert_eq!(data.type_id, BASIC_DATA);
+        assert_eq!(data.type_name.as_deref(), Some("Basic data"));
+        assert_eq!(data.name.as_deref(), Some("Data"));
+        assert_eq!(data.filesystem.as_deref(), Some("FAT32"));
+        assert_eq!(layout.partitions[1].type_name.as_deref(), Some("Linux"));
+        assert_eq!(layout.partitions[1].name, None);
+        assert!(layout.anomalies.is_empty());
+    }
+
+    #[test]
+    fn larger_gpt_entries_are_supported() {
+        let mut disk = blank_disk(4096);
+        gpt(&mut disk, 256, &[
+            GptEntry { type_guid: LINUX, first: 64, last: 127, attributes: 0, name: "a" },
+            GptEntry { type_guid: LINUX, first: 128, last: 191, attributes: 0, name: "b" },
+        ]);
+
+        let table = read_table(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(table.kind, TableKind::Gpt);
+        assert_eq!(extents(&table), vec![(1, 64, 64, LINUX), (2, 128, 64, LINUX)]);
+    }
+
+    #[test]
+    fn malformed_gpt_entry_size_falls_back_to_the_mbr() {
+        for entry_size in [0x8000_0000, u32::MAX, 8192, 200, 64] {
+            let mut disk = blank_disk(4096);
+            gpt(&mut disk, 128, &[GptEntry { type_guid: LINUX, first: 64, last: 127, attributes: 0, name: "" }]);
+            put_u32(&mut disk, SECTOR + 84, entry_size);
+            put_u32(&mut disk, SECTOR + 80, u32::MAX);
+
+            let table = read_table(&mut Cursor::new(disk)).unwrap();
+            assert_eq!(table.kind, TableKind::Mbr, "entry size {}", entry_size);
+            assert_eq!(extents(&table), vec![(1, 1, 4095, "0xEE")]);
+        }
+    }
+
+    #[test]
+    fn hidden_partitions_are_flagged() {
+        let mut disk = blank_disk(4096);
+        mbr_entry(&mut disk, 0, 0, 0x0C, 64, 1024);
+        mbr_entry(&mut disk, 0, 1, 0x1C, 1088, 1024);
+        assert_eq!(anomalies(&layout(disk)), vec![(PartitionAnomaly::HiddenPartition, Some(2))]);
+
+        let mut disk = blank_disk(4096);
+        gpt(&mut disk, 128, &[GptEntry { type_guid: BASIC_DATA, first: 64, last: 1087, attributes: GPT_ATTR_HIDDEN, name: "" }]);
+        assert_eq!(anomalies(&layout(disk)), vec![(PartitionAnomaly::HiddenPartition, Some(1))]);
+    }
+
+    #[test]
+    fn partition_past_the_end_of_the_disk_is_flagged() {
+        let mut disk = blank_disk(2048);
+        mbr_entry(&mut disk, 0, 0, 0x83, 64, 1_000_000);
+
+        let layout = layout(disk);
+        assert_eq!(layout.size, Some(2048 * SECTOR_SIZE));
+        assert_eq!(anomalies(&layout), vec![(PartitionAnomaly::BeyondDiskEnd, Some(1))]);
+    }
+
+    #[test]
+    fn overlapping_partitions_are_flagged() {
+        let mut disk = blank_disk(4096);
+        mbr_entry(&mut disk, 0, 0, 0x83, 64, 2048);
+        mbr_entry(&mut disk, 0, 1, 0x83, 1024, 1024);
+        mbr_entry(&mut disk, 0, 2, 0x83, 2112, 1024);
+
+        let layout = layout(disk);
+        assert_eq!(anomalies(&layout), vec![(PartitionAnomaly::OverlappingPartitions, Some(2))]);
+        assert_eq!(layout.anomalies[0].detail, "Partitions 1 and 2 overlap");
+    }
+
+    #[test]
+    fn filesystem_larger_or_much_smaller_than_its_partition_is_flagged() {
+        let mut disk = blank_disk(8192);
+        mbr_entry(&mut disk, 0, 0, 0x0C, 64, 1024);
+        fat32(&mut disk, 64, 4096);
+        mbr_entry(&mut disk, 0, 1, 0x0C, 1088, 6144);
+        fat32(&mut disk, 1088, 2048);
+        // Within the slack allowance
+        mbr_entry(&mut disk, 0, 2, 0x0C, 7232, 960);
+        fat32(&mut disk, 7232, 900);
+
+        let layout = layout(disk);
+        assert_eq!(anomalies(&layout), vec![
+            (PartitionAnomaly::FilesystemSizeMismatch, Some(1)),
+            (PartitionAnomaly::FilesystemSizeMismatch, Some(2)),
+        ]);
+        assert!(layout.anomalies[0].detail.contains("claims 2097152 bytes"));
+        assert!(layout.anomalies[1].detail.contains("2097152 bytes after the end"));
+    }
+
+    #[test]
+    fn iso_images_are_flagged_as_cdrom_emulation() {
+        // An ISO 9660 image written straight to the disk
+        let mut disk = blank_disk(2048);
+        disk[32769..32774].copy_from_slice(b"CD001");
+        let whole = layout(disk);
+        assert_eq!(whole.table, TableKind::None);
+        assert_eq!(anomalies(&whole), vec![(PartitionAnomaly::CdromEmulation, None)]);
+
+        // A U3-style partition typed as ISO, with and without the image in it
+        let mut disk = blank_disk(4096);
+        mbr_entry(&mut disk, 0, 0, MBR_ISO_TYPE, 64, 1024);
+        disk[64 * SECTOR + 32769..64 * SECTOR + 32774].copy_from_slice(b"CD001");
+        mbr_entry(&mut disk, 0, 1, MBR_ISO_TYPE, 1088, 1024);
+
+        let layout = layout(disk);
+        assert_eq!(layout.partitions[0].filesystem.as_deref(), Some("ISO 9660"));
+        assert_eq!(anomalies(&layout), vec![(PartitionAnomaly::CdromEmulation, Some(1)), (PartitionAnomaly::CdromEmulation, Some(2))]);
+    }
+
+    #[test]
+    fn unpartitioned_filesystem_is_checked_against_the_disk() {
+        let mut disk = blank_disk(4096);
+        fat32(&mut disk, 0, 4096);
+        let fits = layout(disk);
+        assert_eq!(fits.table, TableKind::None);
+        assert!(fits.anomalies.is_empty());
+
+        let mut disk = blank_disk(4096);
+        fat32(&mut disk, 0, 8192);
+        assert_eq!(anomalies(&layout(disk)), vec![(PartitionAnomaly::FilesystemSizeMismatch, None)]);
+    }
+}
@@ -1,8 +1,12 @@
 use std::process::Command;
+#[cfg(windows)]
 use std::os::windows::process::CommandExt;
 use thiserror::Error;
+use crate::services::backend::{BackendError, SystemBackend};
+use crate::services::kernel_modules;
 
 // Windows flag to hide the console window
+#[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 #[derive(Error, Debug)]
@@ -11,24 +15,75 @@ pub enum PowerShellError {
     ExecutionFailed(String),
     #[error("PowerShell returned error: {0}")]
     ScriptError(String),
+    #[error("{0} is not available on this platform")]
+    Unsupported(String),
+}
+
+/// Builds the Windows PowerShell invocation (hidden, no visible window)
+#[cfg(windows)]
+fn command(script: &str) -> Result<Command, PowerShellError> {
+    let mut cmd = Command::new("powershell");
+    cmd.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-WindowStyle", "Hidden",
+        "-Command",
+        script,
+    ])
+    .creation_flags(CREATE_NO_WINDOW);
+    Ok(cmd)
+}
+
+/// Builds a PowerShell Core (`pwsh`) invocation when it is installed
+#[cfg(not(windows))]
+fn command(script: &str) -> Result<Command, PowerShellError> {
+    pwsh_command(find_pwsh(), script)
+}
+
+#[cfg(not(windows))]
+fn pwsh_command(pwsh: Option<std::path::PathBuf>, script: &str) -> Result<Command, PowerShellError> {
+    let pwsh = pwsh.ok_or_else(|| PowerShellError::Unsupported("PowerShell (pwsh)".to_string()))?;
+    let mut cmd = Command::new(pwsh);
+    cmd.args(["-NoProfile", "-NonInteractive", "-Command", script]);
+    Ok(cmd)
+}
+
+/// Looks up the `pwsh` executable on PATH
+#[cfg(not(windows))]
+fn find_pwsh() -> Option<std::path::PathBuf> {
+    find_pwsh_in(&std::env::var_os("PATH")?)
+}
+
+/// Looks up `pwsh` in the directories of a PATH-style list
+#[cfg(not(windows))]
+fn find_pwsh_in(path: &std::ffi::OsStr) -> Option<std::path::PathBuf> {
+    std::env::split_paths(path)
+        .map(|dir| dir.join("pwsh"))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Extracts the cmdlet name from a "term is not recognized" error, if that is what failed
+fn unrecognized_command(stderr: &str) -> Option<String> {
+    if !stderr.contains("is not recognized as") {
+        return None;
+    }
+    let start = stderr.find("The term '")? + "The term '".len();
+    let end = stderr[start..].find('\'')? + start;
+    Some(stderr[start..end].to_string())
 }
 
 /// Executes a PowerShell command and returns the output (hidden, no visible window)
 pub fn execute(script: &str) -> Result<String, PowerShellError> {
-    let output = Command::new("powershell")
-        .args([
-            "-NoProfile",
-            "-NonInteractive",
-            "-WindowStyle", "Hidden",
-            "-Command",
-            script,
-        ])
-        .creation_flags(CREATE_NO_WINDOW)
+    let output = command(script)?
         .output()
         .map_err(|e| PowerShellError::ExecutionFailed(e.to_string()))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
+        // Windows-only cmdlets (PnP, NetFirewall, CIM) are missing from pwsh on other platforms
+        if let Some(cmdlet) = unrecognized_command(&stderr) {
+            return Err(PowerShellError::Unsupported(cmdlet));
+        }
         return Err(PowerShellError::ScriptError(stderr.to_string()));
     }
 
@@ -36,24 +91,108 @@ pub fn execute(script: &str) -> Result<String, PowerShellError> {
     Ok(stdout.trim().to_string())
 }
 
-/// Gets all connected USB devices as JSON
+/// Escapes a value for use inside a single-quoted PowerShell string
+fn quote(value: &str) -> String {
+    value.replace("'", "''")
+}
+
+/// Gets connected devices of every externally attachable class (USB, disks, HID, cameras,
+/// Bluetooth, smart-card readers, portable devices, ...) with their parent as JSON
 pub fn get_usb_devices_json() -> Result<String, PowerShellError> {
     let script = r#"
-        Get-PnpDevice -Class 'USB' -Status 'OK' -ErrorAction SilentlyContinue | 
-        Select-Object @{N='instance_id';E={$_.InstanceId}}, 
-                      @{N='friendly_name';E={if($_.FriendlyName){$_.FriendlyName}else{'Unknown Device'}}},
-                      @{N='device_class';E={$_.Class}},
-                      @{N='status';E={$_.Status}} |
+        $externalClasses = @('USB', 'DiskDrive', 'CDROM', 'HIDClass', 'Keyboard', 'Mouse', 'Camera', 'Image', 'MEDIA',
+            'Bluetooth', 'SmartCardReader', 'WPD', 'Ports', 'Printer', 'Biometric')
+        # Devices on an external bus count whatever their class (USB network adapters, ...)
+        $externalBuses = '^(USB|USBSTOR|USBPRINT|BTHENUM|BTHLE|BTHLEDEVICE|THUNDERBOLT|SD|SWD\\WPDBUSENUM)\\'
+        # Devices on internal buses (ACPI, PCI, HD Audio) are dropped once their bus is resolved
+        Get-PnpDevice -Status 'OK' -ErrorAction SilentlyContinue |
+        Where-Object { ($_.InstanceId -match $externalBuses) -or ($_.Class -in $externalClasses -and $_.InstanceId -notmatch '^(ROOT|HTREE)\\') } |
+        ForEach-Object {
+            $dev = $_
+            $parent = (Get-PnpDeviceProperty -InstanceId $dev.InstanceId -KeyName 'DEVPKEY_Device_Parent' -ErrorAction SilentlyContinue).Data
+            $classes = @()
+            $interfaces = @()
+            if ($dev.InstanceId -match '^USB\\') {
+                # Interface classes come from the USB\Class_XX compatible IDs of the device and its children
+                $children = (Get-PnpDeviceProperty -InstanceId $dev.InstanceId -KeyName 'DEVPKEY_Device_Children' -ErrorAction SilentlyContinue).Data
+                $ids = @($dev.CompatibleID) + @($children | ForEach-Object { (Get-PnpDevice -InstanceId $_ -ErrorAction SilentlyContinue).CompatibleID })
+                $classes = @($ids | ForEach-Object { if ($_ -match '\\Class_([0-9A-Fa-f]{2})') { [Convert]::ToByte($matches[1], 16) } } | Select-Object -Unique)
+                $interfaces = @($ids | ForEach-Object { if ($_ -match '\\Class_([0-9A-Fa-f]{2})&SubClass_([0-9A-Fa-f]{2})&Prot_([0-9A-Fa-f]{2})') { "$($matches[1]):$($matches[2]):$($matches[3])".ToLower() } } | Select-Object -Unique)
+            }
+            [PSCustomObject]@{
+                instance_id = $dev.InstanceId
+                friendly_name = if ($dev.FriendlyName) { $dev.FriendlyName } else { 'Unknown Device' }
+                device_class = $dev.Class
+                status = $dev.Status
+                interface_classes = $classes
+                interfaces = $interfaces
+                parent_id = $parent
+            }
+        } |
         ConvertTo-Json -Compress
     "#;
     execute(script)
 }
 
+/// Script fragment that sets `$usbDisks` to the disks exposed by a USB device (or its interfaces)
+fn usb_disks_script(instance_id: &str) -> String {
+    format!(
+        r#"
+        function Get-Children($id) {{
+            (Get-PnpDeviceProperty -InstanceId $id -KeyName 'DEVPKEY_Device_Children' -ErrorAction SilentlyContinue).Data
+        }}
+        # Storage hangs off the device itself or, on composite devices, off an interface
+        $children = @(Get-Children '{}')
+        $children += @($children | ForEach-Object {{ Get-Children $_ }})
+        $disks = @(Get-Disk -ErrorAction SilentlyContinue)
+        # Disk paths embed the disk's instance ID with '\' replaced by '#'
+        $usbDisks = @($children | Where-Object {{ $_ }} | ForEach-Object {{
+            $key = $_.Replace('\', '#').ToLower()
+            $disks | Where-Object {{ $_.Path -and $_.Path.ToLower().Contains($key) }}
+        }} | Sort-Object Number -Unique)
+    "#,
+        quote(instance_id)
+    )
+}
+
+/// Gets the `\\.\PhysicalDriveN` disks exposed by a USB device and their partition offsets as JSON
+pub fn get_block_devices_json(instance_id: &str) -> Result<String, PowerShellError> {
+    let script = format!(
+        r#"{}
+        $result = @($usbDisks | ForEach-Object {{
+            $number = $_.Number
+            # Extended containers and the Microsoft reserved partition hold no filesystem
+            $volumes = @(Get-Partition -DiskNumber $number -ErrorAction SilentlyContinue |
+                Where-Object {{ $_.Type -notin @('Extended', 'Reserved') }} |
+                ForEach-Object {{ [pscustomobject]@{{ partition = [int]$_.PartitionNumber; offset = [uint64]$_.Offset }} }})
+            [pscustomobject]@{{ path = "\\.\PhysicalDrive$number"; volumes = $volumes }}
+        }})
+        ConvertTo-Json -InputObject $result -Depth 4 -Compress
+    "#,
+        usb_disks_script(instance_id)
+    );
+    execute(&script)
+}
+
+/// Gets the drive roots (`E:\`) of volumes on disks exposed by a USB device as JSON
+pub fn get_mount_points_json(instance_id: &str) -> Result<String, PowerShellError> {
+    let script = format!(
+        r#"{}
+        $roots = @($usbDisks | ForEach-Object {{ Get-Partition -DiskNumber $_.Number -ErrorAction SilentlyContinue }} |
+            Where-Object {{ $_.DriveLetter }} |
+            ForEach-Object {{ "$($_.DriveLetter):\" }})
+        ConvertTo-Json -InputObject $roots -Compress
+    "#,
+        usb_disks_script(instance_id)
+    );
+    execute(&script)
+}
+
 /// Disables a PnP device by instance ID (requires admin rights)
 pub fn disable_device(instance_id: &str) -> Result<(), PowerShellError> {
     let script = format!(
         r#"Disable-PnpDevice -InstanceId '{}' -Confirm:$false -ErrorAction Stop"#,
-        instance_id.replace("'", "''")
+        quote(instance_id)
     );
     execute(&script)?;
     Ok(())
@@ -63,8 +202,592 @@ pub fn disable_device(instance_id: &str) -> Result<(), PowerShellError> {
 pub fn enable_device(instance_id: &str) -> Result<(), PowerShellError> {
     let script = format!(
         r#"Enable-PnpDevice -InstanceId '{}' -Confirm:$false -ErrorAction Stop"#,
-        instance_id.replace("'", "''")
+        quote(instance_id)
     );
     execute(&script)?;
     Ok(())
 }
+
+/// Gets paired Bluetooth devices as JSON. Windows keeps one `BTHENUM\DEV_<address>` (classic)
+/// or `BTHLE\DEV_<address>` (Low Energy) node per paired device.
+pub fn get_bluetooth_devices_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        Get-PnpDevice -Class 'Bluetooth' -ErrorAction SilentlyContinue |
+        Where-Object { $_.InstanceId -match '^(BTHENUM|BTHLE)\\DEV_[0-9A-F]{12}' } |
+        ForEach-Object {
+            $dev = $_
+            $null = $dev.InstanceId -match 'DEV_([0-9A-F]{12})'
+            $address = ($matches[1] -split '(..)' -ne '') -join ':'
+            # DEVPKEY_Bluetooth_ClassOfDevice and DEVPKEY_Device_IsConnected
+            $class = (Get-PnpDeviceProperty -InstanceId $dev.InstanceId -KeyName '{2BD67D8B-8BEB-48D5-87E0-6CDA3428040A} 10' -ErrorAction SilentlyContinue).Data
+            $connected = (Get-PnpDeviceProperty -InstanceId $dev.InstanceId -KeyName '{83DA6326-97A6-4088-9453-A1923F573B29} 15' -ErrorAction SilentlyContinue).Data
+            [PSCustomObject]@{
+                address = $address
+                name = $dev.FriendlyName
+                alias = $dev.FriendlyName
+                class_of_device = $class
+                paired = $true
+                connected = [bool]$connected
+                # Problem code 22: the device node is disabled
+                blocked = $dev.ConfigManagerErrorCode -eq 22
+            }
+        } |
+        Sort-Object address -Unique |
+        ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Removes a Bluetooth pairing through `BluetoothRemoveDevice` (requires admin rights)
+pub fn unpair_bluetooth_device(address: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"
+        Add-Type -Namespace SentinelGuard -Name BluetoothApi -MemberDefinition '[DllImport("BluetoothApis.dll")] public static extern uint BluetoothRemoveDevice(ref ulong address);'
+        $address = [Convert]::ToUInt64('{}', 16)
+        $result = [SentinelGuard.BluetoothApi]::BluetoothRemoveDevice([ref]$address)
+        if ($result -ne 0) {{ throw "BluetoothRemoveDevice failed with error $result" }}
+    "#,
+        quote(&address.replace(':', ""))
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Disables (or re-enables) every PnP node of a Bluetooth device (requires admin rights)
+pub fn set_bluetooth_blocked(address: &str, blocked: bool) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"
+        $nodes = @(Get-PnpDevice -ErrorAction SilentlyContinue | Where-Object {{ $_.InstanceId -match '^(BTHENUM|BTHLE|BTHLEDEVICE)\\' -and $_.InstanceId -like '*{}*' }})
+        if ($nodes.Count -eq 0) {{ throw "Bluetooth device not found" }}
+        $nodes | {}-PnpDevice -Confirm:$false -ErrorAction Stop
+    "#,
+        quote(&address.replace(':', "")),
+        if blocked { "Disable" } else { "Enable" }
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Gets the running kernel drivers from `driverquery` as JSON
+pub fn get_kernel_modules_json() -> Result<String, PowerShellError> {
+    let output = execute("driverquery /v /fo csv")?;
+    let modules = kernel_modules::parse_driverquery_csv(&output);
+    serde_json::to_string(&modules).map_err(|e| PowerShellError::ExecutionFailed(e.to_string()))
+}
+
+/// Stops a kernel driver, which unloads it if the driver supports that (requires admin rights)
+pub fn unload_kernel_module(name: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"
+        $output = sc.exe stop '{}' 2>&1
+        if ($LASTEXITCODE -ne 0) {{ throw "sc.exe stop failed: $output" }}
+    "#,
+        quote(name)
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Gets OS, CPU, RAM, disk and uptime information as JSON
+pub fn get_system_info_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $os = Get-CimInstance Win32_OperatingSystem
+        $cpu = Get-CimInstance Win32_Processor | Select-Object -First 1
+        $cs = Get-CimInstance Win32_ComputerSystem
+        $disk = Get-CimInstance Win32_LogicalDisk -Filter "DeviceID='C:'"
+        $uptime = (Get-Date) - $os.LastBootUpTime
+        
+        @{
+            os_name = $os.Caption
+            os_version = $os.Version
+            os_build = $os.BuildNumber
+            computer_name = $cs.Name
+            username = $env:USERNAME
+            domain = if ($cs.Domain) { $cs.Domain } else { "WORKGROUP" }
+            total_ram_gb = [math]::Round($cs.TotalPhysicalMemory / 1GB, 2)
+            available_ram_gb = [math]::Round($os.FreePhysicalMemory / 1MB, 2)
+            cpu_name = $cpu.Name
+            cpu_cores = $cpu.NumberOfCores
+            cpu_threads = $cpu.NumberOfLogicalProcessors
+            uptime_hours = [math]::Round($uptime.TotalHours, 1)
+            disk_total_gb = [math]::Round($disk.Size / 1GB, 2)
+            disk_free_gb = [math]::Round($disk.FreeSpace / 1GB, 2)
+            last_boot = $os.LastBootUpTime.ToString("yyyy-MM-dd HH:mm")
+        } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets all startup programs as JSON
+pub fn get_startup_programs_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $startups = Get-CimInstance Win32_StartupCommand -ErrorAction SilentlyContinue
+        $result = @()
+        foreach ($s in $startups) {
+            $result += @{
+                name = if ($s.Name) { $s.Name } else { "Unknown" }
+                command = if ($s.Command) { $s.Command } else { "" }
+                location = if ($s.Location) { $s.Location } else { "" }
+                user = if ($s.User) { $s.User } else { "System" }
+            }
+        }
+        $result | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets physical network adapters that are up as JSON
+pub fn get_network_info_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $adapters = Get-NetAdapter -Physical -ErrorAction SilentlyContinue | Where-Object { $_.Status -eq 'Up' }
+        $result = @()
+        foreach ($adapter in $adapters) {
+            $ipConfig = Get-NetIPConfiguration -InterfaceIndex $adapter.ifIndex -ErrorAction SilentlyContinue
+            $ipAddress = ($ipConfig.IPv4Address.IPAddress | Select-Object -First 1)
+            $gateway = ($ipConfig.IPv4DefaultGateway.NextHop | Select-Object -First 1)
+            $dns = ($ipConfig.DNSServer.ServerAddresses -join ', ')
+            
+            $adapterDetails = Get-NetAdapter -InterfaceIndex $adapter.ifIndex -ErrorAction SilentlyContinue
+            $ipDetails = Get-NetIPAddress -InterfaceIndex $adapter.ifIndex -AddressFamily IPv4 -ErrorAction SilentlyContinue | Select-Object -First 1
+            
+            $result += @{
+                adapter_name = $adapter.Name
+                ip_address = if ($ipAddress) { $ipAddress } else { "N/A" }
+                subnet_mask = if ($ipDetails.PrefixLength) { "/$($ipDetails.PrefixLength)" } else { "N/A" }
+                gateway = if ($gateway) { $gateway } else { "N/A" }
+                dns_servers = if ($dns) { $dns } else { "N/A" }
+                mac_address = if ($adapter.MacAddress) { $adapter.MacAddress } else { "N/A" }
+                status = $adapter.Status
+            }
+        }
+        $result | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets the current WiFi connection (SSID and signal) as JSON, or "null"
+pub fn get_connected_wifi_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $wifi = netsh wlan show interfaces | Select-String -Pattern "^\s+SSID\s+:\s+(.+)$|^\s+Signal\s+:\s+(.+)$"
+        if ($wifi) {
+            $ssid = ""
+            $signal = ""
+            foreach ($line in $wifi) {
+                if ($line -match "SSID\s+:\s+(.+)") { $ssid = $matches[1].Trim() }
+                if ($line -match "Signal\s+:\s+(.+)") { $signal = $matches[1].Trim() }
+            }
+            if ($ssid) {
+                @{ ssid = $ssid; signal = $signal } | ConvertTo-Json -Compress
+            } else {
+                "null"
+            }
+        } else {
+            "null"
+        }
+    "#;
+    execute(script)
+}
+
+/// Gets all saved WiFi profiles (SSIDs only) as JSON
+pub fn get_wifi_profiles_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $profiles = netsh wlan show profiles | Select-String 'All User Profile\s*:\s*(.+)$' | ForEach-Object { $_.Matches.Groups[1].Value.Trim() }
+        $result = @()
+        foreach ($ssid in $profiles) {
+            $result += @{
+                ssid = $ssid
+                password = $null
+                authentication = ""
+                encryption = ""
+            }
+        }
+        $result | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets a single WiFi profile including its key as JSON
+pub fn get_wifi_password_json(ssid: &str) -> Result<String, PowerShellError> {
+    let escaped_ssid = ssid.replace("\"", "`\"");
+    let script = format!(
+        r#"
+        $output = netsh wlan show profile name="{}" key=clear
+        $auth = ($output | Select-String 'Authentication\s*:\s*(.+)$').Matches.Groups[1].Value.Trim()
+        $cipher = ($output | Select-String 'Cipher\s*:\s*(.+)$').Matches.Groups[1].Value.Trim()
+        $key = ($output | Select-String 'Key Content\s*:\s*(.+)$').Matches.Groups[1].Value.Trim()
+        @{{
+            ssid = "{}"
+            password = if ($key) {{ $key }} else {{ $null }}
+            authentication = if ($auth) {{ $auth }} else {{ "Unknown" }}
+            encryption = if ($cipher) {{ $cipher }} else {{ "Unknown" }}
+        }} | ConvertTo-Json -Compress
+    "#,
+        escaped_ssid, escaped_ssid
+    );
+    execute(&script)
+}
+
+/// Gets temp folder sizes and file counts as JSON
+pub fn get_temp_info_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $folders = @(
+            @{ path = $env:TEMP; name = "User Temp" },
+            @{ path = "C:\Windows\Temp"; name = "Windows Temp" },
+            @{ path = "$env:LOCALAPPDATA\Microsoft\Windows\INetCache"; name = "IE Cache" }
+        )
+        
+        $result = @()
+        foreach ($folder in $folders) {
+            if (Test-Path $folder.path) {
+                $files = Get-ChildItem -Path $folder.path -Recurse -File -ErrorAction SilentlyContinue
+                $size = ($files | Measure-Object -Property Length -Sum).Sum
+                $result += @{
+                    path = $folder.path
+                    size_mb = [math]::Round($size / 1MB, 2)
+                    file_count = $files.Count
+                }
+            }
+        }
+        $result | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Deletes temp files and returns the cleanup summary as JSON
+pub fn clean_temp_files_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $folders = @($env:TEMP, "C:\Windows\Temp")
+        $deletedCount = 0
+        $freedBytes = 0
+        $errors = @()
+        
+        foreach ($folder in $folders) {
+            if (Test-Path $folder) {
+                $files = Get-ChildItem -Path $folder -Recurse -File -ErrorAction SilentlyContinue
+                foreach ($file in $files) {
+                    try {
+                        $size = $file.Length
+                        Remove-Item -Path $file.FullName -Force -ErrorAction Stop
+                        $deletedCount++
+                        $freedBytes += $size
+                    } catch {
+                        # Skip locked files silently
+                    }
+                }
+                # Try to remove empty directories
+                Get-ChildItem -Path $folder -Recurse -Directory -ErrorAction SilentlyContinue | 
+                    Where-Object { (Get-ChildItem $_.FullName -ErrorAction SilentlyContinue).Count -eq 0 } |
+                    ForEach-Object { 
+                        try { Remove-Item $_.FullName -Force -ErrorAction Stop } catch {} 
+                    }
+            }
+        }
+        
+        @{
+            deleted_count = $deletedCount
+            freed_mb = [math]::Round($freedBytes / 1MB, 2)
+            errors = $errors
+        } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets firewall profile status as JSON
+pub fn get_firewall_status_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $profiles = Get-NetFirewallProfile -ErrorAction SilentlyContinue
+        @{
+            domain_enabled = ($profiles | Where-Object { $_.Name -eq 'Domain' }).Enabled
+            private_enabled = ($profiles | Where-Object { $_.Name -eq 'Private' }).Enabled
+            public_enabled = ($profiles | Where-Object { $_.Name -eq 'Public' }).Enabled
+        } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Gets firewall rules as JSON (SentinelGuard rules first, then the first 50 others)
+pub fn get_firewall_rules_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $sgRules = Get-NetFirewallRule -Description "SentinelGuard Managed Rule" -ErrorAction SilentlyContinue
+        $otherRules = Get-NetFirewallRule -ErrorAction SilentlyContinue | Select-Object -First 50
+        
+        $allRules = @($sgRules) + @($otherRules) | Select-Object -Unique -Property Name
+        
+        $allRules | ForEach-Object {
+            $portFilter = Get-NetFirewallPortFilter -AssociatedNetFirewallRule $_ -ErrorAction SilentlyContinue
+            @{
+                name = $_.DisplayName
+                enabled = $_.Enabled -eq 'True'
+                direction = $_.Direction.ToString()
+                action = $_.Action.ToString()
+                protocol = if ($portFilter.Protocol) { $portFilter.Protocol } else { "Any" }
+                local_port = if ($portFilter.LocalPort) { $portFilter.LocalPort -join ',' } else { "Any" }
+            }
+        } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Creates an inbound block rule for a port
+pub fn block_port(port: u16, protocol: &str, rule_name: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"New-NetFirewallRule -DisplayName '{}' -Description "SentinelGuard Managed Rule" -Direction Inbound -LocalPort {} -Protocol {} -Action Block -ErrorAction Stop"#,
+        quote(rule_name), port, protocol
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Removes a firewall rule by display name
+pub fn remove_firewall_rule(rule_name: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"Remove-NetFirewallRule -DisplayName '{}' -ErrorAction Stop"#,
+        quote(rule_name)
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Enables allowed/blocked connection logging on all firewall profiles
+pub fn enable_firewall_logging() -> Result<(), PowerShellError> {
+    let script = r#"Set-NetFirewallProfile -Profile Domain,Public,Private -LogAllowed True -LogBlocked True -ErrorAction Stop"#;
+    execute(script)?;
+    Ok(())
+}
+
+/// Gets the top 20 processes using more than 100 MB as JSON
+pub fn get_high_memory_processes_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        Get-Process -ErrorAction SilentlyContinue | 
+            Where-Object { $_.WorkingSet64 -gt 100MB } | 
+            Sort-Object WorkingSet64 -Descending |
+            Select-Object -First 20 |
+            ForEach-Object {
+                @{
+                    id = $_.Id
+                    name = $_.ProcessName
+                    cpu_percent = [math]::Round($_.CPU, 2)
+                    memory_mb = [math]::Round($_.WorkingSet64 / 1MB, 2)
+                    path = if ($_.Path) { $_.Path } else { "" }
+                }
+            } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Force-stops a process by ID
+pub fn kill_process(process_id: u32) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"Stop-Process -Id {} -Force -ErrorAction Stop"#,
+        process_id
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Gets the status of critical Windows services as JSON
+pub fn get_critical_services_json() -> Result<String, PowerShellError> {
+    let script = r#"
+        $criticalServices = @('wuauserv', 'bits', 'WinDefend', 'MpsSvc', 'EventLog', 'Spooler', 'W32Time')
+        Get-Service -Name $criticalServices -ErrorAction SilentlyContinue |
+            ForEach-Object {
+                @{
+                    name = $_.Name
+                    display_name = $_.DisplayName
+                    status = $_.Status.ToString()
+                    start_type = $_.StartType.ToString()
+                }
+            } | ConvertTo-Json -Compress
+    "#;
+    execute(script)
+}
+
+/// Restarts a service by name
+pub fn restart_service(service_name: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"Restart-Service -Name '{}' -Force -ErrorAction Stop"#,
+        quote(service_name)
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// Starts a stopped service by name
+pub fn start_service(service_name: &str) -> Result<(), PowerShellError> {
+    let script = format!(
+        r#"Start-Service -Name '{}' -ErrorAction Stop"#,
+        quote(service_name)
+    );
+    execute(&script)?;
+    Ok(())
+}
+
+/// System backend that runs the PowerShell scripts above
+pub struct PowerShellBackend;
+
+impl SystemBackend for PowerShellBackend {
+    fn usb_devices_json(&self) -> Result<String, BackendError> {
+        Ok(get_usb_devices_json()?)
+    }
+
+    fn disable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        Ok(disable_device(instance_id)?)
+    }
+
+    fn enable_device(&self, instance_id: &str) -> Result<(), BackendError> {
+        Ok(enable_device(instance_id)?)
+    }
+
+    fn set_default_deny(&self, _enabled: bool) -> Result<(), BackendError> {
+        Err(PowerShellError::Unsupported("USB default-deny".to_string()).into())
+    }
+
+    fn block_devices_json(&self, instance_id: &str) -> Result<String, BackendError> {
+        Ok(get_block_devices_json(instance_id)?)
+    }
+
+    fn mount_points_json(&self, instance_id: &str) -> Result<String, BackendError> {
+        Ok(get_mount_points_json(instance_id)?)
+    }
+
+    fn bluetooth_devices_json(&self) -> Result<String, BackendError> {
+        Ok(get_bluetooth_devices_json()?)
+    }
+
+    fn unpair_bluetooth_device(&self, address: &str) -> Result<(), BackendError> {
+        Ok(unpair_bluetooth_device(address)?)
+    }
+
+    fn set_bluetooth_blocked(&self, address: &str, blocked: bool) -> Result<(), BackendError> {
+        Ok(set_bluetooth_blocked(address, blocked)?)
+    }
+
+    fn kernel_modules_json(&self) -> Result<String, BackendError> {
+        Ok(get_kernel_modules_json()?)
+    }
+
+    fn unload_kernel_module(&self, name: &str) -> Result<(), BackendError> {
+        Ok(unload_kernel_module(name)?)
+    }
+
+    fn system_info_json(&self) -> Result<String, BackendError> {
+        Ok(get_system_info_json()?)
+    }
+
+    fn startup_programs_json(&self) -> Result<String, BackendError> {
+        Ok(get_startup_programs_json()?)
+    }
+
+    fn network_info_json(&self) -> Result<String, BackendError> {
+        Ok(get_network_info_json()?)
+    }
+
+    fn connected_wifi_json(&self) -> Result<String, BackendError> {
+        Ok(get_connected_wifi_json()?)
+    }
+
+    fn wifi_profiles_json(&self) -> Result<String, BackendError> {
+        Ok(get_wifi_profiles_json()?)
+    }
+
+    fn wifi_password_json(&self, ssid: &str) -> Result<String, BackendError> {
+        Ok(get_wifi_password_json(ssid)?)
+    }
+
+    fn temp_info_json(&self) -> Result<String, BackendError> {
+        Ok(get_temp_info_json()?)
+    }
+
+    fn clean_temp_files_json(&self) -> Result<String, BackendError> {
+        Ok(clean_temp_files_json()?)
+    }
+
+    fn firewall_status_json(&self) -> Result<String, BackendError> {
+        Ok(get_firewall_status_json()?)
+    }
+
+    fn firewall_rules_json(&self) -> Result<String, BackendError> {
+        Ok(get_firewall_rules_json()?)
+    }
+
+    fn block_port(&self, port: u16, protocol: &str, rule_name: &str) -> Result<(), BackendError> {
+        Ok(block_port(port, protocol, rule_name)?)
+    }
+
+    fn remove_firewall_rule(&self, rule_name: &str) -> Result<(), BackendError> {
+        Ok(remove_firewall_rule(rule_name)?)
+    }
+
+    fn enable_firewall_logging(&self) -> Result<(), BackendError> {
+        Ok(enable_firewall_logging()?)
+    }
+
+    fn high_memory_processes_json(&self) -> Result<String, BackendError> {
+        Ok(get_high_memory_processes_json()?)
+    }
+
+    fn kill_process(&self, process_id: u32) -> Result<(), BackendError> {
+        Ok(kill_process(process_id)?)
+    }
+
+    fn critical_services_json(&self) -> Result<String, BackendError> {
+        Ok(get_critical_services_json()?)
+    }
+
+    fn restart_service(&self, service_name: &str) -> Result<(), BackendError> {
+        Ok(restart_service(service_name)?)
+    }
+
+    fn start_service(&self, service_name: &str) -> Result<(), BackendError> {
+        Ok(start_service(service_name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrecognized_command_names_the_missing_cmdlet() {
+        let stderr = "Get-PnpDevice: The term 'Get-PnpDevice' is not recognized as a name of a cmdlet, function, script file, or executable program.";
+        assert_eq!(unrecognized_command(stderr).as_deref(), Some("Get-PnpDevice"));
+        assert_eq!(unrecognized_command("Access is denied."), None);
+    }
+
+    #[test]
+    fn quote_doubles_single_quotes() {
+        assert_eq!(quote("O'Brien's Wi-Fi"), "O''Brien''s Wi-Fi");
+    }
+
+    #[test]
+    fn unsupported_error_names_the_feature() {
+        let error = PowerShellError::Unsupported("Get-NetFirewallRule".to_string());
+        assert_eq!(error.to_string(), "Get-NetFirewallRule is not available on this platform");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn pwsh_is_looked_up_on_path() {
+        let empty = tempfile::tempdir().unwrap();
+        let with_pwsh = tempfile::tempdir().unwrap();
+        std::fs::write(with_pwsh.path().join("pwsh"), "").unwrap();
+        // A directory named pwsh isn't the executable
+        std::fs::create_dir(empty.path().join("pwsh")).unwrap();
+
+        assert_eq!(find_pwsh_in(empty.path().as_os_str()), None);
+        assert_eq!(find_pwsh_in(std::ffi::OsStr::new("")), None);
+        let path = std::env::join_paths([empty.path(), with_pwsh.path()]).unwrap();
+        assert_eq!(find_pwsh_in(&path), Some(with_pwsh.path().join("pwsh")));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn missing_pwsh_is_unsupported() {
+        let error = pwsh_command(None, "Get-Date").unwrap_err();
+        assert!(matches!(error, PowerShellError::Unsupported(ref what) if what.contains("pwsh")));
+
+        let cmd = pwsh_command(Some("/opt/pwsh".into()), "Get-Date").unwrap();
+        assert_eq!(cmd.get_program(), "/opt/pwsh");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), ["-NoProfile", "-NonInteractive", "-Command", "Get-Date"]);
+    }
+}
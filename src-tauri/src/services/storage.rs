@@ -13,10 +13,15 @@ pub enum StorageError {
     Json(#[from] serde_json::Error),
 }
 
+/// Environment variable overriding the application data directory (e.g. alongside fixtures)
+pub const DATA_DIR_ENV: &str = "SENTINELGUARD_DATA_DIR";
+
 /// Gets the application data directory (creates if doesn't exist)
 pub fn get_app_data_dir() -> Result<PathBuf, StorageError> {
-    let base_dir = dirs::data_dir().ok_or(StorageError::NoAppDataDir)?;
-    let app_dir = base_dir.join("SentinelGuard");
+    let app_dir = match std::env::var_os(DATA_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::data_dir().ok_or(StorageError::NoAppDataDir)?.join("SentinelGuard"),
+    };
     
     if !app_dir.exists() {
         fs::create_dir_all(&app_dir)?;
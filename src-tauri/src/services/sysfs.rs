@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use crate::models::DiskVolume;
+
+/// Default mount point of sysfs
+pub const DEFAULT_SYSFS_ROOT: &str = "/sys";
+
+/// A USB device read from `<root>/bus/usb/devices`
+#[derive(Debug, Clone, Serialize)]
+pub struct SysfsUsbDevice {
+    pub instance_id: String,
+    pub friendly_name: String,
+    pub device_class: String,
+    pub status: String,
+    /// Kernel device name, e.g. `1-2.3`
+    pub sysfs_name: String,
+    pub vendor_id: String,
+    pub product_id: String,
+    pub serial: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub class_code: u8,
+    pub interface_classes: Vec<u8>,
+    /// `class:subclass:protocol` of each interface, e.g. `08:06:50`
+    pub interfaces: Vec<String>,
+    pub authorized: bool,
+    pub bus_type: String,
+    /// Instance ID of the hub the device is plugged into (`None` on a root port)
+    pub parent_id: Option<String>,
+}
+
+/// Reads USB devices from a sysfs tree (the real `/sys` or a fake one for tests)
+#[derive(Debug, Clone)]
+pub struct SysfsUsb {
+    root: PathBuf,
+}
+
+impl Default for SysfsUsb {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYSFS_ROOT)
+    }
+}
+
+impl SysfsUsb {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory holding one entry per USB device and interface
+    pub fn devices_dir(&self) -> PathBuf {
+        self.root.join("bus").join("usb").join("devices")
+    }
+
+    /// Lists attached USB devices, skipping root hubs and interface entries
+    pub fn enumerate(&self) -> io::Result<Vec<SysfsUsbDevice>> {
+        let mut devices = Vec::new();
+
+        for entry in fs::read_dir(self.devices_dir())? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_root_hub(&name) || name.contains(':') {
+                continue;
+            }
+            if let Some(device) = read_device(&entry.path(), &name) {
+                devices.push(device);
+            }
+        }
+
+        devices.sort_by(|a, b| a.sysfs_name.cmp(&b.sysfs_name));
+
+        // `1-2.3` sits on port 3 of the hub `1-2`; `1-2` sits on a root hub, which isn't listed
+        let ids: HashMap<String, String> = devices
+            .iter()
+            .map(|d| (d.sysfs_name.clone(), d.instance_id.clone()))
+            .collect();
+        for device in &mut devices {
+            device.parent_id = device.sysfs_name
+                .rsplit_once('.')
+                .and_then(|(hub, _)| ids.get(hub).cloned());
+        }
+        Ok(devices)
+    }
+
+    /// Finds an attached device by its instance ID
+    pub fn find(&self, instance_id: &str) -> io::Result<SysfsUsbDevice> {
+        self.enumerate()?
+            .into_iter()
+            .find(|d| d.instance_id == instance_id)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("Device not found: {}", instance_id))
+            })
+    }
+
+    /// Kernel names (e.g. `sdb`) of the whole-disk block devices behind a USB device
+    pub fn block_devices(&self, instance_id: &str) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+
+        for entry in fs::read_dir(self.root.join("class").join("block"))? {
+            let entry = entry?;
+            // Partitions are inspected through their parent disk
+            if entry.path().join("partition").exists() {
+                continue;
+            }
+            let Ok(device_dir) = fs::canonicalize(entry.path().join("device")) else {
+                continue;
+            };
+            if owning_instance_id(&device_dir).as_deref() == Some(instance_id) {
+                names.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Partitions of a disk (e.g. `sdb`) as the kernel found them
+    pub fn partitions(&self, disk: &str) -> io::Result<Vec<DiskVolume>> {
+        let mut volumes = Vec::new();
+
+        for entry in fs::read_dir(self.root.join("class").join("block").join(disk))? {
+            let dir = entry?.path();
+            let Some(partition) = read_attr(&dir, "partition").and_then(|p| p.parse().ok()) else {
+                continue;
+            };
+            // `start` and `size` count 512-byte sectors whatever the disk's sector size
+            let start: u64 = read_attr(&dir, "start").and_then(|s| s.parse().ok()).unwrap_or(0);
+            let size: u64 = read_attr(&dir, "size").and_then(|s| s.parse().ok()).unwrap_or(0);
+            // An MBR extended partition shows up as a 1 KiB stub holding the EBR chain
+            if size <= 2 {
+                continue;
+            }
+            volumes.push(DiskVolume { partition, offset: start * 512 });
+        }
+
+        volumes.sort_by_key(|v| v.partition);
+        Ok(volumes)
+    }
+
+    /// Authorizes or deauthorizes a device through its `authorized` attribute (requires root)
+    pub fn set_authorized(&self, instance_id: &str, authorized: bool) -> io::Result<()> {
+        let device = self.find(instance_id)?;
+        let path = self.devices_dir().join(&device.sysfs_name).join("authorized");
+        fs::write(path, if authorized { "1" } else { "0" })
+    }
+
+    /// Sets `authorized_default` on every root hub so new devices start (de)authorized.
+    /// `false` gives default-deny: devices stay unconfigured until explicitly authorized.
+    pub fn set_authorized_default(&self, authorized: bool) -> io::Result<()> {
+        let mut hubs = 0;
+        for entry in fs::read_dir(self.devices_dir())? {
+            let entry = entry?;
+            if !is_root_hub(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+            fs::write(entry.path().join("authorized_default"), if authorized { "1" } else { "0" })?;
+            hubs += 1;
+        }
+
+        if hubs == 0 {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "No USB root hubs found"));
+        }
+        Ok(())
+    }
+}
+
+/// Root hubs are named `usbN`; ports are named `<bus>-<port>[.<port>...]`
+pub fn is_root_hub(name: &str) -> bool {
+    name.starts_with("usb")
+}
+
+/// Builds a Windows-style instance ID so the same stick keeps its ID across ports.
+/// Devices without a serial fall back to their port path, like Windows does.
+pub fn instance_id(vendor_id: &str, product_id: &str, serial: Option<&str>, sysfs_name: &str) -> String {
+    format!(
+        "USB\\VID_{}&PID_{}\\{}",
+        vendor_id.to_uppercase(),
+        product_id.to_uppercase(),
+        serial.unwrap_or(sysfs_name)
+    )
+}
+
+/// Maps a USB base class code to a device class name
+pub fn usb_class_name(code: u8) -> &'static str {
+    match code {
+        0x01 => "Audio",
+        0x02 => "Communications",
+        0x03 => "HID",
+        0x05 => "Physical",
+        0x06 => "Image",
+        0x07 => "Printer",
+        0x08 => "MassStorage",
+        0x09 => "Hub",
+        0x0a => "CDCData",
+        0x0b => "SmartCard",
+        0x0d => "ContentSecurity",
+        0x0e => "Video",
+        0x0f => "PersonalHealthcare",
+        0x10 => "AudioVideo",
+        0xdc => "Diagnostic",
+        0xe0 => "Wireless",
+        0xef => "Miscellaneous",
+        0xfe => "ApplicationSpecific",
+        0xff => "VendorSpecific",
+        _ => "USB",
+    }
+}
+
+/// Reads a trimmed sysfs attribute, treating missing or empty files as absent
+pub fn read_attr(dir: &Path, attr: &str) -> Option<String> {
+    let value = fs::read_to_string(dir.join(attr)).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Walks up from a child device (input node, disk, ...) to the USB device that owns it
+/// and builds its instance ID
+pub fn owning_instance_id(device_dir: &Path) -> Option<String> {
+    device_dir.ancestors().find_map(|dir| {
+        let vendor_id = read_attr(dir, "idVendor")?;
+        let product_id = read_attr(dir, "idProduct")?;
+        let name = dir.file_name()?.to_string_lossy().to_string();
+        let serial = read_attr(dir, "serial");
+        Some(instance_id(&vendor_id, &product_id, serial.as_deref(), &name))
+    })
+}
+
+fn read_hex_u8(dir: &Path, attr: &str) -> Option<u8> {
+    u8::from_str_radix(&read_attr(dir, attr)?, 16).ok()
+}
+
+fn read_device(dir: &Path, name: &str) -> Option<SysfsUsbDevice> {
+    let vendor_id = read_attr(dir, "idVendor")?;
+    let product_id = read_attr(dir, "idProduct")?;
+    let serial = read_attr(dir, "serial");
+    let manufacturer = read_attr(dir, "manufacturer");
+    let product = read_attr(dir, "product");
+    let class_code = read_hex_u8(dir, "bDeviceClass").unwrap_or(0);
+    let interfaces = read_interfaces(dir, name);
+    let interface_classes = interfaces.iter().map(|&(class, _, _)| class).collect::<Vec<u8>>();
+    let authorized = read_attr(dir, "authorized").is_none_or(|v| v != "0");
+
+    // Class 0x00 means "defined per interface"
+    let effective_class = match (class_code, interface_classes.first()) {
+        (0x00, Some(&first)) => first,
+        _ => class_code,
+    };
+
+    Some(SysfsUsbDevice {
+        instance_id: instance_id(&vendor_id, &product_id, serial.as_deref(), name),
+        friendly_name: product.clone().unwrap_or_else(|| "Unknown Device".to_string()),
+        device_class: usb_class_name(effective_class).to_string(),
+        status: if authorized { "OK" } else { "Disabled" }.to_string(),
+        sysfs_name: name.to_string(),
+        vendor_id,
+        product_id,
+        serial,
+        manufacturer,
+        product,
+        class_code,
+        interface_classes,
+        interfaces: interfaces
+            .iter()
+            .map(|(class, subclass, protocol)| format!("{:02x}:{:02x}:{:02x}", class, subclass, protocol))
+            .collect(),
+        authorized,
+        bus_type: "USB".to_string(),
+        parent_id: None,
+    })
+}
+
+/// Collects class, subclass and protocol from the `<name>:<config>.<interface>` children of a device
+fn read_interfaces(dir: &Path, name: &str) -> Vec<(u8, u8, u8)> {
+    let prefix = format!("{}:", name);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut interfaces: Vec<(String, (u8, u8, u8))> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let child = entry.file_name().to_string_lossy().to_string();
+            if !child.starts_with(&prefix) {
+                return None;
+            }
+            let path = entry.path();
+            let class = read_hex_u8(&path, "bInterfaceClass")?;
+            let subclass = read_hex_u8(&path, "bInterfaceSubClass").unwrap_or(0);
+            let protocol = read_hex_u8(&path, "bInterfaceProtocol").unwrap_or(0);
+            Some((child, (class, subclass, protocol)))
+        })
+        .collect();
+
+    interfaces.sort();
+    interfaces.into_iter().map(|(_, interface)| interface).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Fake `/sys` with devices under `bus/usb/devices`
+    struct FakeSysfs {
+        root: TempDir,
+    }
+
+    impl FakeSysfs {
+        fn new() -> Self {
+            let root = tempfile::tempdir().unwrap();
+            fs::create_dir_all(root.path().join("bus/usb/devices")).unwrap();
+            Self { root }
+        }
+
+        fn usb(&self) -> SysfsUsb {
+            SysfsUsb::new(self.root.path())
+        }
+
+        fn entry(&self, name: &str, attrs: &[(&str, &str)]) -> PathBuf {
+            let dir = self.usb().devices_dir().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            for (attr, value) in attrs {
+                fs::write(dir.join(attr), format!("{}\n", value)).unwrap();
+            }
+            dir
+        }
+
+        fn device(&self, name: &str, vendor: &str, product: &str, attrs: &[(&str, &str)]) -> PathBuf {
+            let mut all = vec![("idVendor", vendor), ("idProduct", product)];
+            all.extend_from_slice(attrs);
+            self.entry(name, &all)
+        }
+
+        /// Adds an interface as a child directory, like the kernel's `<name>:<config>.<n>` entries
+        fn interface(&self, device: &str, number: u8, class: &str) {
+            let name = format!("{}:1.{}", device, number);
+            let dir = self.usb().devices_dir().join(device).join(&name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("bInterfaceClass"), class).unwrap();
+            fs::write(dir.join("bInterfaceSubClass"), "06").unwrap();
+            fs::write(dir.join("bInterfaceProtocol"), "50").unwrap();
+            // The devices directory lists interfaces too
+            fs::create_dir_all(self.usb().devices_dir().join(&name)).unwrap();
+        }
+    }
+
+    #[test]
+    fn enumerate_skips_root_hubs_and_interfaces() {
+        let sys = FakeSysfs::new();
+        sys.entry("usb1", &[("idVendor", "1d6b"), ("idProduct", "0002"), ("bDeviceClass", "09")]);
+        sys.device("1-1", "0781", "5567", &[("bDeviceClass", "00"), ("product", "Cruzer Blade"), ("serial", "4C530001")]);
+        sys.interface("1-1", 0, "08");
+
+        let devices = sys.usb().enumerate().unwrap();
+        assert_eq!(devices.len(), 1);
+        let stick = &devices[0];
+        assert_eq!(stick.sysfs_name, "1-1");
+        assert_eq!(stick.friendly_name, "Cruzer Blade");
+        assert_eq!(stick.interfaces, vec!["08:06:50"]);
+        assert_eq!(stick.status, "OK");
+    }
+
+    #[test]
+    fn per_interface_class_falls_back_to_the_first_interface() {
+        let sys = FakeSysfs::new();
+        sys.device("1-1", "0781", "5567", &[("bDeviceClass", "00")]);
+        sys.interface("1-1", 1, "03");
+        sys.interface("1-1", 0, "08");
+        sys.device("1-2", "2109", "2813", &[("bDeviceClass", "09")]);
+        sys.interface("1-2", 0, "09");
+
+        let devices = sys.usb().enumerate().unwrap();
+        assert_eq!(devices[0].class_code, 0);
+        assert_eq!(devices[0].interface_classes, vec![8, 3]);
+        assert_eq!(devices[0].device_class, "MassStorage");
+        assert_eq!(devices[1].device_class, "Hub");
+    }
+
+    #[test]
+    fn instance_id_uses_the_serial_or_the_port_path() {
+        let sys = FakeSysfs::new();
+        sys.device("1-1", "0781", "5567", &[("serial", "4C530001")]);
+        sys.device("1-2", "046d", "c077", &[]);
+
+        let devices = sys.usb().enumerate().unwrap();
+        assert_eq!(devices[0].instance_id, "USB\\VID_0781&PID_5567\\4C530001");
+        assert_eq!(devices[1].instance_id, "USB\\VID_046D&PID_C077\\1-2");
+        assert_eq!(devices[1].friendly_name, "Unknown Device");
+    }
+
+    #[test]
+    fn parent_id_points_at_the_hub() {
+        let sys = FakeSysfs::new();
+        sys.device("1-2", "2109", "2813", &[("bDeviceClass", "09"), ("serial", "HUB1")]);
+        sys.device("1-2.3", "046d", "c077", &[]);
+        sys.device("1-2.3.1", "0781", "5567", &[("serial", "S1")]);
+
+        let devices = sys.usb().enumerate().unwrap();
+        let parent = |name: &str| devices.iter().find(|d| d.sysfs_name == name).unwrap().parent_id.clone();
+        // A device on a root port has no listed parent
+        assert_eq!(parent("1-2"), None);
+        assert_eq!(parent("1-2.3").as_deref(), Some("USB\\VID_2109&PID_2813\\HUB1"));
+        assert_eq!(parent("1-2.3.1").as_deref(), Some("USB\\VID_046D&PID_C077\\1-2.3"));
+    }
+
+    #[test]
+    fn entries_without_ids_are_ignored() {
+        let sys = FakeSysfs::new();
+        sys.entry("1-4", &[("product", "Half-probed")]);
+        assert!(sys.usb().enumerate().unwrap().is_empty());
+    }
+
+    #[test]
+    fn authorized_default_is_written_on_every_root_hub() {
+        let sys = FakeSysfs::new();
+        let hub1 = sys.entry("usb1", &[("authorized_default", "1")]);
+        let hub2 = sys.entry("usb2", &[("authorized_default", "1")]);
+        let stick = sys.device("1-1", "0781", "5567", &[]);
+
+        sys.usb().set_authorized_default(false).unwrap();
+        assert_eq!(fs::read_to_string(hub1.join("authorized_default")).unwrap(), "0");
+        assert_eq!(fs::read_to_string(hub2.join("authorized_default")).unwrap(), "0");
+        assert!(!stick.join("authorized_default").exists());
+
+        sys.usb().set_authorized_default(true).unwrap();
+        assert_eq!(fs::read_to_string(hub1.join("authorized_default")).unwrap(), "1");
+    }
+
+    #[test]
+    fn authorized_default_without_root_hubs_is_not_found() {
+        let sys = FakeSysfs::new();
+        let error = sys.usb().set_authorized_default(false).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn set_authorized_writes_the_device_attribute() {
+        let sys = FakeSysfs::new();
+        let stick = sys.device("1-1", "0781", "5567", &[("serial", "4C530001"), ("authorized", "1")]);
+        let id = "USB\\VID_0781&PID_5567\\4C530001";
+
+        sys.usb().set_authorized(id, false).unwrap();
+        assert_eq!(fs::read_to_string(stick.join("authorized")).unwrap(), "0");
+        assert_eq!(sys.usb().find(id).unwrap().status, "Disabled");
+
+        sys.usb().set_authorized(id, true).unwrap();
+        assert_eq!(sys.usb().find(id).unwrap().status, "OK");
+
+        let missing = sys.usb().set_authorized("USB\\VID_1234&PID_5678\\X", false).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use serde::Serialize;
+use crate::services::device_id;
+use crate::services::sysfs::{read_attr, DEFAULT_SYSFS_ROOT};
+
+/// Instance ID prefix of Thunderbolt devices, matching the Windows enumerator name
+pub const INSTANCE_PREFIX: &str = "THUNDERBOLT\\";
+
+/// A Thunderbolt/USB4 device read from `<root>/bus/thunderbolt/devices`.
+/// Serializes with the same field names as a USB device so it goes through the same listing.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThunderboltDevice {
+    pub instance_id: String,
+    pub friendly_name: String,
+    pub device_class: String,
+    pub status: String,
+    pub bus_type: String,
+    /// Kernel device name, e.g. `0-1` or `0-301` for the second device in a chain
+    pub sysfs_name: String,
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    pub vendor_name: Option<String>,
+    pub device_name: Option<String>,
+    /// UUID the device reports (`unique_id`); stable across ports and reboots, so it stands in for a serial
+    #[serde(rename = "serial")]
+    pub unique_id: String,
+    pub authorized: bool,
+    /// Security level of the domain the device is on (`none`, `user`, `secure`, `dponly`, ...)
+    pub security: Option<String>,
+    /// Device in front of this one in a daisy chain
+    pub parent_id: Option<String>,
+}
+
+/// A Thunderbolt domain (host controller) and how it authorizes devices
+#[derive(Debug, Clone, Serialize)]
+pub struct ThunderboltDomain {
+    pub name: String,
+    pub security: Option<String>,
+    /// Whether the IOMMU keeps unauthorized devices from doing DMA
+    pub iommu_dma_protection: bool,
+}
+
+impl ThunderboltDomain {
+    /// Whether PCIe tunnels come up without waiting for user space, so devices get DMA
+    /// before they can be checked (`dponly` and `usbonly` don't tunnel PCIe at all)
+    pub fn allows_unauthorized_dma(&self) -> bool {
+        self.security.as_deref() == Some("none")
+    }
+}
+
+/// Reads and authorizes Thunderbolt devices in a sysfs tree (the real `/sys` or a fake one for tests)
+#[derive(Debug, Clone)]
+pub struct SysfsThunderbolt {
+    root: PathBuf,
+}
+
+impl Default for SysfsThunderbolt {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYSFS_ROOT)
+    }
+}
+
+/// Parses `0-301` into its domain and route string
+fn parse_name(name: &str) -> Option<(u32, u64)> {
+    let (domain, route) = name.split_once('-')?;
+    Some((domain.parse().ok()?, u64::from_str_radix(route, 16).ok()?))
+}
+
+/// Instance ID built from the vendor/device IDs and the device's UUID
+pub fn instance_id(vendor_id: Option<&str>, product_id: Option<&str>, unique_id: &str) -> String {
+    format!(
+        "{}VEN_{}&DEV_{}\\{}",
+        INSTANCE_PREFIX,
+        vendor_id.unwrap_or("0000"),
+        product_id.unwrap_or("0000"),
+        unique_id.to_uppercase()
+    )
+}
+
+/// Route of the upstream device. The route string holds one port per byte, starting
+/// from the host in the lowest byte, so dropping the highest byte steps back one hop.
+fn parent_route(route: u64) -> u64 {
+    let hops = (u64::BITS - route.leading_zeros()).div_ceil(8);
+    route & (1u64 << (8 * hops.saturating_sub(1))).wrapping_sub(1)
+}
+
+fn read_hex_id(value: Option<String>) -> Option<String> {
+    let value = value?;
+    device_id::normalize_hex_id(value.trim_start_matches("0x"))
+}
+
+impl SysfsThunderbolt {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn devices_dir(&self) -> PathBuf {
+        self.root.join("bus").join("thunderbolt").join("devices")
+    }
+
+    /// Lists the domains; empty when the machine has no Thunderbolt controller
+    pub fn domains(&self) -> io::Result<Vec<ThunderboltDomain>> {
+        let entries = match fs::read_dir(self.devices_dir()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut domains: Vec<ThunderboltDomain> = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("domain"))
+            .map(|entry| ThunderboltDomain {
+                name: entry.file_name().to_string_lossy().to_string(),
+                security: read_attr(&entry.path(), "security"),
+                iommu_dma_protection: read_attr(&entry.path(), "iommu_dma_protection").as_deref() == Some("1"),
+            })
+            .collect();
+        domains.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(domains)
+    }
+
+    /// Lists attached devices, skipping host routers, domains and XDomain services
+    pub fn enumerate(&self) -> io::Result<Vec<ThunderboltDevice>> {
+        let entries = match fs::read_dir(self.devices_dir()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let security: HashMap<String, Option<String>> = self
+            .domains()?
+            .into_iter()
+            .map(|d| (d.name, d.security))
+            .collect();
+
+        let mut devices = Vec::new();
+        let mut by_route = HashMap::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Route 0 is the host router itself
+            let Some((domain, route)) = parse_name(&name).filter(|&(_, route)| route != 0) else {
+                continue;
+            };
+            let dir = entry.path();
+            let Some(unique_id) = read_attr(&dir, "unique_id") else {
+                continue;
+            };
+
+            let vendor_id = read_hex_id(read_attr(&dir, "vendor"));
+            let product_id = read_hex_id(read_attr(&dir, "device"));
+            let vendor_name = read_attr(&dir, "vendor_name");
+            let device_name = read_attr(&dir, "device_name");
+            // 0 = not authorized, 1 = authorized, 2 = authorized with a secure key
+            let authorized = read_attr(&dir, "authorized").is_some_and(|v| v != "0");
+            let friendly_name = match (&vendor_name, &device_name) {
+                (Some(vendor), Some(device)) => format!("{} {}", vendor, device),
+                (None, Some(device)) => device.clone(),
+                _ => "Thunderbolt Device".to_string(),
+            };
+
+            let id = instance_id(vendor_id.as_deref(), product_id.as_deref(), &unique_id);
+            by_route.insert((domain, route), id.clone());
+            devices.push(ThunderboltDevice {
+                instance_id: id,
+                friendly_name,
+                device_class: "Thunderbolt".to_string(),
+                status: if authorized { "OK" } else { "Disabled" }.to_string(),
+                bus_type: "Thunderbolt".to_string(),
+                sysfs_name: name,
+                vendor_id,
+                product_id,
+                vendor_name,
+                device_name,
+                unique_id,
+                authorized,
+                security: security.get(&format!("domain{}", domain)).cloned().flatten(),
+                parent_id: None,
+            });
+        }
+
+        for device in &mut devices {
+            if let Some((domain, route)) = parse_name(&device.sysfs_name) {
+                device.parent_id = by_route.get(&(domain, parent_route(route))).cloned();
+            }
+        }
+        devices.sort_by(|a, b| a.sysfs_name.cmp(&b.sysfs_name));
+        Ok(devices)
+    }
+
+    /// Authorizes or deauthorizes a device through its `authorized` attribute (requires root).
+    /// Deauthorizing needs kernel support (`deauthorization` on the domain).
+    pub fn set_authorized(&self, instance_id: &str, authorized: bool) -> io::Result<()> {
+        let device = self
+            .enumerate()?
+            .into_iter()
+            .find(|d| d.instance_id.eq_ignore_ascii_case(instance_id))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("Device not found: {}", instance_id))
+            })?;
+        if device.authorized == authorized {
+            return Ok(());
+        }
+        let path = self.devices_dir().join(&device.sysfs_name).join("authorized");
+        fs::write(path, if authorized { "1" } else { "0" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    struct FakeThunderbolt {
+        root: TempDir,
+    }
+
+    impl FakeThunderbolt {
+        fn new() -> Self {
+            Self { root: tempfile::tempdir().unwrap() }
+        }
+
+        fn tb(&self) -> SysfsThunderbolt {
+            SysfsThunderbolt::new(self.root.path())
+        }
+
+        fn entry(&self, name: &str, attrs: &[(&str, &str)]) -> PathBuf {
+            let dir = self.tb().devices_dir().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            for (attr, value) in attrs {
+                fs::write(dir.join(attr), format!("{}\n", value)).unwrap();
+            }
+            dir
+        }
+
+        fn domain(&self, name: &str, security: &str) {
+            self.entry(name, &[("security", security), ("iommu_dma_protection", "0")]);
+        }
+
+        fn device(&self, name: &str, unique_id: &str, authorized: &str) -> PathBuf {
+            self.entry(name, &[
+                ("vendor", "0x8086"),
+                ("device", "0x15ef"),
+                ("vendor_name", "Intel"),
+                ("device_name", "Dock"),
+                ("unique_id", unique_id),
+                ("authorized", authorized),
+            ])
+        }
+    }
+
+    fn authorized_file(dir: &Path) -> String {
+        fs::read_to_string(dir.join("authorized")).unwrap()
+    }
+
+    #[test]
+    fn parent_route_drops_the_last_hop() {
+        assert_eq!(parent_route(0x1), 0x0);
+        assert_eq!(parent_route(0x301), 0x1);
+        assert_eq!(parent_route(0x30201), 0x201);
+        assert_eq!(parent_route(0x0), 0x0);
+        assert_eq!(parse_name("0-301"), Some((0, 0x301)));
+        assert_eq!(parse_name("0-1.1"), None);
+        assert_eq!(parse_name("domain0"), None);
+    }
+
+    #[test]
+    fn enumerate_skips_host_routers_and_links_daisy_chains() {
+        let sys = FakeThunderbolt::new();
+        sys.domain("domain0", "user");
+        sys.entry("0-0", &[("unique_id", "host-uuid"), ("authorized", "1")]);
+        sys.device("0-1", "d0c2f6e0-0000-0000-0000-000000000001", "1");
+        sys.device("0-301", "d0c2f6e0-0000-0000-0000-000000000002", "0");
+        // An XDomain service and a device that hasn't reported its UUID yet
+        sys.entry("0-1.1", &[("unique_id", "peer")]);
+        sys.entry("0-2", &[("authorized", "0")]);
+
+        let devices = sys.tb().enumerate().unwrap();
+        let names: Vec<&str> = devices.iter().map(|d| d.sysfs_name.as_str()).collect();
+        assert_eq!(names, vec!["0-1", "0-301"]);
+
+        let dock = &devices[0];
+        assert_eq!(dock.instance_id, "THUNDERBOLT\\VEN_8086&DEV_15EF\\D0C2F6E0-0000-0000-0000-000000000001");
+        assert_eq!(dock.friendly_name, "Intel Dock");
+        assert_eq!(dock.status, "OK");
+        assert_eq!(dock.security.as_deref(), Some("user"));
+        assert_eq!(dock.parent_id, None);
+
+        let chained = &devices[1];
+        assert!(!chained.authorized);
+        assert_eq!(chained.status, "Disabled");
+        assert_eq!(chained.parent_id.as_deref(), Some(dock.instance_id.as_str()));
+    }
+
+    #[test]
+    fn missing_bus_is_empty() {
+        let sys = FakeThunderbolt::new();
+        assert!(sys.tb().enumerate().unwrap().is_empty());
+        assert!(sys.tb().domains().unwrap().is_empty());
+    }
+
+    #[test]
+    fn only_security_none_allows_unauthorized_dma() {
+        let sys = FakeThunderbolt::new();
+        for (i, level) in ["none", "user", "secure", "dponly", "usbonly"].iter().enumerate() {
+            sys.domain(&format!("domain{}", i), level);
+        }
+        sys.entry("domain5", &[]);
+
+        let domains = sys.tb().domains().unwrap();
+        let dma: Vec<(&str, bool)> = domains.iter().map(|d| (d.name.as_str(), d.allows_unauthorized_dma())).collect();
+        assert_eq!(dma, vec![
+            ("domain0", true),
+            ("domain1", false),
+            ("domain2", false),
+            ("domain3", false),
+            ("domain4", false),
+            ("domain5", false),
+        ]);
+        assert!(!domains[0].iommu_dma_protection);
+    }
+
+    #[test]
+    fn set_authorized_writes_only_when_the_state_changes() {
+        let sys = FakeThunderbolt::new();
+        sys.domain("domain0", "user");
+        let dock = sys.device("0-1", "uuid-1", "1");
+        let disk = sys.device("0-301", "uuid-2", "0");
+        let disk_id = instance_id(Some("8086"), Some("15EF"), "uuid-2");
+
+        sys.tb().set_authorized(&disk_id.to_lowercase(), true).unwrap();
+        assert_eq!(authorized_file(&disk), "1");
+
+        // Already authorized: the attribute keeps the kernel's trailing newline
+        let dock_id = instance_id(Some("8086"), Some("15EF"), "uuid-1");
+        sys.tb().set_authorized(&dock_id, true).unwrap();
+        assert_eq!(authorized_file(&dock), "1\n");
+
+        sys.tb().set_authorized(&dock_id, false).unwrap();
+        assert_eq!(authorized_file(&dock), "0");
+
+        let err = sys.tb().set_authorized("THUNDERBOLT\\VEN_0000&DEV_0000\\NOPE", true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
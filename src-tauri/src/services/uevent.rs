@@ -0,0 +1,214 @@
+use std::io;
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::Duration;
+
+// Multicast group the kernel broadcasts uevents on (udevd re-broadcasts on group 2)
+const KERNEL_UEVENT_GROUP: u32 = 1;
+
+/// A kernel uevent, e.g. `add@/devices/pci0000:00/0000:00:14.0/usb1/1-2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uevent {
+    pub action: String,
+    pub devpath: String,
+    pub subsystem: Option<String>,
+    pub devtype: Option<String>,
+}
+
+impl Uevent {
+    /// Whether this is a whole USB device (not an interface) being added or removed
+    pub fn is_usb_device_change(&self) -> bool {
+        matches!(self.action.as_str(), "add" | "remove")
+            && self.subsystem.as_deref() == Some("usb")
+            && self.devtype.as_deref() == Some("usb_device")
+    }
+
+    /// Whether a Thunderbolt device was added, removed or (de)authorized
+    pub fn is_thunderbolt_device_change(&self) -> bool {
+        matches!(self.action.as_str(), "add" | "remove" | "change")
+            && self.subsystem.as_deref() == Some("thunderbolt")
+            && self.devtype.as_deref() == Some("thunderbolt_device")
+    }
+}
+
+/// Parses a kernel uevent datagram: an `action@devpath` header followed by NUL-separated `KEY=value` pairs
+pub fn parse_uevent(buf: &[u8]) -> Option<Uevent> {
+    let mut fields = buf.split(|&b| b == 0).filter(|f| !f.is_empty());
+    let header = String::from_utf8_lossy(fields.next()?).to_string();
+    let (action, devpath) = header.split_once('@')?;
+
+    let mut event = Uevent {
+        action: action.to_string(),
+        devpath: devpath.to_string(),
+        subsystem: None,
+        devtype: None,
+    };
+
+    for field in fields {
+        let field = String::from_utf8_lossy(field);
+        match field.split_once('=') {
+            Some(("SUBSYSTEM", value)) => event.subsystem = Some(value.to_string()),
+            Some(("DEVTYPE", value)) => event.devtype = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Some(event)
+}
+
+/// Netlink socket subscribed to kernel uevents
+pub struct UeventSocket {
+    fd: OwnedFd,
+}
+
+impl UeventSocket {
+    /// Opens the socket; `recv` gives up after `timeout` so callers can resync periodically
+    pub fn open(timeout: Duration) -> io::Result<Self> {
+        // SAFETY: plain socket(2) call; the result is checked before use
+        let raw = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_KOBJECT_UEVENT,
+            )
+        };
+        if raw < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `raw` is a freshly created descriptor that nothing else owns
+        let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+        // SAFETY: sockaddr_nl is plain old data, all-zero is a valid value
+        let mut addr: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = KERNEL_UEVENT_GROUP;
+
+        // SAFETY: addr is a valid sockaddr_nl and the length matches it
+        let bound = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let tv = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+        };
+        // SAFETY: tv is a valid timeval and the length matches it
+        let set = unsafe {
+            libc::setsockopt(
+                fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &tv as *const libc::timeval as *const libc::c_void,
+                mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if set < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd })
+    }
+
+    /// Receives the next uevent, or `None` when the timeout elapses first
+    pub fn recv(&self) -> io::Result<Option<Uevent>> {
+        let mut buf = [0u8; 8192];
+        // SAFETY: buf is valid for writes of buf.len() bytes
+        let len = unsafe {
+            libc::recv(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            )
+        };
+        if len < 0 {
+            let err = io::Error::last_os_error();
+            return match err.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted => Ok(None),
+                _ => Err(err),
+            };
+        }
+        Ok(parse_uevent(&buf[..len as usize]))
+    }
+
+    /// Blocks until a USB or Thunderbolt device is added or removed, or the timeout elapses
+    pub fn wait_for_device_change(&self) -> io::Result<()> {
+        loop {
+            match self.recv()? {
+                Some(event) if event.is_usb_device_change() || event.is_thunderbolt_device_change() => return Ok(()),
+                Some(_) => continue,
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from the kernel group while plugging in a flash drive
+    const USB_DEVICE_ADD: &[u8] = b"add@/devices/pci0000:00/0000:00:14.0/usb1/1-2\0ACTION=add\0DEVPATH=/devices/pci0000:00/0000:00:14.0/usb1/1-2\0SUBSYSTEM=usb\0MAJOR=189\0MINOR=3\0DEVNAME=bus/usb/001/004\0DEVTYPE=usb_device\0PRODUCT=781/5567/100\0TYPE=0/0/0\0BUSNUM=001\0DEVNUM=004\0SEQNUM=5123\0";
+    const USB_INTERFACE_ADD: &[u8] = b"add@/devices/pci0000:00/0000:00:14.0/usb1/1-2/1-2:1.0\0ACTION=add\0DEVPATH=/devices/pci0000:00/0000:00:14.0/usb1/1-2/1-2:1.0\0SUBSYSTEM=usb\0DEVTYPE=usb_interface\0PRODUCT=781/5567/100\0INTERFACE=8/6/80\0SEQNUM=5124\0";
+    const BLOCK_ADD: &[u8] = b"add@/devices/pci0000:00/0000:00:14.0/usb1/1-2/1-2:1.0/host6/target6:0:0/6:0:0:0/block/sdb\0ACTION=add\0SUBSYSTEM=block\0DEVNAME=sdb\0DEVTYPE=disk\0SEQNUM=5140\0";
+    const THUNDERBOLT_CHANGE: &[u8] = b"change@/devices/pci0000:00/0000:00:0d.2/domain0/0-0/0-1\0ACTION=change\0DEVPATH=/devices/pci0000:00/0000:00:0d.2/domain0/0-0/0-1\0SUBSYSTEM=thunderbolt\0DEVTYPE=thunderbolt_device\0SEQNUM=812\0";
+
+    #[test]
+    fn usb_device_add_is_parsed() {
+        let event = parse_uevent(USB_DEVICE_ADD).unwrap();
+        assert_eq!(event, Uevent {
+            action: "add".to_string(),
+            devpath: "/devices/pci0000:00/0000:00:14.0/usb1/1-2".to_string(),
+            subsystem: Some("usb".to_string()),
+            devtype: Some("usb_device".to_string()),
+        });
+        assert!(event.is_usb_device_change());
+        assert!(!event.is_thunderbolt_device_change());
+    }
+
+    #[test]
+    fn usb_device_removal_is_a_change_but_bind_is_not() {
+        let remove = USB_DEVICE_ADD.strip_prefix(b"add").map(|rest| [b"remove".as_slice(), rest].concat()).unwrap();
+        assert!(parse_uevent(&remove).unwrap().is_usb_device_change());
+        let bind = USB_DEVICE_ADD.strip_prefix(b"add").map(|rest| [b"bind".as_slice(), rest].concat()).unwrap();
+        assert!(!parse_uevent(&bind).unwrap().is_usb_device_change());
+    }
+
+    #[test]
+    fn interface_and_non_usb_events_are_ignored() {
+        let interface = parse_uevent(USB_INTERFACE_ADD).unwrap();
+        assert_eq!(interface.devtype.as_deref(), Some("usb_interface"));
+        assert!(!interface.is_usb_device_change());
+
+        let block = parse_uevent(BLOCK_ADD).unwrap();
+        assert_eq!(block.subsystem.as_deref(), Some("block"));
+        assert!(!block.is_usb_device_change());
+        assert!(!block.is_thunderbolt_device_change());
+    }
+
+    #[test]
+    fn thunderbolt_authorization_is_a_change() {
+        let event = parse_uevent(THUNDERBOLT_CHANGE).unwrap();
+        assert_eq!(event.action, "change");
+        assert!(event.is_thunderbolt_device_change());
+        assert!(!event.is_usb_device_change());
+    }
+
+    #[test]
+    fn malformed_datagrams_are_rejected() {
+        assert_eq!(parse_uevent(b""), None);
+        assert_eq!(parse_uevent(b"\0\0"), None);
+        // udevd's re-broadcast starts with "libudev" rather than action@devpath
+        assert_eq!(parse_uevent(b"libudev\0\xfe\xed\xca\xfe"), None);
+
+        let bare = parse_uevent(b"add@/devices/virtual/misc/uhid").unwrap();
+        assert_eq!((bare.subsystem, bare.devtype), (None, None));
+    }
+}
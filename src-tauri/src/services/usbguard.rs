@@ -0,0 +1,408 @@
+use crate::models::{InterfaceMatch, SetOperator};
+use crate::services::device_id;
+
+/// What a USBGuard rule does with the devices it matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTarget {
+    Allow,
+    Block,
+    Reject,
+}
+
+/// A USBGuard rule restricted to the attributes SentinelGuard can evaluate:
+/// `id`, `serial`, `name` and `with-interface`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbGuardRule {
+    pub target: RuleTarget,
+    /// `None` for a `*` wildcard
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    pub serial: Option<String>,
+    pub name: Option<String>,
+    pub with_interface: Option<InterfaceMatch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Open,
+    Close,
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '{' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            '}' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => value.push(escaped),
+                            None => return Err("Unterminated string".to_string()),
+                        },
+                        Some(other) => value.push(other),
+                        None => return Err("Unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_operator(word: &str) -> Option<SetOperator> {
+    match word {
+        "all-of" => Some(SetOperator::AllOf),
+        "one-of" => Some(SetOperator::OneOf),
+        "none-of" => Some(SetOperator::NoneOf),
+        "equals" => Some(SetOperator::Equals),
+        "equals-ordered" => Some(SetOperator::EqualsOrdered),
+        _ => None,
+    }
+}
+
+fn operator_keyword(operator: SetOperator) -> &'static str {
+    match operator {
+        SetOperator::AllOf => "all-of",
+        SetOperator::OneOf => "one-of",
+        SetOperator::NoneOf => "none-of",
+        SetOperator::Equals => "equals",
+        SetOperator::EqualsOrdered => "equals-ordered",
+    }
+}
+
+/// Parses `vvvv:pppp` where either half may be `*`
+fn parse_id(value: &str) -> Result<(Option<String>, Option<String>), String> {
+    let (vendor, product) = value
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid id '{}'", value))?;
+    let part = |v: &str| -> Result<Option<String>, String> {
+        if v == "*" {
+            return Ok(None);
+        }
+        device_id::normalize_hex_id(v)
+            .map(Some)
+            .ok_or_else(|| format!("Invalid id '{}'", value))
+    };
+    let (vendor_id, product_id) = (part(vendor)?, part(product)?);
+    if vendor_id.is_none() && product_id.is_some() {
+        return Err(format!("Unsupported id '{}': a product ID needs a vendor ID", value));
+    }
+    Ok((vendor_id, product_id))
+}
+
+/// Validates and normalizes a `cc:ss:pp` interface pattern
+fn parse_interface_pattern(value: &str) -> Result<String, String> {
+    let parts: Vec<&str> = value.split(':').collect();
+    let valid = parts.len() == 3
+        && parts.iter().all(|p| *p == "*" || (p.len() == 2 && p.chars().all(|c| c.is_ascii_hexdigit())));
+    if !valid {
+        return Err(format!("Invalid interface '{}'", value));
+    }
+    Ok(value.to_lowercase())
+}
+
+/// Parses one rule line. Attributes outside the supported subset are errors rather than
+/// being ignored, since dropping a condition would make an allow rule broader.
+pub fn parse_rule(line: &str) -> Result<UsbGuardRule, String> {
+    let tokens = tokenize(line)?;
+    let mut tokens = tokens.into_iter().peekable();
+
+    let target = match tokens.next() {
+        Some(Token::Word(w)) if w == "allow" => RuleTarget::Allow,
+        Some(Token::Word(w)) if w == "block" => RuleTarget::Block,
+        Some(Token::Word(w)) if w == "reject" => RuleTarget::Reject,
+        _ => return Err("Rule must start with allow, block or reject".to_string()),
+    };
+
+    let mut rule = UsbGuardRule {
+        target,
+        vendor_id: None,
+        product_id: None,
+        serial: None,
+        name: None,
+        with_interface: None,
+    };
+
+    while let Some(token) = tokens.next() {
+        let Token::Word(attribute) = token else {
+            return Err("Expected an attribute name".to_string());
+        };
+
+        match attribute.as_str() {
+            "id" => match tokens.next() {
+                Some(Token::Word(value)) | Some(Token::Quoted(value)) => {
+                    (rule.vendor_id, rule.product_id) = parse_id(&value)?;
+                }
+                _ => return Err("Only a single value is supported for id".to_string()),
+            },
+            "serial" | "name" => {
+                let Some(Token::Quoted(value)) = tokens.next() else {
+                    return Err(format!("Only a single quoted value is supported for {}", attribute));
+                };
+                if attribute == "serial" {
+                    rule.serial = Some(value);
+                } else {
+                    rule.name = Some(value);
+                }
+            }
+            "with-interface" => {
+                let mut operator = SetOperator::Equals;
+                if let Some(Token::Word(word)) = tokens.peek() {
+                    if let Some(op) = parse_operator(word) {
+                        operator = op;
+                        tokens.next();
+                    }
+                }
+
+                let mut patterns = Vec::new();
+                match tokens.next() {
+                    Some(Token::Word(value)) => patterns.push(parse_interface_pattern(&value)?),
+                    Some(Token::Open) => loop {
+                        match tokens.next() {
+                            Some(Token::Close) => break,
+                            Some(Token::Word(value)) => patterns.push(parse_interface_pattern(&value)?),
+                            _ => return Err("Unterminated interface set".to_string()),
+                        }
+                    },
+                    _ => return Err("Missing with-interface value".to_string()),
+                }
+                if patterns.is_empty() {
+                    return Err("Empty interface set".to_string());
+                }
+                rule.with_interface = Some(InterfaceMatch { operator, patterns });
+            }
+            other => return Err(format!("Unsupported attribute '{}'", other)),
+        }
+    }
+
+    Ok(rule)
+}
+
+/// Parses a rules file, returning each non-comment line with its 1-based line number and result
+pub fn parse_rules(text: &str) -> Vec<(usize, String, Result<UsbGuardRule, String>)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| (number, line.to_string(), parse_rule(line)))
+        .collect()
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Formats a rule in USBGuard syntax
+pub fn format_rule(rule: &UsbGuardRule) -> String {
+    let mut text = match rule.target {
+        RuleTarget::Allow => "allow",
+        RuleTarget::Block => "block",
+        RuleTarget::Reject => "reject",
+    }
+    .to_string();
+
+    if rule.vendor_id.is_some() || rule.product_id.is_some() {
+        let part = |v: &Option<String>| v.as_deref().map(str::to_lowercase).unwrap_or_else(|| "*".to_string());
+        text.push_str(&format!(" id {}:{}", part(&rule.vendor_id), part(&rule.product_id)));
+    }
+    if let Some(serial) = &rule.serial {
+        text.push_str(&format!(" serial {}", quote(serial)));
+    }
+    if let Some(name) = &rule.name {
+        text.push_str(&format!(" name {}", quote(name)));
+    }
+    if let Some(interfaces) = &rule.with_interface {
+        text.push_str(" with-interface ");
+        if interfaces.patterns.len() == 1 && interfaces.operator == SetOperator::Equals {
+            text.push_str(&interfaces.patterns[0]);
+        } else {
+            text.push_str(&format!(
+                "{} {{ {} }}",
+                operator_keyword(interfaces.operator),
+                interfaces.patterns.join(" ")
+            ));
+        }
+    }
+
+    text
+}
+
+fn pattern_matches(pattern: &str, interface: &str) -> bool {
+    let mut actual = interface.split(':');
+    pattern.split(':').all(|p| {
+        let a = actual.next().unwrap_or("");
+        p == "*" || p.eq_ignore_ascii_case(a)
+    })
+}
+
+/// Evaluates a `with-interface` condition against a device's `cc:ss:pp` interfaces
+pub fn interfaces_match(condition: &InterfaceMatch, interfaces: &[String]) -> bool {
+    let patterns = &condition.patterns;
+    let any_interface = |p: &String| interfaces.iter().any(|i| pattern_matches(p, i));
+
+    match condition.operator {
+        SetOperator::AllOf => patterns.iter().all(any_interface),
+        SetOperator::OneOf => patterns.iter().any(any_interface),
+        SetOperator::NoneOf => !patterns.iter().any(any_interface),
+        SetOperator::Equals => {
+            !interfaces.is_empty()
+                && patterns.iter().all(any_interface)
+                && interfaces.iter().all(|i| patterns.iter().any(|p| pattern_matches(p, i)))
+        }
+        SetOperator::EqualsOrdered => {
+            patterns.len() == interfaces.len()
+                && patterns.iter().zip(interfaces).all(|(p, i)| pattern_matches(p, i))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interfaces(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn condition(operator: SetOperator, patterns: &[&str]) -> InterfaceMatch {
+        InterfaceMatch { operator, patterns: interfaces(patterns) }
+    }
+
+    #[test]
+    fn full_rule_is_parsed() {
+        let rule = parse_rule(r#"allow id 0781:5581 serial "4C53\"01" name "Ultra" with-interface one-of { 08:06:50 03:*:* }"#).unwrap();
+        assert_eq!(rule, UsbGuardRule {
+            target: RuleTarget::Allow,
+            vendor_id: Some("0781".to_string()),
+            product_id: Some("5581".to_string()),
+            serial: Some("4C53\"01".to_string()),
+            name: Some("Ultra".to_string()),
+            with_interface: Some(condition(SetOperator::OneOf, &["08:06:50", "03:*:*"])),
+        });
+    }
+
+    #[test]
+    fn rules_round_trip_through_format() {
+        for line in [
+            "allow",
+            "block id 046d:*",
+            "reject id 1d6b:0002 with-interface 09:00:00",
+            r#"allow id 0781:5581 serial "A\\B \"C\"" name "SanDisk 3.2Gen1""#,
+            "allow with-interface all-of { 03:01:01 03:01:02 }",
+            "block with-interface none-of { e0:*:* }",
+            "allow with-interface equals { 08:06:50 03:*:* }",
+            "allow with-interface equals-ordered { 08:06:50 }",
+        ] {
+            let rule = parse_rule(line).unwrap();
+            assert_eq!(format_rule(&rule), line);
+            assert_eq!(parse_rule(&format_rule(&rule)).unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn ids_and_interfaces_are_normalized() {
+        let rule = parse_rule("allow id ABCD:12 with-interface 0E:*:FF").unwrap();
+        assert_eq!(rule.vendor_id.as_deref(), Some("ABCD"));
+        assert_eq!(rule.product_id.as_deref(), Some("0012"));
+        assert_eq!(format_rule(&rule), "allow id abcd:0012 with-interface 0e:*:ff");
+    }
+
+    #[test]
+    fn unsupported_syntax_is_rejected() {
+        for (line, error) in [
+            ("", "Rule must start with allow, block or reject"),
+            ("permit id 0781:5581", "Rule must start with allow, block or reject"),
+            ("allow hash \"abc=\"", "Unsupported attribute 'hash'"),
+            ("allow via-port \"1-2\"", "Unsupported attribute 'via-port'"),
+            ("allow id *:5581", "Unsupported id '*:5581': a product ID needs a vendor ID"),
+            ("allow id 0781", "Invalid id '0781'"),
+            ("allow id 07811:5581", "Invalid id '07811:5581'"),
+            ("allow id { 0781:5581 0781:5583 }", "Only a single value is supported for id"),
+            ("allow serial 4C53", "Only a single quoted value is supported for serial"),
+            ("allow name \"Ultra", "Unterminated string"),
+            ("allow with-interface 08:06", "Invalid interface '08:06'"),
+            ("allow with-interface one-of { 08:06:50", "Unterminated interface set"),
+            ("allow with-interface one-of { }", "Empty interface set"),
+            ("allow with-interface", "Missing with-interface value"),
+            ("allow \"id\" 0781:5581", "Expected an attribute name"),
+        ] {
+            assert_eq!(parse_rule(line), Err(error.to_string()), "{}", line);
+        }
+    }
+
+    #[test]
+    fn rules_file_skips_comments_and_keeps_line_numbers() {
+        let parsed = parse_rules("# policy\n\nallow id 0781:5581\n  block via-port \"1-1\"\n");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, 3);
+        assert!(parsed[0].2.is_ok());
+        assert_eq!(parsed[1].0, 4);
+        assert_eq!(parsed[1].1, "block via-port \"1-1\"");
+        assert!(parsed[1].2.is_err());
+    }
+
+    #[test]
+    fn equals_ignores_order_but_equals_ordered_does_not() {
+        let device = interfaces(&["03:01:02", "08:06:50"]);
+        let reversed = ["08:06:50", "03:*:*"];
+
+        assert!(interfaces_match(&condition(SetOperator::Equals, &reversed), &device));
+        assert!(!interfaces_match(&condition(SetOperator::EqualsOrdered, &reversed), &device));
+        assert!(interfaces_match(&condition(SetOperator::EqualsOrdered, &["03:*:*", "08:06:50"]), &device));
+    }
+
+    #[test]
+    fn equals_needs_every_interface_covered() {
+        let device = interfaces(&["08:06:50", "e0:01:01"]);
+        assert!(!interfaces_match(&condition(SetOperator::Equals, &["08:06:50"]), &device));
+        assert!(!interfaces_match(&condition(SetOperator::EqualsOrdered, &["08:06:50"]), &device));
+        assert!(!interfaces_match(&condition(SetOperator::Equals, &["08:06:50"]), &[]));
+    }
+
+    #[test]
+    fn set_operators_match_interfaces() {
+        let keyboard_and_storage = interfaces(&["03:01:01", "08:06:50"]);
+        let storage = interfaces(&["08:06:50"]);
+        let hid = ["03:*:*"];
+
+        assert!(interfaces_match(&condition(SetOperator::OneOf, &hid), &keyboard_and_storage));
+        assert!(!interfaces_match(&condition(SetOperator::OneOf, &hid), &storage));
+        assert!(interfaces_match(&condition(SetOperator::AllOf, &["03:01:01", "08:*:*"]), &keyboard_and_storage));
+        assert!(!interfaces_match(&condition(SetOperator::AllOf, &["03:01:01", "08:*:*"]), &storage));
+        assert!(interfaces_match(&condition(SetOperator::NoneOf, &hid), &storage));
+        assert!(!interfaces_match(&condition(SetOperator::NoneOf, &hid), &keyboard_and_storage));
+        assert!(interfaces_match(&condition(SetOperator::OneOf, &["03:01:01"]), &interfaces(&["03:01:01"])));
+        assert!(interfaces_match(&condition(SetOperator::OneOf, &["0E:*:*"]), &interfaces(&["0e:01:00"])));
+    }
+}
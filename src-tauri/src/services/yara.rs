@@ -0,0 +1,336 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::Serialize;
+use yara_x::{Compiler, Rules, Scanner, SourceCode};
+
+/// Rule files are picked up by extension from the rules folder
+pub const RULE_EXTENSIONS: [&str; 2] = ["yar", "yara"];
+
+// Bounds so a huge drive or a pathological rule can't stall the scan
+const MAX_DEPTH: usize = 16;
+const MAX_FILES: usize = 100_000;
+const MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+const FILE_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ERRORS: usize = 100;
+// Progress is reported every this many files
+const PROGRESS_INTERVAL: usize = 25;
+
+/// Compiled rules from the rules folder
+pub struct RuleSet {
+    pub rules: Rules,
+    /// Rule files that compiled
+    pub files: Vec<String>,
+    /// Compile errors, one per rejected file
+    pub errors: Vec<String>,
+}
+
+impl RuleSet {
+    pub fn rule_count(&self) -> usize {
+        self.rules.iter().len()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YaraMatch {
+    pub rule: String,
+    /// Rule file the rule comes from (file name without extension)
+    pub namespace: String,
+    pub tags: Vec<String>,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YaraProgress {
+    pub root: String,
+    pub files_scanned: usize,
+    pub matches: usize,
+    /// File being scanned, empty once the scan is done
+    pub current: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YaraScanReport {
+    pub root: String,
+    pub rules_loaded: usize,
+    /// Rule files the rules were compiled from
+    pub rule_files: Vec<String>,
+    pub files_scanned: usize,
+    /// Files too large to scan
+    pub files_skipped: usize,
+    /// True when the file or depth limit stopped the scan early
+    pub truncated: bool,
+    pub matches: Vec<YaraMatch>,
+    /// Files that couldn't be read or timed out
+    pub errors: Vec<String>,
+}
+
+/// Compiles every `.yar`/`.yara` file in a folder. Each file gets its own namespace so rule
+/// names can repeat across files; a file that doesn't compile is reported and skipped.
+pub fn compile_dir(dir: &Path) -> io::Result<RuleSet> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| RULE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    paths.sort();
+
+    let mut compiler = Compiler::new();
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                errors.push(format!("{}: {}", name, e));
+                continue;
+            }
+        };
+        let namespace = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        compiler.new_namespace(&namespace);
+        match compiler.add_source(SourceCode::from(source.as_str()).with_origin(name.as_str())) {
+            Ok(_) => files.push(name),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+
+    Ok(RuleSet { rules: compiler.build(), files, errors })
+}
+
+/// Files under `root` (or `root` itself when it is a file), not following symlinks,
+/// stopping after `max_files`
+fn collect_files(root: &Path, max_files: usize, truncated: &mut bool) -> io::Result<Vec<PathBuf>> {
+    if fs::metadata(root)?.is_file() {
+        return Ok(vec![root.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth + 1 < MAX_DEPTH {
+                    pending.push((entry.path(), depth + 1));
+                } else {
+                    *truncated = true;
+                }
+            } else if file_type.is_file() {
+                if files.len() >= max_files {
+                    *truncated = true;
+                    return Ok(files);
+                }
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Scans a file or folder with the compiled rules, reporting progress as it goes
+pub fn scan(rule_set: &RuleSet, root: &Path, mut progress: impl FnMut(&YaraProgress)) -> io::Result<YaraScanReport> {
+    let mut report = YaraScanReport {
+        root: root.to_string_lossy().to_string(),
+        rules_loaded: rule_set.rule_count(),
+        rule_files: rule_set.files.clone(),
+        files_scanned: 0,
+        files_skipped: 0,
+        truncated: false,
+        matches: Vec::new(),
+        errors: Vec::new(),
+    };
+    let files = collect_files(root, MAX_FILES, &mut report.truncated)?;
+
+    let mut scanner = Scanner::new(&rule_set.rules);
+    scanner.set_timeout(FILE_TIMEOUT);
+
+    for (i, path) in files.into_iter().enumerate() {
+        let display = path.to_string_lossy().to_string();
+        if i.is_multiple_of(PROGRESS_INTERVAL) {
+            progress(&YaraProgress {
+                root: report.root.clone(),
+                files_scanned: report.files_scanned,
+                matches: report.matches.len(),
+                current: display.clone(),
+                done: false,
+            });
+        }
+
+        if fs::metadata(&path).map(|m| m.len() > MAX_FILE_SIZE).unwrap_or(false) {
+            report.files_skipped += 1;
+            continue;
+        }
+
+        report.files_scanned += 1;
+        match scanner.scan_file(&path) {
+            Ok(results) => {
+                report.matches.extend(results.matching_rules().map(|rule| YaraMatch {
+                    rule: rule.identifier().to_string(),
+                    namespace: rule.namespace().to_string(),
+                    tags: rule.tags().map(|t| t.identifier().to_string()).collect(),
+                    path: display.clone(),
+                }));
+            }
+            Err(e) if report.errors.len() < MAX_ERRORS => report.errors.push(format!("{}: {}", display, e)),
+            Err(_) => {}
+        }
+    }
+
+    progress(&YaraProgress {
+        root: report.root.clone(),
+        files_scanned: report.files_scanned,
+        matches: report.matches.len(),
+        current: String::new(),
+        done: true,
+    });
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MARKER_RULE: &str = "rule marker : payload { strings: $a = \"SENTINEL-MARKER\" condition: $a }";
+
+    fn write(root: &Path, relative: &str, content: &str) -> PathBuf {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn rules(files: &[(&str, &str)]) -> RuleSet {
+        let dir = TempDir::new().unwrap();
+        for (name, source) in files {
+            write(dir.path(), name, source);
+        }
+        compile_dir(dir.path()).unwrap()
+    }
+
+    fn scan_quietly(rule_set: &RuleSet, root: &Path) -> YaraScanReport {
+        scan(rule_set, root, |_| {}).unwrap()
+    }
+
+    #[test]
+    fn broken_rule_files_are_reported_and_skipped() {
+        let rule_set = rules(&[
+            ("a_marker.yar", MARKER_RULE),
+            ("b_broken.yara", "rule broken { condition: undefined_identifier }"),
+            ("c_other.YARA", "rule other { condition: false }"),
+            ("notes.txt", "not a rule"),
+        ]);
+
+        assert_eq!(rule_set.files, vec!["a_marker.yar", "c_other.YARA"]);
+        assert_eq!(rule_set.errors.len(), 1);
+        assert!(rule_set.errors[0].contains("b_broken.yara"), "{}", rule_set.errors[0]);
+        assert_eq!(rule_set.rule_count(), 2);
+    }
+
+    #[test]
+    fn each_file_gets_its_own_namespace() {
+        let rule_set = rules(&[("first.yar", MARKER_RULE), ("second.yar", MARKER_RULE)]);
+        assert!(rule_set.errors.is_empty());
+        assert_eq!(rule_set.rule_count(), 2);
+
+        let target = TempDir::new().unwrap();
+        write(target.path(), "drop.bin", "xx SENTINEL-MARKER xx");
+        let report = scan_quietly(&rule_set, target.path());
+        let namespaces: Vec<&str> = report.matches.iter().map(|m| m.namespace.as_str()).collect();
+        assert_eq!(namespaces, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn matches_carry_the_rule_tags_and_path() {
+        let rule_set = rules(&[("markers.yar", MARKER_RULE)]);
+        let target = TempDir::new().unwrap();
+        let hit = write(target.path(), "docs/drop.bin", "xx SENTINEL-MARKER xx");
+        write(target.path(), "clean.txt", "nothing here");
+
+        let report = scan_quietly(&rule_set, target.path());
+        assert_eq!((report.rules_loaded, report.files_scanned, report.files_skipped), (1, 2, 0));
+        assert_eq!(report.rule_files, vec!["markers.yar"]);
+        assert_eq!(report.matches.len(), 1);
+        assert_eq!(report.matches[0].rule, "marker");
+        assert_eq!(report.matches[0].namespace, "markers");
+        assert_eq!(report.matches[0].tags, vec!["payload"]);
+        assert_eq!(report.matches[0].path, hit.to_string_lossy());
+
+        // A single file can be scanned too
+        let single = scan_quietly(&rule_set, &hit);
+        assert_eq!((single.files_scanned, single.matches.len()), (1, 1));
+    }
+
+    #[test]
+    fn oversized_files_are_skipped() {
+        let rule_set = rules(&[("markers.yar", MARKER_RULE)]);
+        let target = TempDir::new().unwrap();
+        let large = write(target.path(), "large.img", "SENTINEL-MARKER");
+        fs::OpenOptions::new().write(true).open(&large).unwrap().set_len(MAX_FILE_SIZE + 1).unwrap();
+        write(target.path(), "small.bin", "SENTINEL-MARKER");
+
+        let report = scan_quietly(&rule_set, target.path());
+        assert_eq!((report.files_scanned, report.files_skipped), (1, 1));
+        assert_eq!(report.matches.len(), 1);
+        assert!(report.matches[0].path.ends_with("small.bin"));
+    }
+
+    #[test]
+    fn deep_folders_and_file_limit_truncate() {
+        let target = TempDir::new().unwrap();
+        let deep: String = (0..MAX_DEPTH).map(|i| format!("d{}/", i)).collect();
+        write(target.path(), &format!("{}too_deep.bin", deep), "SENTINEL-MARKER");
+        write(target.path(), "top.bin", "");
+
+        let mut truncated = false;
+        let files = collect_files(target.path(), MAX_FILES, &mut truncated).unwrap();
+        assert!(truncated);
+        assert_eq!(files.len(), 1);
+        assert!(scan_quietly(&rules(&[("markers.yar", MARKER_RULE)]), target.path()).truncated);
+
+        let many = TempDir::new().unwrap();
+        for i in 0..5 {
+            write(many.path(), &format!("{}.bin", i), "");
+        }
+        let mut truncated = false;
+        assert_eq!(collect_files(many.path(), 3, &mut truncated).unwrap().len(), 3);
+        assert!(truncated);
+        let mut truncated = false;
+        assert_eq!(collect_files(many.path(), 5, &mut truncated).unwrap().len(), 5);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn progress_is_reported_and_ends_with_done() {
+        let rule_set = rules(&[("markers.yar", MARKER_RULE)]);
+        let target = TempDir::new().unwrap();
+        for i in 0..PROGRESS_INTERVAL + 5 {
+            write(target.path(), &format!("{:03}.bin", i), if i == 0 { "SENTINEL-MARKER" } else { "" });
+        }
+
+        let mut updates = Vec::new();
+        let report = scan(&rule_set, target.path(), |p| updates.push(p.clone())).unwrap();
+        assert_eq!(report.files_scanned, PROGRESS_INTERVAL + 5);
+
+        let scanned: Vec<usize> = updates.iter().map(|p| p.files_scanned).collect();
+        assert_eq!(scanned, vec![0, PROGRESS_INTERVAL, PROGRESS_INTERVAL + 5]);
+        assert!(updates[..2].iter().all(|p| !p.done && !p.current.is_empty()));
+        assert!(updates[1].current.ends_with(&format!("{:03}.bin", PROGRESS_INTERVAL)));
+        let last = updates.last().unwrap();
+        assert!(last.done);
+        assert_eq!((last.matches, last.current.as_str()), (1, ""));
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use tempfile::TempDir;
+use crate::services::backend;
+use crate::services::fixtures::FixtureBackend;
+use crate::services::storage;
+
+// Tests that install a backend or use the data directory change process-wide state
+static GLOBAL_STATE: Mutex<()> = Mutex::new(());
+
+/// A fixture backend installed as the current backend, with a fresh data directory.
+/// Other tests using `TestEnv` wait until this one is dropped.
+pub struct TestEnv {
+    pub backend: Arc<FixtureBackend>,
+    _data_dir: TempDir,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub fn new(fixtures: FixtureBackend) -> Self {
+        let guard = GLOBAL_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var(storage::DATA_DIR_ENV, data_dir.path());
+
+        let backend = Arc::new(fixtures);
+        backend::install(backend.clone());
+        Self { backend, _data_dir: data_dir, _guard: guard }
+    }
+}
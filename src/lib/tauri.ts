@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 // ============================================
 // Types (mirroring Rust models)
@@ -10,12 +11,97 @@ export interface UsbDevice {
     device_class: string;
     status: string;
     is_trusted: boolean;
+    vendor_id?: string | null;
+    product_id?: string | null;
+    serial?: string | null;
+    interface_classes?: number[];
+    interfaces?: string[]; // "cc:ss:pp", e.g. "08:06:50"
+    policy_action?: PolicyAction | null;
+    risk_flags?: RiskFlag[];
+    capture?: CaptureKind[]; // cameras and microphones
+    bus_type?: string | null; // "USB", "Bluetooth", "Thunderbolt", ...
+    parent_id?: string | null;
+    children?: UsbDevice[]; // functions of a composite device
+}
+
+export type CaptureKind = "camera" | "microphone";
+
+export type RiskFlag = "storage_with_hid" | "network_with_hid" | "programmable_board_keyboard";
+
+export type PolicyAction = "allow" | "prompt" | "block";
+
+// Matches a USB interface class code (e.g. 0x08 mass storage) or a device class name
+export interface ClassRule {
+    class_code?: number;
+    device_class?: string;
+    action: PolicyAction;
+    label: string;
+}
+
+export interface DevicePolicy {
+    class_rules: ClassRule[];
+    default_action: PolicyAction;
 }
 
 export interface WhitelistEntry {
     instance_id: string;
     friendly_name: string;
     added_at: string;
+    vendor_id?: string;
+    product_id?: string;
+    serial?: string;
+    name?: string;
+    with_interface?: InterfaceMatch;
+    expires_at?: string;
+    allowed_windows?: TimeWindow[];
+    owner?: string;
+    asset_tag?: string;
+    notes?: string;
+    tags?: string[];
+    added_by?: string;
+}
+
+// Unset fields are cleared; an unset friendly_name keeps the current name
+export interface WhitelistMetadata {
+    friendly_name?: string;
+    owner?: string;
+    asset_tag?: string;
+    notes?: string;
+    tags?: string[];
+}
+
+export interface WhitelistQuery {
+    text?: string;
+    owner?: string;
+    asset_tag?: string;
+    tag?: string;
+    added_by?: string;
+}
+
+export type SetOperator = "all-of" | "one-of" | "none-of" | "equals" | "equals-ordered";
+
+// USBGuard with-interface condition; patterns are "cc:ss:pp" where any part may be "*"
+export interface InterfaceMatch {
+    operator: SetOperator;
+    patterns: string[];
+}
+
+export interface SkippedRule {
+    line: number;
+    rule: string;
+    reason: string;
+}
+
+export interface UsbGuardImport {
+    imported: number;
+    skipped: SkippedRule[];
+}
+
+// Local-time window; days are "mon".."sun", "weekdays" or "weekends" (empty = every day)
+export interface TimeWindow {
+    days: string[];
+    start: string; // "HH:MM"
+    end: string; // "HH:MM"
 }
 
 export interface EventLog {
@@ -26,10 +112,247 @@ export interface EventLog {
     device_id?: string;
 }
 
+export type EnforcementMode = "monitor" | "prompt" | "enforce";
+
+export interface Settings {
+    enforcement_mode: EnforcementMode;
+    block_keystroke_injection: boolean;
+    approval_timeout_secs: number;
+    bundle_public_key?: string | null;
+    encryption_requirement: EncryptionRequirement;
+    block_suspicious_media: boolean;
+    privacy_mode: PrivacyMode;
+}
+
+// Keeps cameras and microphones disabled outside the allowed windows
+export interface PrivacyMode {
+    enabled: boolean;
+    allowed_windows: TimeWindow[];
+}
+
+export type EncryptionRequirement = "off" | "warn" | "block";
+
+// A partition's number and its start on the disk in bytes
+export interface DiskVolume {
+    partition: number;
+    offset: number;
+}
+
+export type VolumeEncryption = "luks" | "bitlocker" | "veracrypt" | "none";
+
+export interface VolumeReport {
+    partition: number | null; // null when the volume spans the whole disk
+    offset: number;
+    encryption: VolumeEncryption;
+    filesystem: string | null;
+}
+
+export interface EncryptionReport {
+    path: string;
+    volumes: VolumeReport[];
+    encrypted: boolean;
+}
+
+export interface UnencryptedStorageAlert {
+    device: UsbDevice;
+    volumes: string[];
+}
+
+export type TableKind = "mbr" | "gpt" | "none";
+
+export interface Partition {
+    index: number;
+    start: number;
+    size: number;
+    type_id: string; // MBR type byte ("0x07") or GPT type GUID
+    type_name?: string;
+    name?: string;
+    attributes: number;
+    filesystem?: string;
+}
+
+export type PartitionAnomaly =
+    | "hidden_partition"
+    | "beyond_disk_end"
+    | "overlapping_partitions"
+    | "filesystem_size_mismatch"
+    | "cdrom_emulation";
+
+export interface AnomalyFinding {
+    anomaly: PartitionAnomaly;
+    partition: number | null; // null for the whole disk
+    detail: string;
+}
+
+export interface DiskLayout {
+    path: string;
+    inspected_at: string;
+    size: number | null;
+    table: TableKind;
+    partitions: Partition[];
+    anomalies: AnomalyFinding[];
+}
+
+export interface PartitionAnomalyAlert {
+    device: UsbDevice;
+    layout: DiskLayout;
+}
+
+export type MediaFindingKind = "autorun" | "executable_shortcut" | "double_extension" | "root_script";
+
+export type FindingSeverity = "medium" | "high";
+
+export interface MediaFinding {
+    kind: MediaFindingKind;
+    severity: FindingSeverity;
+    path: string;
+    detail: string;
+}
+
+export interface MediaScanReport {
+    root: string;
+    files_scanned: number;
+    truncated: boolean;
+    findings: MediaFinding[];
+}
+
+export interface SuspiciousMediaAlert {
+    device: UsbDevice;
+    report: MediaScanReport;
+}
+
+export interface YaraMatch {
+    rule: string;
+    namespace: string; // rule file name without extension
+    tags: string[];
+    path: string;
+}
+
+export interface YaraProgress {
+    root: string;
+    files_scanned: number;
+    matches: number;
+    current: string;
+    done: boolean;
+}
+
+export interface YaraScanReport {
+    root: string;
+    rules_loaded: number;
+    rule_files: string[];
+    files_scanned: number;
+    files_skipped: number;
+    truncated: boolean;
+    matches: YaraMatch[];
+    errors: string[];
+}
+
+export interface YaraMatchAlert {
+    device: UsbDevice | null; // null for on-demand scans
+    match: YaraMatch;
+}
+
+// Same trust fields as a USB device (serial is the address), so it can be passed to addToWhitelist
+export interface BluetoothDevice extends UsbDevice {
+    address: string; // "AA:BB:CC:DD:EE:FF"
+    class_of_device: number | null; // null for Low Energy devices
+    paired: boolean;
+    connected: boolean;
+    blocked: boolean;
+}
+
+export type ModuleAction = "flag" | "unload";
+
+// A loaded kernel module (Linux) or running kernel driver (Windows)
+export interface KernelModule {
+    name: string;
+    size: number | null;
+    used_by: string[];
+    state: string | null;
+    description: string | null; // Windows display name
+    path: string | null; // Windows driver file
+    in_baseline: boolean;
+    denied: ModuleAction | null;
+}
+
+export interface ModuleDenylistEntry {
+    name: string;
+    action: ModuleAction;
+    added_at: string;
+}
+
+export interface KernelModuleDeniedAlert {
+    module: KernelModule;
+    unloaded: boolean;
+}
+
+export type TrustDecisionKind =
+    | "trusted"
+    | "untrusted"
+    | "enabled"
+    | "blocked"
+    | "approved"
+    | "rejected"
+    | "timed_out";
+
+export interface TrustDecision {
+    timestamp: string;
+    decision: TrustDecisionKind;
+    user: string;
+}
+
+export interface ConnectionRecord {
+    connected_at: string;
+    disconnected_at?: string;
+    user: string;
+}
+
+export interface InventoryRecord {
+    instance_id: string;
+    friendly_name: string;
+    device_class: string;
+    vendor_id?: string;
+    product_id?: string;
+    serial?: string;
+    first_seen: string;
+    last_seen: string;
+    connection_count: number;
+    connections: ConnectionRecord[];
+    decisions: TrustDecision[];
+    disk_layouts?: DiskLayout[];
+}
+
+export interface InventoryQuery {
+    search?: string;
+    device_class?: string;
+    seen_since?: string;
+    seen_until?: string;
+}
+
+export interface PendingDevice {
+    device: UsbDevice;
+    requested_at: string;
+    expires_at: string;
+}
+
+export interface CadenceReport {
+    keystrokes: number;
+    mean_interval_ms: number;
+    stddev_interval_ms: number;
+    score: number;
+    injection_suspected: boolean;
+}
+
+export interface KeystrokeInjectionAlert {
+    device: UsbDevice;
+    report: CadenceReport;
+}
+
 export interface DashboardStats {
     total_devices: number;
     trusted_devices: number;
     blocked_devices: number;
+    pending_devices: number;
     total_scans: number;
     blocked_threats: number;
     is_secure: boolean;
@@ -76,9 +399,10 @@ export interface SystemInfo {
 const isTauri = () => typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
 
 // Device Commands
-export async function getConnectedDevices(): Promise<UsbDevice[]> {
+// Optionally limited to device classes such as "DiskDrive", "Camera" or "Bluetooth"
+export async function getConnectedDevices(classes?: string[]): Promise<UsbDevice[]> {
     if (!isTauri()) return [];
-    return invoke<UsbDevice[]>("get_connected_devices");
+    return invoke<UsbDevice[]>("get_connected_devices", { classes });
 }
 
 export async function enableDevice(instanceId: string): Promise<void> {
@@ -91,12 +415,18 @@ export async function disableDevice(instanceId: string): Promise<void> {
     return invoke("disable_device", { instanceId });
 }
 
+export async function setDefaultDeny(enabled: boolean): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_default_deny", { enabled });
+}
+
 export async function getDashboardStats(): Promise<DashboardStats> {
     if (!isTauri()) {
         return {
             total_devices: 0,
             trusted_devices: 0,
             blocked_devices: 0,
+            pending_devices: 0,
             total_scans: 0,
             blocked_threats: 0,
             is_secure: true,
@@ -105,15 +435,66 @@ export async function getDashboardStats(): Promise<DashboardStats> {
     return invoke<DashboardStats>("get_dashboard_stats");
 }
 
+// Device Events (emitted by the background USB monitor)
+export async function onDeviceConnected(handler: (device: UsbDevice) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<UsbDevice>("device-connected", (event) => handler(event.payload));
+}
+
+export async function onDeviceRemoved(handler: (device: UsbDevice) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<UsbDevice>("device-removed", (event) => handler(event.payload));
+}
+
+export async function onDeviceRiskDetected(handler: (device: UsbDevice) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<UsbDevice>("device-risk-detected", (event) => handler(event.payload));
+}
+
+export async function onDeviceApprovalRequired(handler: (pending: PendingDevice) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<PendingDevice>("device-approval-required", (event) => handler(event.payload));
+}
+
+export async function onUnencryptedStorageDetected(handler: (alert: UnencryptedStorageAlert) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<UnencryptedStorageAlert>("unencrypted-storage-detected", (event) => handler(event.payload));
+}
+
+export async function onBluetoothDevicePaired(handler: (device: BluetoothDevice) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<BluetoothDevice>("bluetooth-device-paired", (event) => handler(event.payload));
+}
+
+export async function onKeystrokeInjectionDetected(handler: (alert: KeystrokeInjectionAlert) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<KeystrokeInjectionAlert>("keystroke-injection-detected", (event) => handler(event.payload));
+}
+
 // Whitelist Commands
 export async function getWhitelist(): Promise<WhitelistEntry[]> {
     if (!isTauri()) return [];
     return invoke<WhitelistEntry[]>("get_whitelist");
 }
 
-export async function addToWhitelist(device: UsbDevice): Promise<void> {
+export async function addToWhitelist(
+    device: UsbDevice,
+    expiresAt?: string,
+    allowedWindows?: TimeWindow[]
+): Promise<void> {
     if (!isTauri()) return;
-    return invoke("add_to_whitelist", { device });
+    return invoke("add_to_whitelist", { device, expiresAt, allowedWindows });
+}
+
+// spec is "VID", "VID:PID" or "VID:PID:SERIAL", e.g. "1050:0407"
+export async function addWhitelistRule(
+    spec: string,
+    friendlyName: string,
+    expiresAt?: string,
+    allowedWindows?: TimeWindow[]
+): Promise<WhitelistEntry | null> {
+    if (!isTauri()) return null;
+    return invoke<WhitelistEntry>("add_whitelist_rule", { spec, friendlyName, expiresAt, allowedWindows });
 }
 
 export async function removeFromWhitelist(instanceId: string): Promise<void> {
@@ -126,6 +507,54 @@ export async function clearWhitelist(): Promise<void> {
     return invoke("clear_whitelist");
 }
 
+// Signs the whitelist and device policy with an Ed25519 private key (PEM or base64 seed); returns the bundle path
+export async function exportPolicyBundle(signingKey: string): Promise<string> {
+    if (!isTauri()) return "";
+    return invoke<string>("export_policy_bundle", { signingKey });
+}
+
+// Replaces the whitelist and device policy after verifying the bundle against the configured public key
+export async function importPolicyBundle(bundle: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("import_policy_bundle", { bundle });
+}
+
+export async function updateWhitelistEntry(instanceId: string, metadata: WhitelistMetadata): Promise<WhitelistEntry | null> {
+    if (!isTauri()) return null;
+    return invoke<WhitelistEntry>("update_whitelist_entry", { instanceId, metadata });
+}
+
+export async function searchWhitelist(query: WhitelistQuery): Promise<WhitelistEntry[]> {
+    if (!isTauri()) return [];
+    return invoke<WhitelistEntry[]>("search_whitelist", { query });
+}
+
+export async function removeWhitelistByTag(tag: string): Promise<number> {
+    if (!isTauri()) return 0;
+    return invoke<number>("remove_whitelist_by_tag", { tag });
+}
+
+export async function importUsbguardRules(rules: string): Promise<UsbGuardImport> {
+    if (!isTauri()) return { imported: 0, skipped: [] };
+    return invoke<UsbGuardImport>("import_usbguard_rules", { rules });
+}
+
+export async function exportUsbguardRules(): Promise<string> {
+    if (!isTauri()) return "";
+    return invoke<string>("export_usbguard_rules");
+}
+
+// Policy Commands
+export async function getDevicePolicy(): Promise<DevicePolicy> {
+    if (!isTauri()) return { class_rules: [], default_action: "block" };
+    return invoke<DevicePolicy>("get_device_policy");
+}
+
+export async function setDevicePolicy(policy: DevicePolicy): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_device_policy", { policy });
+}
+
 // Log Commands
 export async function getEventLogs(): Promise<EventLog[]> {
     if (!isTauri()) return [];
@@ -343,16 +772,246 @@ export async function startService(serviceName: string): Promise<void> {
     return invoke("start_service", { serviceName });
 }
 
+// ============================================
+// Settings Commands
+// ============================================
+
+export async function getSettings(): Promise<Settings> {
+    if (!isTauri()) return { enforcement_mode: "monitor", block_keystroke_injection: false, approval_timeout_secs: 300, bundle_public_key: null, encryption_requirement: "off", block_suspicious_media: false, privacy_mode: { enabled: false, allowed_windows: [] } };
+    return invoke<Settings>("get_settings");
+}
+
+export async function setEnforcementMode(mode: EnforcementMode): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_enforcement_mode", { mode });
+}
+
+export async function setKeystrokeInjectionBlocking(enabled: boolean): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_keystroke_injection_blocking", { enabled });
+}
+
+export async function setApprovalTimeout(seconds: number): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_approval_timeout", { seconds });
+}
+
+export async function setEncryptionRequirement(requirement: EncryptionRequirement): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_encryption_requirement", { requirement });
+}
+
+export async function setSuspiciousMediaBlocking(enabled: boolean): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_suspicious_media_blocking", { enabled });
+}
+
+export async function setPrivacyMode(enabled: boolean, allowedWindows?: TimeWindow[]): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_privacy_mode", { enabled, allowedWindows });
+}
+
+// Ed25519 public key as PEM or base64; null disables bundle imports
+export async function setBundlePublicKey(publicKey: string | null): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("set_bundle_public_key", { publicKey });
+}
+
+// ============================================
+// Approval Queue Commands
+// ============================================
+
+export async function getPendingDevices(): Promise<PendingDevice[]> {
+    if (!isTauri()) return [];
+    return invoke<PendingDevice[]>("get_pending_devices");
+}
+
+export async function approveDevice(instanceId: string, addToWhitelist: boolean, expiresAt?: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("approve_device", { instanceId, addToWhitelist, expiresAt });
+}
+
+export async function rejectDevice(instanceId: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("reject_device", { instanceId });
+}
+
+// ============================================
+// Storage Encryption Commands
+// ============================================
+
+// Accepts a block device (/dev/sdb, \\.\PhysicalDrive2) or a disk image path; without
+// volumes the whole device is inspected as one volume
+export async function checkEncryption(path: string, volumes?: DiskVolume[]): Promise<EncryptionReport | null> {
+    if (!isTauri()) return null;
+    return invoke<EncryptionReport>("check_encryption", { path, volumes: volumes ?? null });
+}
+
+// ============================================
+// Partition Inspection Commands
+// ============================================
+
+// Accepts a block device (/dev/sdb, \\.\PhysicalDrive2) or a disk image path
+export async function inspectPartitions(path: string): Promise<DiskLayout | null> {
+    if (!isTauri()) return null;
+    return invoke<DiskLayout>("inspect_partitions", { path });
+}
+
+// ============================================
+// Removable Media Scan Commands
+// ============================================
+
+export async function scanMedia(path: string): Promise<MediaScanReport | null> {
+    if (!isTauri()) return null;
+    return invoke<MediaScanReport>("scan_media", { path });
+}
+
+// ============================================
+// YARA Commands
+// ============================================
+
+export async function getYaraRulesDir(): Promise<string | null> {
+    if (!isTauri()) return null;
+    return invoke<string>("get_yara_rules_dir");
+}
+
+// Progress arrives as "yara-scan-progress" events while the scan runs
+export async function scanWithYara(path: string): Promise<YaraScanReport | null> {
+    if (!isTauri()) return null;
+    return invoke<YaraScanReport>("scan_with_yara", { path });
+}
+
+// ============================================
+// Bluetooth Commands
+// ============================================
+
+export async function getBluetoothDevices(): Promise<BluetoothDevice[]> {
+    if (!isTauri()) return [];
+    return invoke<BluetoothDevice[]>("get_bluetooth_devices");
+}
+
+export async function unpairBluetoothDevice(address: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("unpair_bluetooth_device", { address });
+}
+
+export async function blockBluetoothDevice(address: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("block_bluetooth_device", { address });
+}
+
+export async function unblockBluetoothDevice(address: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("unblock_bluetooth_device", { address });
+}
+
+// ============================================
+// Camera and Microphone Commands
+// ============================================
+
+export async function getCaptureDevices(): Promise<UsbDevice[]> {
+    if (!isTauri()) return [];
+    return invoke<UsbDevice[]>("get_capture_devices");
+}
+
+// Resolve to the instance IDs whose state changed
+export async function disableCaptureDevices(): Promise<string[]> {
+    if (!isTauri()) return [];
+    return invoke<string[]>("disable_capture_devices");
+}
+
+export async function enableCaptureDevices(): Promise<string[]> {
+    if (!isTauri()) return [];
+    return invoke<string[]>("enable_capture_devices");
+}
+
+export async function onPrivacyModeChanged(handler: (active: boolean) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<{ active: boolean }>("privacy-mode-changed", (event) => handler(event.payload.active));
+}
+
+// ============================================
+// Kernel Module Commands
+// ============================================
+
+export async function getKernelModules(): Promise<KernelModule[]> {
+    if (!isTauri()) return [];
+    return invoke<KernelModule[]>("get_kernel_modules");
+}
+
+// Resolves to the number of modules in the new baseline
+export async function resetModuleBaseline(): Promise<number> {
+    if (!isTauri()) return 0;
+    return invoke<number>("reset_module_baseline");
+}
+
+export async function getModuleDenylist(): Promise<ModuleDenylistEntry[]> {
+    if (!isTauri()) return [];
+    return invoke<ModuleDenylistEntry[]>("get_module_denylist");
+}
+
+export async function addModuleDenylistEntry(name: string, action: ModuleAction): Promise<ModuleDenylistEntry | null> {
+    if (!isTauri()) return null;
+    return invoke<ModuleDenylistEntry>("add_module_denylist_entry", { name, action });
+}
+
+export async function removeModuleDenylistEntry(name: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("remove_module_denylist_entry", { name });
+}
+
+export async function unloadKernelModule(name: string): Promise<void> {
+    if (!isTauri()) return;
+    return invoke("unload_kernel_module", { name });
+}
+
+export async function onKernelModuleLoaded(handler: (module: KernelModule) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<KernelModule>("kernel-module-loaded", (event) => handler(event.payload));
+}
+
+export async function onKernelModuleDenied(handler: (alert: KernelModuleDeniedAlert) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return listen<KernelModuleDeniedAlert>("kernel-module-denied", (event) => handler(event.payload));
+}
+
+// ============================================
+// Inventory Commands
+// ============================================
+
+export async function getDeviceInventory(query?: InventoryQuery): Promise<InventoryRecord[]> {
+    if (!isTauri()) return [];
+    return invoke<InventoryRecord[]>("get_device_inventory", { query });
+}
+
 // Convenience API object
 export const api = {
     getConnectedDevices,
     enableDevice,
     disableDevice,
+    setDefaultDeny,
     getDashboardStats,
+    onDeviceConnected,
+    onDeviceRemoved,
+    onDeviceApprovalRequired,
+    onDeviceRiskDetected,
+    onKeystrokeInjectionDetected,
+    onUnencryptedStorageDetected,
+    onBluetoothDevicePaired,
     getWhitelist,
     addToWhitelist,
+    addWhitelistRule,
     removeFromWhitelist,
     clearWhitelist,
+    updateWhitelistEntry,
+    searchWhitelist,
+    removeWhitelistByTag,
+    importUsbguardRules,
+    exportUsbguardRules,
+    exportPolicyBundle,
+    importPolicyBundle,
+    getDevicePolicy,
+    setDevicePolicy,
     getEventLogs,
     addEventLog,
     clearLogs,
@@ -376,4 +1035,37 @@ export const api = {
     getCriticalServices,
     restartService,
     startService,
+    getSettings,
+    setEnforcementMode,
+    setKeystrokeInjectionBlocking,
+    setApprovalTimeout,
+    setBundlePublicKey,
+    setEncryptionRequirement,
+    setSuspiciousMediaBlocking,
+    setPrivacyMode,
+    getPendingDevices,
+    approveDevice,
+    rejectDevice,
+    getDeviceInventory,
+    checkEncryption,
+    inspectPartitions,
+    scanMedia,
+    getYaraRulesDir,
+    scanWithYara,
+    getBluetoothDevices,
+    unpairBluetoothDevice,
+    blockBluetoothDevice,
+    unblockBluetoothDevice,
+    getCaptureDevices,
+    disableCaptureDevices,
+    enableCaptureDevices,
+    onPrivacyModeChanged,
+    getKernelModules,
+    resetModuleBaseline,
+    getModuleDenylist,
+    addModuleDenylistEntry,
+    removeModuleDenylistEntry,
+    unloadKernelModule,
+    onKernelModuleLoaded,
+    onKernelModuleDenied,
 };